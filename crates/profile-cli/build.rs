@@ -0,0 +1,33 @@
+//! Writes the shell completions and the man page for packaging, when
+//! `PROFILE_GEN_COMPLETIONS_DIR` names a directory:
+//! `PROFILE_GEN_COMPLETIONS_DIR=pkg/share cargo build --release`.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/completions.rs"]
+mod completions;
+
+use completions::{completions, man_page, Shell, BIN};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/completions.rs");
+    println!("cargo:rerun-if-env-changed=PROFILE_GEN_COMPLETIONS_DIR");
+    let Some(dir) = env::var_os("PROFILE_GEN_COMPLETIONS_DIR") else {
+        return;
+    };
+    let dir = Path::new(&dir);
+    fs::create_dir_all(dir).expect("Failed to create the completions directory");
+    // The names bash-completion, zsh and fish look for, and the man page section.
+    let files = [
+        (format!("{}.bash", BIN), completions(Shell::Bash)),
+        (format!("_{}", BIN), completions(Shell::Zsh)),
+        (format!("{}.fish", BIN), completions(Shell::Fish)),
+        (format!("{}.1", BIN), man_page()),
+    ];
+    for (name, contents) in files {
+        fs::write(dir.join(name), contents).expect("Failed to write a completion script");
+    }
+}
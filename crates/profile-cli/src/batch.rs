@@ -0,0 +1,136 @@
+use profile_core::anomaly::take_anomalies;
+use profile_core::cancel::is_cancelled;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// One profile to generate: whose, and where its README goes.
+pub struct BatchEntry {
+    pub username: String,
+    pub output: PathBuf,
+}
+
+/// Parses a batch file of `<username> <output path>` lines; blank lines and `#` comments are
+/// skipped.
+pub fn parse_batch_file(contents: &str) -> Result<Vec<BatchEntry>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.split('#').next().unwrap_or("").trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(
+            |(number, line)| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [username, output] => Ok(BatchEntry {
+                    username: username.to_string(),
+                    output: PathBuf::from(output),
+                }),
+                _ => Err(format!(
+                    "line {}: expected `<username> <output path>`, got `{}`",
+                    number, line
+                )),
+            },
+        )
+        .collect()
+}
+
+/// What a panic was raised with, when it was a message.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".to_string())
+}
+
+/// Runs `generate` for every entry on up to `jobs` threads and returns the failures as
+/// `(username, error)` pairs in batch file order. An entry that panics fails on its own; the
+/// others still run.
+pub fn run_batch<F>(entries: &[BatchEntry], jobs: usize, generate: F) -> Vec<(String, String)>
+where
+    F: Fn(&BatchEntry) -> Result<(), String> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let failures = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, entries.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(entry) = entries
+                    .get(next.fetch_add(1, Ordering::Relaxed))
+                    .filter(|_| !is_cancelled())
+                {
+                    // An entry that failed early never took its anomalies; they aren't this one's.
+                    take_anomalies();
+                    let result = catch_unwind(AssertUnwindSafe(|| generate(entry))).unwrap_or_else(
+                        |payload| Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+                    );
+                    if let Err(e) = result {
+                        failures.lock().unwrap().push((entry.username.clone(), e));
+                    }
+                }
+            });
+        }
+    });
+
+    let mut failures = failures.into_inner().unwrap();
+    failures
+        .sort_by_key(|(username, _)| entries.iter().position(|entry| &entry.username == username));
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use profile_core::anomaly::report_anomaly;
+
+    fn entry(username: &str) -> BatchEntry {
+        BatchEntry {
+            username: username.to_string(),
+            output: PathBuf::from(format!("{}.md", username)),
+        }
+    }
+
+    #[test]
+    fn failed_entry_leaves_no_anomalies_for_the_next() {
+        let entries = [entry("broken"), entry("clean")];
+        // One thread, so the clean entry runs where the broken one left off.
+        let failures = run_batch(&entries, 1, |entry| {
+            if entry.username == "broken" {
+                report_anomaly("missing field");
+                return Err("request failed".to_string());
+            }
+            // What `--strict` checks before writing.
+            match take_anomalies() {
+                anomalies if anomalies.is_empty() => Ok(()),
+                anomalies => Err(format!("strict mode: {}", anomalies.join(", "))),
+            }
+        });
+        assert_eq!(
+            failures,
+            [("broken".to_string(), "request failed".to_string())]
+        );
+    }
+
+    #[test]
+    fn panicking_entry_fails_alone() {
+        let entries = [entry("first"), entry("typo"), entry("last")];
+        let done = Mutex::new(Vec::new());
+        let failures = run_batch(&entries, 2, |entry| {
+            if entry.username == "typo" {
+                panic!("user not found");
+            }
+            done.lock().unwrap().push(entry.username.clone());
+            Ok(())
+        });
+        assert_eq!(
+            failures,
+            [("typo".to_string(), "panicked: user not found".to_string())]
+        );
+        let mut done = done.into_inner().unwrap();
+        done.sort();
+        assert_eq!(done, ["first", "last"]);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::completions::Shell;
+use chrono::NaiveDate;
+use profile_core::import::Source;
+use profile_core::status::{Status, StatusState};
+
+/// Where `demo` writes its cast unless `--output` says otherwise.
+const DEFAULT_DEMO_OUTPUT: &str = "demo.cast";
+
+/// Width of `art` output in braille cells, about that of the built-in header figure.
+const DEFAULT_ART_COLUMNS: usize = 36;
+
+/// Profiles generated at once in batch mode, unless `--jobs` says otherwise.
+const DEFAULT_BATCH_JOBS: usize = 4;
+
+pub enum Command {
+    Generate {
+        strict: bool,
+        canary: bool,
+    },
+    Batch {
+        file: String,
+        jobs: usize,
+        strict: bool,
+    },
+    Export {
+        anonymize: bool,
+        output: Option<String>,
+    },
+    Cv {
+        output: Option<String>,
+    },
+    Backfill {
+        since: i32,
+    },
+    Demo {
+        output: String,
+    },
+    #[cfg_attr(not(feature = "image"), allow(dead_code))]
+    Art {
+        picture: String,
+        columns: usize,
+    },
+    ConfigSchema,
+    ConfigImport {
+        source: Source,
+        file: String,
+    },
+    Tui,
+    StatusSet(Status),
+    StatusClear,
+    Completions(Shell),
+    Man,
+}
+
+fn parse_status_set(args: &[String]) -> Result<Status, String> {
+    let (state, flags) = args.split_first().ok_or("missing status")?;
+    let state = StatusState::parse(state).ok_or_else(|| format!("unknown status `{}`", state))?;
+
+    let mut status = Status {
+        state,
+        message: None,
+        until: None,
+    };
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        let value = flags
+            .next()
+            .ok_or_else(|| format!("missing value for `{}`", flag))?;
+        match flag.as_str() {
+            "--message" => status.message = Some(value.clone()),
+            "--until" => {
+                status.until = Some(
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .map_err(|_| format!("invalid date `{}`", value))?,
+                )
+            }
+            _ => return Err(format!("unknown flag `{}`", flag)),
+        }
+    }
+    Ok(status)
+}
+
+fn parse_batch(file: &str, flags: &[String]) -> Result<Command, String> {
+    let mut jobs = DEFAULT_BATCH_JOBS;
+    let mut strict = false;
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--strict" => strict = true,
+            "--jobs" => {
+                let value = flags
+                    .next()
+                    .ok_or_else(|| format!("missing value for `{}`", flag))?;
+                jobs = value
+                    .parse()
+                    .ok()
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or_else(|| format!("invalid job count `{}`", value))?;
+            }
+            _ => return Err(format!("unknown flag `{}`", flag)),
+        }
+    }
+    Ok(Command::Batch {
+        file: file.to_string(),
+        jobs,
+        strict,
+    })
+}
+
+fn parse_generate(flags: &[String]) -> Result<Command, String> {
+    let (mut strict, mut canary) = (false, false);
+    for flag in flags {
+        match flag.as_str() {
+            "--strict" => strict = true,
+            "--canary" => canary = true,
+            _ => return Err(format!("unknown flag `{}`", flag)),
+        }
+    }
+    Ok(Command::Generate { strict, canary })
+}
+
+fn parse_export(flags: &[String]) -> Result<Command, String> {
+    let mut anonymize = false;
+    let mut output = None;
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--anonymize" => anonymize = true,
+            "--output" => {
+                output = Some(
+                    flags
+                        .next()
+                        .ok_or_else(|| format!("missing value for `{}`", flag))?
+                        .clone(),
+                )
+            }
+            _ => return Err(format!("unknown flag `{}`", flag)),
+        }
+    }
+    Ok(Command::Export { anonymize, output })
+}
+
+fn parse_cv(flags: &[String]) -> Result<Command, String> {
+    match flags {
+        [] => Ok(Command::Cv { output: None }),
+        [flag, path] if flag == "--output" => Ok(Command::Cv {
+            output: Some(path.clone()),
+        }),
+        _ => Err("usage: cv [--output <path>]".to_string()),
+    }
+}
+
+fn parse_backfill(flags: &[String]) -> Result<Command, String> {
+    match flags {
+        [flag, year] if flag == "--since" => year
+            .parse()
+            .map(|since| Command::Backfill { since })
+            .map_err(|_| format!("invalid year `{}`", year)),
+        _ => Err("usage: backfill --since <year>".to_string()),
+    }
+}
+
+fn parse_art(picture: &str, flags: &[String]) -> Result<Command, String> {
+    let mut columns = DEFAULT_ART_COLUMNS;
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--width" => {
+                let value = flags
+                    .next()
+                    .ok_or_else(|| format!("missing value for `{}`", flag))?;
+                columns = value
+                    .parse()
+                    .ok()
+                    .filter(|columns| *columns > 0)
+                    .ok_or_else(|| format!("invalid width `{}`", value))?;
+            }
+            _ => return Err(format!("unknown flag `{}`", flag)),
+        }
+    }
+    Ok(Command::Art {
+        picture: picture.to_string(),
+        columns,
+    })
+}
+
+fn parse_demo(flags: &[String]) -> Result<Command, String> {
+    let mut output = DEFAULT_DEMO_OUTPUT.to_string();
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--output" => {
+                output = flags
+                    .next()
+                    .ok_or_else(|| format!("missing value for `{}`", flag))?
+                    .clone()
+            }
+            _ => return Err(format!("unknown flag `{}`", flag)),
+        }
+    }
+    Ok(Command::Demo { output })
+}
+
+/// Options every command takes, wherever they appear on the command line.
+#[derive(Default)]
+pub struct GlobalOptions {
+    /// `--profile`: the `[profile.<name>]` table to apply.
+    pub profile: Option<String>,
+    /// `--config`: the config file, overriding the usual lookup.
+    pub config: Option<String>,
+    /// `--config-sha256`: the checksum the config file must have.
+    pub config_sha256: Option<String>,
+    /// `--record`: the directory to save the run's responses to.
+    pub record: Option<String>,
+    /// `--replay`: the directory of a recording to answer from.
+    pub replay: Option<String>,
+}
+
+fn split_global_options(args: &[String]) -> Result<(Vec<String>, GlobalOptions), String> {
+    let mut rest = Vec::new();
+    let mut options = GlobalOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let option = match arg.as_str() {
+            "--profile" => &mut options.profile,
+            "--config" => &mut options.config,
+            "--config-sha256" => &mut options.config_sha256,
+            "--record" => &mut options.record,
+            "--replay" => &mut options.replay,
+            _ => {
+                rest.push(arg.clone());
+                continue;
+            }
+        };
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for `{}`", arg))?;
+        *option = Some(value.clone());
+    }
+    if options.record.is_some() && options.replay.is_some() {
+        return Err("--record and --replay can't be combined".to_string());
+    }
+    Ok((rest, options))
+}
+
+/// Parses the arguments after the program name into the command and the global options.
+pub fn parse_args(args: &[String]) -> Result<(Command, GlobalOptions), String> {
+    let (args, options) = split_global_options(args)?;
+    parse_command(&args).map(|command| (command, options))
+}
+
+fn parse_command(args: &[String]) -> Result<Command, String> {
+    match args {
+        [] => Ok(Command::Generate {
+            strict: false,
+            canary: false,
+        }),
+        [flag, ..] if flag.starts_with("--") => parse_generate(args),
+        [command, file, rest @ ..] if command == "batch" => parse_batch(file, rest),
+        [command, rest @ ..] if command == "export" => parse_export(rest),
+        [command, rest @ ..] if command == "cv" => parse_cv(rest),
+        [command, rest @ ..] if command == "backfill" => parse_backfill(rest),
+        [command, rest @ ..] if command == "demo" => parse_demo(rest),
+        [command, picture, rest @ ..] if command == "art" => parse_art(picture, rest),
+        [command, subcommand] if command == "config" && subcommand == "schema" => {
+            Ok(Command::ConfigSchema)
+        }
+        [command, subcommand, source, file] if command == "config" && subcommand == "import" => {
+            Ok(Command::ConfigImport {
+                source: Source::parse(source)
+                    .ok_or_else(|| format!("unknown import source `{}`", source))?,
+                file: file.clone(),
+            })
+        }
+        [command] if command == "tui" => Ok(Command::Tui),
+        [command, subcommand, rest @ ..] if command == "status" && subcommand == "set" => {
+            parse_status_set(rest).map(Command::StatusSet)
+        }
+        [command, subcommand] if command == "status" && subcommand == "clear" => {
+            Ok(Command::StatusClear)
+        }
+        [command, shell] if command == "completions" => Shell::parse(shell)
+            .map(Command::Completions)
+            .ok_or_else(|| format!("unknown shell `{}`", shell)),
+        [command] if command == "man" => Ok(Command::Man),
+        _ => Err(format!("unrecognized arguments: {}", args.join(" "))),
+    }
+}
@@ -0,0 +1,564 @@
+//! Shell completions, a man page and the usage text, generated from one table of the commands
+//! `parse_command` accepts, so packagers can ship them with
+//! `m4ster-slave_readme_update completions bash > /usr/share/bash-completion/completions/...`,
+//! or have the build write them (see build.rs).
+
+pub const BIN: &str = "m4ster-slave_readme_update";
+
+/// One command line `parse_command` accepts.
+struct CommandSpec {
+    /// Words selecting the command, e.g. `["config", "import"]`; empty for the default command.
+    path: &'static [&'static str],
+    /// Values the first argument after `path` must be one of, if it is a fixed set.
+    choices: &'static [&'static str],
+    flags: &'static [&'static str],
+    /// Whether arguments after `path` are file names.
+    files: bool,
+    /// Arguments after `path`, as shown in the man page.
+    synopsis: &'static str,
+    about: &'static str,
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        path: &[],
+        choices: &[],
+        flags: &["--strict", "--canary"],
+        files: false,
+        synopsis: "[--strict] [--canary]",
+        about: "Regenerate the README; --strict fails on any data anomaly, --canary writes a \
+                preview to review instead (see [canary]). Without GITHUB_TOKEN, a cut-down \
+                preview from the public API, written where --canary writes.",
+    },
+    CommandSpec {
+        path: &["batch"],
+        choices: &[],
+        flags: &["--jobs", "--strict"],
+        files: true,
+        synopsis: "<file> [--jobs <n>] [--strict]",
+        about: "Generate one README per `<username> <output path>` line of <file>.",
+    },
+    CommandSpec {
+        path: &["export"],
+        choices: &[],
+        flags: &["--anonymize", "--output"],
+        files: true,
+        synopsis: "[--anonymize] [--output <path>]",
+        about: "Write the fetched metrics as JSON, e.g. for the playground.",
+    },
+    CommandSpec {
+        path: &["cv"],
+        choices: &[],
+        flags: &["--output"],
+        files: true,
+        synopsis: "[--output <path>]",
+        about: "Write a one-page CV from the [cv] config and the fetched stats: markdown, or an \
+                HTML page when <path> ends in .html.",
+    },
+    CommandSpec {
+        path: &["backfill"],
+        choices: &[],
+        flags: &["--since"],
+        files: false,
+        synopsis: "--since <year>",
+        about: "Add a stats snapshot for the end of every year since <year>.",
+    },
+    CommandSpec {
+        path: &["demo"],
+        choices: &[],
+        flags: &["--output"],
+        files: true,
+        synopsis: "[--output <path>]",
+        about: "Record an asciinema cast of a run from the cached sections.",
+    },
+    CommandSpec {
+        path: &["art"],
+        choices: &[],
+        flags: &["--width"],
+        files: true,
+        synopsis: "<picture.pgm> [--width <columns>]",
+        about: "Convert a picture to braille art for `header.art`, picking the threshold whose \
+                result looks most like the picture.",
+    },
+    CommandSpec {
+        path: &["config", "schema"],
+        choices: &[],
+        flags: &[],
+        files: false,
+        synopsis: "",
+        about: "Print a JSON Schema of the config for editor completion.",
+    },
+    CommandSpec {
+        path: &["config", "import"],
+        choices: &["readme-stats", "metrics"],
+        flags: &[],
+        files: true,
+        synopsis: "<readme-stats|metrics> <file>",
+        about: "Print a config translated from a README with github-readme-stats cards or from \
+                a lowlighter/metrics workflow.",
+    },
+    CommandSpec {
+        path: &["tui"],
+        choices: &[],
+        flags: &[],
+        files: false,
+        synopsis: "",
+        about: "Reorder, hide and theme sections with a live preview.",
+    },
+    CommandSpec {
+        path: &["status", "set"],
+        choices: &["busy", "open-to-work", "on-vacation"],
+        flags: &["--message", "--until"],
+        files: false,
+        synopsis: "<busy|open-to-work|on-vacation> [--message <text>] [--until <YYYY-MM-DD>]",
+        about: "Show a status banner on the profile.",
+    },
+    CommandSpec {
+        path: &["status", "clear"],
+        choices: &[],
+        flags: &[],
+        files: false,
+        synopsis: "",
+        about: "Remove the status banner.",
+    },
+    CommandSpec {
+        path: &["completions"],
+        choices: &["bash", "zsh", "fish"],
+        flags: &[],
+        files: false,
+        synopsis: "<bash|zsh|fish>",
+        about: "Print a completion script for the shell.",
+    },
+    CommandSpec {
+        path: &["man"],
+        choices: &[],
+        flags: &[],
+        files: false,
+        synopsis: "",
+        about: "Print the manual page in roff format.",
+    },
+];
+
+/// Flags every command takes.
+const GLOBAL_FLAGS: [&str; 5] = [
+    "--profile",
+    "--config",
+    "--config-sha256",
+    "--record",
+    "--replay",
+];
+
+#[derive(Debug, Clone, Copy)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub fn parse(name: &str) -> Option<Shell> {
+        match name {
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            _ => None,
+        }
+    }
+}
+
+/// The words that may follow `path` directly: the next word of longer commands under it.
+fn subcommands(path: &[&str]) -> Vec<&'static str> {
+    let mut words: Vec<&'static str> = COMMANDS
+        .iter()
+        .filter(|command| command.path.len() > path.len() && command.path.starts_with(path))
+        .map(|command| command.path[path.len()])
+        .collect();
+    words.dedup();
+    words
+}
+
+/// Every command path and its prefixes (`config` for `config import`), shortest first.
+fn paths() -> Vec<Vec<&'static str>> {
+    let mut paths: Vec<Vec<&'static str>> = COMMANDS
+        .iter()
+        .flat_map(|command| (0..=command.path.len()).map(|length| command.path[..length].to_vec()))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths.sort_by_key(Vec::len);
+    paths
+}
+
+fn bash_completions() -> String {
+    let mut cases = String::new();
+    // Longest paths first, so `config import` is matched before `config`.
+    for path in paths().iter().rev() {
+        let command = COMMANDS
+            .iter()
+            .find(|command| command.path == path.as_slice());
+        let joined = path.join(" ");
+        let mut exact: Vec<&str> = subcommands(path);
+        let mut after: Vec<&str> = Vec::new();
+        if let Some(command) = command {
+            exact.extend(command.choices);
+            exact.extend(command.flags);
+            after.extend(command.flags);
+        }
+        exact.extend(GLOBAL_FLAGS);
+        after.extend(GLOBAL_FLAGS);
+        let files = command.is_some_and(|command| command.files);
+        let reply = |words: &[&str]| {
+            let mut reply = format!(
+                "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")",
+                words.join(" ")
+            );
+            if files {
+                reply += " $(compgen -f -- \"$cur\")";
+            }
+            reply + ")"
+        };
+        cases += &format!("        \"{}\") {} ;;\n", joined, reply(&exact));
+        if command.is_some() {
+            let pattern = match path.is_empty() {
+                true => "*".to_string(),
+                false => format!("\"{} \"*", joined),
+            };
+            cases += &format!("        {}) {} ;;\n", pattern, reply(&after));
+        }
+    }
+    format!(
+        "# bash completion for {0}\n\
+         _{1}() {{\n    \
+             local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"\n    \
+             local words=\"${{COMP_WORDS[*]:1:COMP_CWORD-1}}\"\n    \
+             case \"$words\" in\n\
+         {2}    \
+             esac\n\
+         }}\n\
+         complete -F _{1} {0}\n",
+        BIN,
+        BIN.replace('-', "_"),
+        cases
+    )
+}
+
+fn fish_completions() -> String {
+    let mut script = format!("# fish completion for {}\ncomplete -c {} -f\n", BIN, BIN);
+    for flag in GLOBAL_FLAGS {
+        script += &format!("complete -c {} -l {} -r\n", BIN, &flag[2..]);
+    }
+    for path in paths() {
+        let condition = match path.last() {
+            None => "__fish_use_subcommand".to_string(),
+            Some(word) => format!("__fish_seen_subcommand_from {}", word),
+        };
+        for word in subcommands(&path) {
+            let mut line = format!("complete -c {} -n '{}' -a {}", BIN, condition, word);
+            // Groups such as `config` have no description of their own.
+            let about = COMMANDS.iter().find(|command| {
+                command.path.len() == path.len() + 1
+                    && command.path.starts_with(path.as_slice())
+                    && command.path[path.len()] == word
+            });
+            if let Some(command) = about {
+                let about = command
+                    .about
+                    .split(';')
+                    .next()
+                    .unwrap_or("")
+                    .trim_end_matches('.');
+                line += &format!(" -d '{}'", about.replace('\'', "\\'"));
+            }
+            script += &line;
+            script.push('\n');
+        }
+        let Some(command) = COMMANDS
+            .iter()
+            .find(|command| command.path == path.as_slice())
+        else {
+            continue;
+        };
+        if !command.choices.is_empty() {
+            script += &format!(
+                "complete -c {} -n '{}' -a '{}'\n",
+                BIN,
+                condition,
+                command.choices.join(" ")
+            );
+        }
+        for flag in command.flags {
+            script += &format!("complete -c {} -n '{}' -l {}\n", BIN, condition, &flag[2..]);
+        }
+        if command.files {
+            script += &format!("complete -c {} -n '{}' -F\n", BIN, condition);
+        }
+    }
+    script
+}
+
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        // zsh runs the bash function through its bash compatibility layer.
+        Shell::Zsh => format!(
+            "#compdef {}\nautoload -U +X bashcompinit && bashcompinit\n{}",
+            BIN,
+            bash_completions()
+        ),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+/// Column the command descriptions of `usage` start at.
+const USAGE_INDENT: usize = 46;
+
+/// Width `usage` wraps the command descriptions to.
+const USAGE_WIDTH: usize = 100;
+
+const USAGE_NOTES: &str = "\
+Every command takes --profile <name> to apply the config's [profile.<name>] table, and
+--config <path> to read the config from <path>, which may be a URL or github:owner/repo/path@ref
+pinned by --config-sha256 <hex>. Without --config it is $PROFILE_GEN_CONFIG, else profile.toml
+(.yaml, .yml, .json) here, else $XDG_CONFIG_HOME/profile-gen/config.toml.
+--record <dir> saves every API response of the run to <dir>, and --replay <dir> answers
+from such a recording without going online, to reproduce a run exactly; both skip the cache.
+A replayed run writes a preview like --canary, leaving history, changelog and pull requests.";
+
+/// The usage text printed with a command line error.
+pub fn usage() -> String {
+    let mut usage = "Usage:\n".to_string();
+    for command in COMMANDS {
+        let mut line = format!("  {}", BIN);
+        for word in command.path.iter().chain([&command.synopsis]) {
+            if !word.is_empty() {
+                line += " ";
+                line += word;
+            }
+        }
+        // Descriptions start on a line of their own after a long synopsis.
+        let mut column = line.chars().count();
+        if column + 2 > USAGE_INDENT {
+            line.push('\n');
+            column = 0;
+        }
+        for word in command.about.split_whitespace() {
+            if column >= USAGE_INDENT && column + 1 + word.chars().count() > USAGE_WIDTH {
+                line.push('\n');
+                column = 0;
+            }
+            if column < USAGE_INDENT {
+                line += &" ".repeat(USAGE_INDENT - column);
+                column = USAGE_INDENT;
+            } else {
+                line.push(' ');
+                column += 1;
+            }
+            line += word;
+            column += word.chars().count();
+        }
+        usage += &line;
+        usage.push('\n');
+    }
+    usage + "\n" + USAGE_NOTES
+}
+
+/// Escapes text for roff: backslashes, hyphens (which would otherwise be typeset as dashes) and
+/// a leading dot or apostrophe, which would start a request.
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    match escaped.starts_with(['.', '\'']) {
+        true => format!("\\&{}", escaped),
+        false => escaped,
+    }
+}
+
+pub fn man_page() -> String {
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{} {}\"\n\
+         .SH NAME\n\
+         {} \\- generate a GitHub profile README from live stats\n\
+         .SH SYNOPSIS\n",
+        roff(&BIN.to_uppercase()),
+        roff(BIN),
+        env!("CARGO_PKG_VERSION"),
+        roff(BIN)
+    );
+    for command in COMMANDS {
+        page += &format!(
+            ".B {}\n{}\n.br\n",
+            roff(BIN),
+            roff(format!("{} {}", command.path.join(" "), command.synopsis).trim())
+        );
+    }
+    page += ".SH DESCRIPTION\n\
+             Reads the profile config (profile.toml, .yaml, .yml or .json) from the working \
+             directory, fetches the user's GitHub activity and writes the README and its assets.\n\
+             .SH COMMANDS\n";
+    for command in COMMANDS {
+        let name = match command.path {
+            [] => "(none)".to_string(),
+            path => path.join(" "),
+        };
+        page += &format!(".TP\n.B {}\n{}\n", roff(&name), roff(command.about));
+    }
+    page += ".SH OPTIONS\n\
+             .TP\n\
+             .BI \\-\\-profile \" name\"\n\
+             Apply the config's [profile.\\fIname\\fR] table on top of the rest.\n\
+             .TP\n\
+             .BI \\-\\-config \" path\"\n\
+             Read the config from \\fIpath\\fR instead of looking for one. It may be a URL or \
+             github:\\fIowner\\fR/\\fIrepo\\fR/\\fIpath\\fR[@\\fIref\\fR] in another repository.\n\
+             .TP\n\
+             .BI \\-\\-config\\-sha256 \" hex\"\n\
+             Fail unless the config has this SHA\\-256 checksum, e.g. to pin a remote one.\n\
+             .SH ENVIRONMENT\n\
+             .TP\n\
+             .B GITHUB_TOKEN\n\
+             Token for the GitHub API; required by every command that fetches data, except that\n\
+             \\fBgenerate\\fR without it writes a cut-down preview from the public API.\n\
+             .TP\n\
+             .B GITHUB_TOKENS\n\
+             More tokens, separated by commas or spaces. GitHub API requests then go out with\n\
+             whichever token has the most of its rate limit left, e.g. for large batches.\n\
+             .TP\n\
+             .B PROFILE_GEN_CONFIG\n\
+             The config file to use when \\-\\-config is not given.\n\
+             .TP\n\
+             .B XDG_CONFIG_HOME\n\
+             Where to look for profile\\-gen/config.toml when the working directory has no \
+             config; defaults to ~/.config.\n\
+             .SH FILES\n\
+             .TP\n\
+             .B profile.toml\n\
+             The config; see the commented example in the repository. profile.yaml, \
+             profile.yml and profile.json are read too.\n\
+             .TP\n\
+             .B $XDG_CONFIG_HOME/profile\\-gen/config.toml\n\
+             The config when the working directory has none, e.g. for a system\\-wide install.\n\
+             .TP\n\
+             .B .cache/\n\
+             Cached API responses and rendered sections.\n";
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{usage, BIN, COMMANDS, GLOBAL_FLAGS, USAGE_INDENT, USAGE_WIDTH};
+    use crate::cli::parse_args;
+
+    /// A value `parse_command` accepts for the synopsis placeholder `<name>`.
+    fn example(name: &str) -> String {
+        match name {
+            "n" => "2",
+            "year" => "2020",
+            "columns" => "40",
+            "YYYY-MM-DD" => "2024-01-01",
+            // A fixed set such as `<bash|zsh|fish>`: its first choice.
+            _ => name.split('|').next().unwrap_or(name),
+        }
+        .to_string()
+    }
+
+    /// The command line a spec's synopsis describes, with every optional part given.
+    fn example_line(path: &[&str], synopsis: &str) -> Vec<String> {
+        path.iter()
+            .map(|word| word.to_string())
+            .chain(synopsis.split_whitespace().map(|word| {
+                let word = word.trim_matches(['[', ']']);
+                match word.strip_prefix('<').and_then(|w| w.strip_suffix('>')) {
+                    Some(name) => example(name),
+                    None => word.to_string(),
+                }
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn every_listed_command_parses() {
+        for command in COMMANDS {
+            let line = example_line(command.path, command.synopsis);
+            if let Err(e) = parse_args(&line) {
+                panic!("`{}` is listed but rejected: {}", line.join(" "), e);
+            }
+        }
+    }
+
+    #[test]
+    fn listed_flags_are_the_documented_ones() {
+        for command in COMMANDS {
+            let documented: Vec<&str> = command
+                .synopsis
+                .split_whitespace()
+                .map(|word| word.trim_matches(['[', ']']))
+                .filter(|word| word.starts_with("--"))
+                .collect();
+            assert_eq!(command.flags, documented, "{:?}", command.path);
+        }
+    }
+
+    #[test]
+    fn listed_choices_all_parse() {
+        for command in COMMANDS
+            .iter()
+            .filter(|command| !command.choices.is_empty())
+        {
+            for choice in command.choices {
+                let synopsis = command.synopsis.replacen(
+                    &format!("<{}>", command.choices.join("|")),
+                    choice,
+                    1,
+                );
+                let line = example_line(command.path, &synopsis);
+                assert!(
+                    parse_args(&line).is_ok(),
+                    "`{}` is rejected",
+                    line.join(" ")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unlisted_flags_are_rejected() {
+        for command in COMMANDS.iter().filter(|command| !command.flags.is_empty()) {
+            let mut line = example_line(command.path, command.synopsis);
+            line.push("--bogus".to_string());
+            assert!(parse_args(&line).is_err(), "`{}` parses", line.join(" "));
+        }
+    }
+
+    #[test]
+    fn global_flags_are_taken_by_every_command() {
+        for flag in GLOBAL_FLAGS {
+            for command in COMMANDS {
+                let mut line = example_line(command.path, command.synopsis);
+                line.extend([flag.to_string(), "value".to_string()]);
+                assert!(
+                    parse_args(&line).is_ok(),
+                    "`{}` is rejected",
+                    line.join(" ")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn usage_names_every_command_and_wraps_descriptions() {
+        let usage = usage();
+        // Only a synopsis may run longer, having no place to break.
+        for line in usage.lines() {
+            if line.starts_with(&" ".repeat(USAGE_INDENT)) {
+                assert!(line.chars().count() <= USAGE_WIDTH, "{}", line);
+            }
+        }
+        for command in COMMANDS {
+            let start = format!("  {} {}", BIN, command.path.join(" "));
+            assert!(
+                usage.lines().any(|line| line.starts_with(start.trim_end())),
+                "{:?} is missing from the usage",
+                command.path
+            );
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use profile_core::backend::ansi::{self, ColorDepth};
+use profile_core::backend::text::layout;
+use profile_core::builder::ProfileBuilder;
+use profile_core::cache::write_atomically;
+use profile_core::cast::Recorder;
+use profile_core::config::Config;
+use profile_core::render::display_width;
+use profile_core::widget_cache::load_sections;
+use std::path::Path;
+
+const PROMPT: &str = "\x1b[32m$\x1b[0m ";
+const COMMAND: &str = "m4ster-slave_readme_update";
+/// Seconds between typed characters, progress lines and lines of the profile.
+const TYPING_DELAY: f64 = 0.06;
+const STEP_DELAY: f64 = 0.3;
+const LINE_DELAY: f64 = 0.04;
+/// Seconds the finished profile stays on screen before the recording ends.
+const HOLD: f64 = 3.0;
+const HEIGHT: usize = 40;
+const MIN_WIDTH: usize = 80;
+
+/// Replays a run from the sections cached by the last one, without any requests: the command
+/// being typed, a progress line per section and the profile in colour, recorded as an
+/// asciinema cast at `output`.
+pub fn record_demo(config: &Config, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let sections = load_sections(&config.username).ok_or_else(|| {
+        format!(
+            "no cached sections for {}; generate the README once before recording a demo",
+            config.username
+        )
+    })?;
+    let mut profile = ProfileBuilder::new(&config.username)
+        .with_layout(&config.layout)
+        .with_style(&config.style);
+    for section in sections {
+        profile = profile.with_section(section);
+    }
+    let document = profile.build().document;
+
+    let width = layout(&document)
+        .iter()
+        .map(|line| display_width(&line.text))
+        .max()
+        .unwrap_or(0)
+        .max(MIN_WIDTH);
+    let mut recorder = Recorder::new(width, HEIGHT);
+    recorder.print(PROMPT);
+    recorder.pause(1.0);
+    recorder.type_text(COMMAND, TYPING_DELAY);
+    recorder.print("\n");
+    recorder.pause(STEP_DELAY);
+    for title in document
+        .sections
+        .iter()
+        .filter_map(|section| section.title.as_ref())
+    {
+        recorder.print(&format!("\x1b[32m✓\x1b[0m {}\n", title));
+        recorder.pause(STEP_DELAY);
+    }
+    recorder.print("✅ README.md has been updated successfully.\n\n");
+    recorder.pause(1.0);
+    // Casts are replayed in players that show every colour, whatever this terminal supports.
+    recorder.print_lines(&ansi::render(&document, ColorDepth::TrueColor), LINE_DELAY);
+    recorder.pause(HOLD);
+    recorder.print(PROMPT);
+
+    write_atomically(
+        Path::new(output),
+        recorder.to_cast(&format!("@{}", config.username)),
+    )?;
+    println!("✅ Demo recorded to {}.", output);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! A lock file held while a run writes the history, cache and README, so a manual run started
+//! while the scheduled one is still going waits for it instead of interleaving their writes.
+
+use profile_core::cancel::is_cancelled;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+pub const LOCK_FILE: &str = ".profile-update.lock";
+
+/// How long to wait for another run to finish before giving up.
+const LOCK_WAIT: Duration = Duration::from_secs(10 * 60);
+
+/// Age after which a lock is taken to be left behind by a run that was killed; a run never takes
+/// this long, even with rate limit backoff.
+const STALE_AFTER: Duration = Duration::from_secs(60 * 60);
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Removes the lock file when the run ends.
+pub struct RunLock {
+    path: PathBuf,
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Why the lock at `path` can be broken, or `None` if its run may still be going.
+fn stale_reason(path: &Path) -> Option<String> {
+    let age = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+    if age > STALE_AFTER {
+        return Some(format!("it is {} minutes old", age.as_secs() / 60));
+    }
+    // Where processes can be looked up, a lock whose run has exited is stale right away. The pid
+    // is written just after the file is created, so an empty file is a run that is starting.
+    let pid = fs::read_to_string(path).ok()?.trim().parse::<u32>().ok()?;
+    let proc = Path::new("/proc");
+    match proc.is_dir() && !proc.join(pid.to_string()).exists() {
+        true => Some(format!("process {} is gone", pid)),
+        false => None,
+    }
+}
+
+/// Moves the stale lock at `path` aside and deletes it. Of several runs breaking it at once only
+/// one gets to move it; and should that be a fresh lock another run took after breaking the stale
+/// one, it is put back.
+fn break_lock(path: &str, reason: &str) {
+    let aside = PathBuf::from(format!("{}.{}.stale", path, std::process::id()));
+    if fs::rename(path, &aside).is_err() {
+        return;
+    }
+    match stale_reason(&aside) {
+        Some(_) => eprintln!("⚠️ Removed stale lock {} ({}).", path, reason),
+        // A hard link fails instead of replacing a lock taken in the meantime.
+        None => {
+            let _ = fs::hard_link(&aside, path);
+        }
+    }
+    let _ = fs::remove_file(&aside);
+}
+
+/// Takes the lock at `path`, waiting up to [`LOCK_WAIT`] for another run to release it.
+pub fn acquire(path: &str) -> Result<RunLock, Box<dyn Error>> {
+    let started = Instant::now();
+    let mut announced = false;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut file) => {
+                let lock = RunLock {
+                    path: PathBuf::from(path),
+                };
+                writeln!(file, "{}", std::process::id())?;
+                return Ok(lock);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(format!("Failed to create {}: {}", path, e).into()),
+        }
+        if let Some(reason) = stale_reason(Path::new(path)) {
+            break_lock(path, &reason);
+            continue;
+        }
+        if is_cancelled() {
+            return Err("Cancelled while waiting for another run".into());
+        }
+        if started.elapsed() > LOCK_WAIT {
+            return Err(format!(
+                "Another run still holds {} after {} minutes; delete it if that run is gone",
+                path,
+                LOCK_WAIT.as_secs() / 60
+            )
+            .into());
+        }
+        if !announced {
+            eprintln!(
+                "⏳ Another run is in progress ({} exists), waiting for it...",
+                path
+            );
+            announced = true;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{acquire, break_lock};
+    use std::fs;
+
+    fn scratch_lock(name: &str) -> String {
+        let path =
+            std::env::temp_dir().join(format!("profile-lock-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn lock_of_an_exited_process_is_broken() {
+        // Elsewhere only the lock's age tells that it is stale.
+        if !std::path::Path::new("/proc").is_dir() {
+            return;
+        }
+        let path = scratch_lock("exited");
+        // Pids wrap around well below this.
+        fs::write(&path, "4294967295\n").unwrap();
+        let lock = acquire(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().trim(),
+            std::process::id().to_string()
+        );
+        drop(lock);
+        assert!(!fs::exists(&path).unwrap());
+    }
+
+    #[test]
+    fn a_lock_taken_meanwhile_is_put_back() {
+        let path = scratch_lock("meanwhile");
+        // What a run breaking the stale lock just before us would have left.
+        let _lock = acquire(&path).unwrap();
+        break_lock(&path, "it looked stale");
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().trim(),
+            std::process::id().to_string()
+        );
+    }
+}
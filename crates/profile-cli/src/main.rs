@@ -0,0 +1,795 @@
+mod batch;
+mod cli;
+mod completions;
+mod demo;
+mod lock;
+mod progress;
+#[cfg(feature = "tui")]
+mod tui;
+
+use batch::{parse_batch_file, run_batch};
+use chrono::prelude::*;
+use cli::{parse_args, Command};
+use demo::record_demo;
+use lock::LOCK_FILE;
+use profile_core::anomaly::{report_anomaly, take_anomalies};
+use profile_core::anonymize::anonymize;
+#[cfg(feature = "image")]
+use profile_core::art::{best_braille, parse_pgm};
+use profile_core::backend::html;
+use profile_core::cache::{bypass_cache, write_atomically};
+use profile_core::cancel::{cancel, is_cancelled};
+use profile_core::changelog::{append_changelog, commit_message, count_new_activities};
+#[cfg(feature = "svg")]
+use profile_core::config::DiffAnimationConfig;
+use profile_core::config::{config_schema, find_config, load_config, Config, PullRequestConfig};
+use profile_core::cv::{build_cv, is_html};
+use profile_core::history::{load_history, save_history, Snapshot};
+use profile_core::import::import;
+use profile_core::lint::lint_markdown;
+use profile_core::locales::localize;
+use profile_core::overrides::{apply_overrides, override_count};
+use profile_core::plausibility::{implausible_changes, keep_previous};
+use profile_core::profile::ProfileData;
+use profile_core::remote::install_fetcher;
+use profile_core::render::odometer::describe_changes;
+use profile_core::render::ASSETS_DIR;
+use profile_core::secrets::find_secrets;
+use profile_core::spell::{misspellings, Dictionary};
+use profile_core::status::{clear_status, format_status, load_status, save_status};
+use profile_core::widget_cache::{load_widget_cache, save_sections, save_widget_cache};
+#[cfg(feature = "svg")]
+use profile_github::assemble::DIFF_ANIMATION_FILE;
+use profile_github::assemble::{assemble, Assembled, CoreData, Run, Steps};
+use profile_github::backfill::backfill_history;
+use profile_github::calendar::get_contribution_calendar;
+use profile_github::cassette::{load_cassettes, save_cassettes};
+use profile_github::fetch::{
+    get_all_languages, get_github_activity, get_github_followers, get_github_stats,
+    get_public_stats,
+};
+use profile_github::github::graphql_cost;
+use profile_github::http::Client;
+use profile_github::http::{install, BlockingClient, HttpClient, Recorder, Replayer};
+use profile_github::links::find_dead_links;
+#[cfg(feature = "svg")]
+use profile_github::notify::send_webhook;
+use profile_github::plausibility::notify_implausible;
+use profile_github::polite::{restrict_to_public, Polite, REQUEST_BUDGET};
+use profile_github::publish::{
+    commit_files, default_branch, enable_auto_merge, open_pull_request, profile_repo, put_file,
+    reset_branch,
+};
+use profile_github::token_pool::{github_token, github_tokens, TokenPool};
+use profile_github::widgets::celebrations::notify_celebrations;
+use progress::Progress;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const HISTORY_FILE: &str = "profile-history.json";
+
+const STATUS_FILE: &str = "status.toml";
+
+/// Now in the configured timezone, or the runner's local time.
+fn current_time(config: &Config) -> DateTime<FixedOffset> {
+    match config.timezone() {
+        Some(timezone) => Utc::now().with_timezone(&timezone).fixed_offset(),
+        None => Local::now().fixed_offset(),
+    }
+}
+
+/// Fetches what the core sections render from and writes it as `ProfileData` JSON.
+fn export(
+    config: &Config,
+    anonymize_data: bool,
+    output: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    config.languages.validate()?;
+    let username = config.username.as_str();
+    let token = github_token().ok_or("GITHUB_TOKEN not set")?;
+    let today = current_time(config).date_naive();
+    let progress = Progress::new();
+
+    let activities = progress.step("Activity", || get_github_activity(username, &token))?;
+    let languages = progress.step("Languages", || {
+        get_all_languages(username, &token, &config.languages, &config.sampling)
+    })?;
+    let stats = progress.step("Stats", || get_github_stats(username, &token))?;
+    let calendar = if config.year_comparison {
+        let from = NaiveDate::from_ymd_opt(today.year() - 1, 1, 1).unwrap();
+        progress
+            .step("Contribution calendar", || {
+                get_contribution_calendar(username, &token, from, today)
+            })
+            .map_err(|e| report_anomaly(format!("Skipping contribution calendar: {}", e)))
+            .ok()
+    } else {
+        None
+    };
+
+    let mut data = ProfileData {
+        generated_on: today,
+        languages,
+        stats,
+        activities,
+        calendar,
+    };
+    if anonymize_data {
+        anonymize(&mut data, username);
+    }
+
+    let json = serde_json::to_string_pretty(&data)?;
+    match output {
+        Some(path) => {
+            write_atomically(Path::new(path), &json)?;
+            println!("✅ Metrics exported to {}.", path);
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// Writes the one-page CV from `config.cv` to `output`, or to the configured path.
+fn write_cv(config: &Config, output: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let cv_config = config
+        .cv
+        .as_ref()
+        .ok_or("there is no [cv] table in the config")?;
+    config.languages.validate()?;
+    let username = config.username.as_str();
+    let token = github_token().ok_or("GITHUB_TOKEN not set")?;
+    let progress = Progress::new();
+
+    let languages = progress.step("Languages", || {
+        get_all_languages(username, &token, &config.languages, &config.sampling)
+    })?;
+    let mut stats = progress.step("Stats", || get_github_stats(username, &token))?;
+    apply_overrides(&mut stats, &config.overrides);
+    let data = ProfileData {
+        generated_on: current_time(config).date_naive(),
+        languages,
+        stats,
+        activities: Vec::new(),
+        calendar: None,
+    };
+
+    let cv = build_cv(&data, config, cv_config);
+    let path = output.unwrap_or(&cv_config.output);
+    let contents = match is_html(path) {
+        true => html::render(&cv.document),
+        false => cv.markdown,
+    };
+    write_atomically(Path::new(path), &contents)?;
+    println!("✅ CV written to {}.", path);
+    Ok(())
+}
+
+/// Pushes `markdown` as `readme` to `branch` of the profile repository, reset to the default
+/// branch first, and opens a pull request for it. Returns the pull request's URL.
+fn publish_preview(
+    username: &str,
+    token: &str,
+    branch: &str,
+    readme: &str,
+    markdown: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let repo = profile_repo(username);
+    let base = default_branch(&client, &repo, token)?;
+    reset_branch(&client, &repo, branch, &base, token)?;
+    put_file(
+        &client,
+        &repo,
+        branch,
+        readme,
+        markdown.as_bytes(),
+        "Preview the regenerated README",
+        token,
+    )?;
+    let pull = open_pull_request(
+        &client,
+        &repo,
+        branch,
+        &base,
+        "Preview: regenerated profile README",
+        "Generated with `--canary`. Merge to publish, or close to discard.",
+        token,
+    )?;
+    Ok(pull.url)
+}
+
+/// Commits the files at `paths` to the configured branch of the profile repository, reset to
+/// the default branch first, and opens a pull request for them. Returns the pull request's URL,
+/// or `None` when the files match the default branch already.
+fn publish_update(
+    username: &str,
+    token: &str,
+    config: &PullRequestConfig,
+    paths: &[PathBuf],
+    message: &str,
+    body: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    for path in paths {
+        let contents = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        files.push((path.to_string_lossy().replace('\\', "/"), contents));
+    }
+    let client = Client::new();
+    let repo = profile_repo(username);
+    let base = default_branch(&client, &repo, token)?;
+    reset_branch(&client, &repo, &config.branch, &base, token)?;
+    if !commit_files(&client, &repo, &config.branch, &files, message, token)? {
+        return Ok(None);
+    }
+    let pull = open_pull_request(&client, &repo, &config.branch, &base, message, body, token)?;
+    if config.auto_merge {
+        enable_auto_merge(&client, &pull, config.merge_method, token)?;
+    }
+    Ok(Some(pull.url))
+}
+
+/// Sets the step output `name` when running in GitHub Actions, for later workflow steps to use
+/// as `steps.<id>.outputs.<name>`.
+fn set_github_output(name: &str, value: &str) -> std::io::Result<()> {
+    let Some(path) = env::var_os("GITHUB_OUTPUT") else {
+        return Ok(());
+    };
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)?;
+    writeln!(file, "{}={}", name, value.replace('\n', " "))
+}
+
+/// Where one profile's README and its stats history are written.
+struct Target {
+    readme: PathBuf,
+    history: PathBuf,
+    /// The `status` banner belongs to the owner's profile only, not to batch-generated ones.
+    show_status: bool,
+    /// A `--canary` run: only `readme` is written, and nothing that outlives the run is touched.
+    preview: bool,
+    /// Goes through `[pull_request]` when configured; batch profiles live in other repositories.
+    pull_request: bool,
+    /// An unauthenticated preview, limited to what the public API answers without a token.
+    anonymous: bool,
+}
+
+impl Target {
+    /// `path` relative to the README, as assets and their links are, made relative to here.
+    fn next_to_readme(&self, path: &str) -> PathBuf {
+        self.readme.parent().unwrap_or(Path::new("")).join(path)
+    }
+}
+
+fn generate(
+    config: &Config,
+    target: &Target,
+    progress: &Progress,
+    strict: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    config.languages.validate()?;
+    // Before any request, so a damaged history stops the run instead of being written over.
+    let mut history = load_history(&target.history)?;
+    let username = config.username.as_str();
+    // The effective config, so a change to a base config or profile invalidates the cache too.
+    let mut widgets = load_widget_cache(username, &format!("{:?}", config));
+    // Empty for an unauthenticated preview, which `main` has already cut down to match, and
+    // possibly for a replay, which needs none.
+    let token = github_token().unwrap_or_default();
+
+    // Step 3: Fetch GitHub data
+    let activities = progress.step("Activity", || get_github_activity(username, &token))?;
+    // Failures of these two leave a placeholder in the README rather than stop the run.
+    let top_languages = progress
+        .step("Languages", || {
+            get_all_languages(username, &token, &config.languages, &config.sampling)
+        })
+        .map_err(|e| e.to_string());
+    let mut github_stats = progress
+        .step("Stats", || match target.anonymous {
+            true => Ok(get_public_stats(username)),
+            false => get_github_stats(username, &token),
+        })
+        .map_err(|e| e.to_string());
+    let mut github_followers =
+        progress.step("Followers", || get_github_followers(username, &token));
+
+    let now = current_time(config);
+    let today = now.date_naive();
+    // Without the stats or languages there is no snapshot to record: one of zeros would read as
+    // a collapse next run.
+    if let (Ok(stats), Ok(languages)) = (&mut github_stats, &top_languages) {
+        let mut snapshot = Snapshot::new(today, stats, github_followers, languages);
+        let implausible = history
+            .previous(today)
+            .map(|previous| implausible_changes(previous, &snapshot, &config.plausibility))
+            .unwrap_or_default();
+        for change in &implausible {
+            report_anomaly(format!(
+                "Implausible change: {}{}",
+                change.describe(),
+                match config.plausibility.keep_previous {
+                    true => "; keeping the previous value",
+                    false => "",
+                }
+            ));
+        }
+        if !target.preview {
+            notify_implausible(&config.plausibility, &implausible, today);
+        }
+        if config.plausibility.keep_previous {
+            keep_previous(&implausible, &mut snapshot, stats, &mut github_followers);
+        }
+        history.record(snapshot);
+    }
+
+    // The history keeps the fetched numbers; everything shown from here on has the overrides.
+    let mut no_stats = Value::Null;
+    let shown_stats = match &mut github_stats {
+        Ok(stats) => {
+            apply_overrides(stats, &config.overrides);
+            stats
+        }
+        Err(_) => &mut no_stats,
+    };
+    let github_followers = override_count(
+        shown_stats,
+        "followers",
+        github_followers,
+        config.overrides.get("followers"),
+    );
+
+    let status = load_status(STATUS_FILE)
+        .filter(|_| target.show_status)
+        .and_then(|status| format_status(&status, today));
+    let data = CoreData {
+        activities,
+        languages: top_languages,
+        stats: github_stats,
+        followers: github_followers,
+    };
+    let run = Run {
+        config,
+        token: &token,
+        now,
+        steps: progress,
+    };
+    let Assembled {
+        profile,
+        prose,
+        celebrations,
+        stat_diff,
+    } = assemble(&run, &data, status, &mut history, &mut widgets);
+
+    let sections = profile.sections().to_vec();
+    let mut profile = profile.build();
+    let mut translations = Vec::new();
+    if let Some(locales_config) = &config.locales {
+        let readme = target.readme.to_string_lossy();
+        (profile.markdown, translations) = localize(&profile.document, locales_config, &readme);
+    }
+
+    let mut problems = lint_markdown(&profile.markdown, &config.lint);
+    for (path, markdown) in &translations {
+        problems.extend(
+            lint_markdown(markdown, &config.lint)
+                .into_iter()
+                .map(|problem| format!("{}: {}", path, problem)),
+        );
+    }
+    if config.lint.fail && !problems.is_empty() {
+        return Err(format!(
+            "README failed the lint, nothing was written:\n  - {}",
+            problems.join("\n  - ")
+        )
+        .into());
+    }
+    for problem in problems {
+        report_anomaly(problem);
+    }
+
+    if let Some(links_config) = &config.links {
+        let dead = progress.step("Links", || find_dead_links(&profile.markdown, links_config));
+        for (url, reason) in dead {
+            report_anomaly(format!("Dead link {}: {}", url, reason));
+        }
+    }
+
+    if let Some(spelling_config) = &config.spelling {
+        match Dictionary::load(spelling_config) {
+            Ok(dictionary) => {
+                for (source, text) in &prose {
+                    for word in misspellings(text, &dictionary) {
+                        report_anomaly(format!("Possible typo \"{}\" in {}", word, source));
+                    }
+                }
+            }
+            Err(e) => report_anomaly(format!("Skipping spell-check: {}", e)),
+        }
+    }
+
+    // Always fatal, strict or not: a leaked token is worse than a stale README.
+    let tokens = github_tokens();
+    let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+    let mut leaks: Vec<String> = find_secrets(&profile.markdown, &tokens)
+        .into_iter()
+        .map(|leak| format!("{} of {}", leak, target.readme.display()))
+        .collect();
+    for (path, markdown) in &translations {
+        for leak in find_secrets(markdown, &tokens) {
+            leaks.push(format!("{} of {}", leak, path));
+        }
+    }
+    for asset in &profile.assets {
+        for leak in find_secrets(&String::from_utf8_lossy(&asset.contents), &tokens) {
+            leaks.push(format!("{} of {}", leak, asset.path));
+        }
+    }
+    if !leaks.is_empty() {
+        return Err(format!(
+            "the output looks like it contains secrets, nothing was written:\n  - {}",
+            leaks.join("\n  - ")
+        )
+        .into());
+    }
+
+    // Everything below writes files; past this point a signal no longer interrupts the run.
+    if is_cancelled() {
+        return Err("interrupted, nothing was written".into());
+    }
+
+    let anomalies = take_anomalies();
+    if strict && !anomalies.is_empty() {
+        return Err(format!(
+            "strict mode: {} anomalies, nothing was written:\n  - {}",
+            anomalies.len(),
+            anomalies.join("\n  - ")
+        )
+        .into());
+    }
+
+    if target.preview {
+        write_atomically(&target.readme, &profile.markdown).expect("Failed to write README");
+        if let Some(branch) = &config.canary.branch {
+            match progress.step("Preview pull request", || {
+                publish_preview(username, &token, branch, &config.output, &profile.markdown)
+            }) {
+                Ok(url) => println!("🔍 Preview pull request: {}", url),
+                Err(e) => report_anomaly(format!("Failed to open the preview pull request: {}", e)),
+            }
+        }
+        println!(
+            "✅ Preview written to {}; history, caches and assets are unchanged.",
+            target.readme.display()
+        );
+        return Ok(());
+    }
+
+    let current = history
+        .snapshots
+        .iter()
+        .find(|snapshot| snapshot.date == today);
+    if let (Some(changelog_config), Some(previous), Some(current)) =
+        (&config.changelog, history.previous(today), current)
+    {
+        if let Err(e) = append_changelog(changelog_config, previous, current) {
+            report_anomaly(format!("Failed to write the changelog: {}", e));
+        }
+    }
+    save_history(&target.history, &history);
+    save_widget_cache(&widgets);
+    save_sections(username, &sections);
+
+    if !profile.assets.is_empty() {
+        fs::create_dir_all(target.next_to_readme(ASSETS_DIR))
+            .expect("Failed to create assets directory");
+    }
+    for asset in &profile.assets {
+        write_atomically(&target.next_to_readme(&asset.path), &asset.contents)
+            .expect("Failed to write asset");
+    }
+    write_atomically(&target.readme, &profile.markdown).expect("Failed to write README");
+    for (path, markdown) in &translations {
+        write_atomically(Path::new(path), markdown).expect("Failed to write a translated README");
+    }
+
+    if target.pull_request {
+        let previous = history.previous(today);
+        // No snapshot today when the stats or languages failed; the message then leaves out
+        // the stats.
+        let no_snapshot = Snapshot::new(today, &Value::Null, 0, &[]);
+        let message = commit_message(
+            previous.filter(|_| current.is_some()),
+            current.unwrap_or(&no_snapshot),
+            previous.map_or(0, |previous| {
+                count_new_activities(&data.activities, previous.date)
+            }),
+            widgets.changed(),
+        );
+        match &config.pull_request {
+            Some(pull_request) => {
+                let mut paths = vec![target.readme.clone(), target.history.clone()];
+                if let Some(changelog_config) = &config.changelog {
+                    paths.push(PathBuf::from(&changelog_config.path));
+                }
+                paths.extend(
+                    profile
+                        .assets
+                        .iter()
+                        .map(|asset| target.next_to_readme(&asset.path)),
+                );
+                let mut body = "Regenerated profile README.\n".to_string();
+                if !stat_diff.is_empty() {
+                    body += &format!("\n**Stats:** {}\n", describe_changes(&stat_diff));
+                }
+                if !widgets.changed().is_empty() {
+                    let changed: Vec<&str> = widgets.changed().iter().map(String::as_str).collect();
+                    body += &format!("\n**Changed sections:** {}\n", changed.join(", "));
+                }
+                match progress.step("Pull request", || {
+                    publish_update(username, &token, pull_request, &paths, &message, &body)
+                }) {
+                    Ok(Some(url)) => println!("🔀 Pull request: {}", url),
+                    Ok(None) => {
+                        println!("Nothing to publish, the profile repository is up to date.")
+                    }
+                    Err(e) => {
+                        report_anomaly(format!("Failed to open the update pull request: {}", e))
+                    }
+                }
+            }
+            // The workflow commits and pushes the files itself, with this subject.
+            None => {
+                if let Err(e) = set_github_output("commit_message", &message) {
+                    report_anomaly(format!("Failed to hand over the commit message: {}", e));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "svg")]
+    if let (
+        Some(DiffAnimationConfig {
+            webhook: Some(webhook),
+        }),
+        false,
+    ) = (&config.diff_animation, stat_diff.is_empty())
+    {
+        // The profile repository is named after the user, and the workflow pushes right after.
+        let path = target.next_to_readme(&format!("{}/{}", ASSETS_DIR, DIFF_ANIMATION_FILE));
+        let message = format!(
+            "📈 {}\nhttps://raw.githubusercontent.com/{1}/{1}/HEAD/{2}",
+            describe_changes(&stat_diff),
+            username,
+            path.to_string_lossy().replace('\\', "/")
+        );
+        if let Err(e) = send_webhook(webhook, &message) {
+            report_anomaly(format!("Failed to send the stats diff notification: {}", e));
+        }
+    }
+
+    if let Some((celebrations_config, celebrations)) = &celebrations {
+        notify_celebrations(celebrations_config, celebrations, today);
+    }
+
+    if widgets.changed().is_empty() {
+        println!("No section changed since the last run.");
+    } else {
+        let changed: Vec<&str> = widgets.changed().iter().map(String::as_str).collect();
+        println!("Changed sections: {}", changed.join(", "));
+    }
+    if let (spent @ 1.., remaining) = graphql_cost() {
+        match remaining {
+            Some(remaining) => println!(
+                "GraphQL cost: {} points ({} left this hour).",
+                spent, remaining
+            ),
+            None => println!("GraphQL cost: {} points.", spent),
+        }
+    }
+    println!(
+        "✅ {} has been updated successfully.",
+        target.readme.display()
+    );
+    Ok(())
+}
+
+/// The first SIGINT/SIGTERM stops new requests and lets the run wind down without writing
+/// anything; a second one exits immediately.
+fn install_signal_handler() {
+    let result = ctrlc::set_handler(|| {
+        if is_cancelled() {
+            std::process::exit(130);
+        }
+        eprintln!("⏹ Interrupted, finishing up (press Ctrl-C again to quit now)...");
+        cancel();
+    });
+    if let Err(e) = result {
+        eprintln!("⚠️ Failed to install the signal handler: {}", e);
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    install_signal_handler();
+    // Base configs and header art can be remote; they are read before anything else runs.
+    install_fetcher(profile_github::remote::fetch)?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let (command, options) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, completions::usage());
+            std::process::exit(2);
+        }
+    };
+    let mut config_source = find_config(options.config.as_deref());
+    config_source.sha256 = options.config_sha256.clone();
+    let config = || load_config(&config_source, options.profile.as_deref());
+    // Commands that write the history or cache run one at a time.
+    let _lock = match command {
+        Command::Generate { .. }
+        | Command::Batch { .. }
+        | Command::Export { .. }
+        | Command::Cv { .. }
+        | Command::Backfill { .. } => Some(lock::acquire(LOCK_FILE)?),
+        _ => None,
+    };
+
+    // Without a token, `generate` previews what the public API allows; see `restrict_to_public`.
+    let tokens = github_tokens();
+    let replaying = options.replay.is_some();
+    // A replay answers from its recording, whoever recorded it; it needs no token.
+    let anonymous = matches!(command, Command::Generate { .. }) && tokens.is_empty() && !replaying;
+    let (client, recorder): (Arc<dyn HttpClient>, _) = match (&options.record, &options.replay) {
+        (Some(_), _) => {
+            let recorder = Arc::new(Recorder::new(BlockingClient::default()));
+            bypass_cache(false);
+            (recorder.clone(), Some(recorder))
+        }
+        (None, Some(dir)) => {
+            bypass_cache(true);
+            let replayer = Replayer::new(load_cassettes(Path::new(dir))?);
+            (Arc::new(replayer), None)
+        }
+        (None, None) => (Arc::new(BlockingClient::default()), None),
+    };
+    match (anonymous, tokens.len()) {
+        _ if replaying => install(client)?,
+        (true, _) => install(Arc::new(Polite::new(client)))?,
+        (false, 2..) => install(Arc::new(TokenPool::new(client, tokens)))?,
+        (false, _) => install(client)?,
+    }
+
+    let result = match command {
+        Command::Generate { strict, canary } => {
+            let mut config = config()?;
+            if anonymous {
+                let disabled = restrict_to_public(&mut config);
+                eprintln!(
+                    "⚠️ GITHUB_TOKEN is not set: previewing from the public API, at most {} \
+                     requests, written to {} only. Set a token for the full profile.",
+                    REQUEST_BUDGET, config.canary.output
+                );
+                if !disabled.is_empty() {
+                    eprintln!("   Turned off without a token: {}.", disabled.join(", "));
+                }
+                config.canary.branch = None;
+            }
+            // A replay reproduces someone else's run; it must not publish or extend the history.
+            let preview = canary || replaying;
+            if replaying {
+                config.canary.branch = None;
+            }
+            let target = Target {
+                readme: PathBuf::from(match preview || anonymous {
+                    true => &config.canary.output,
+                    false => &config.output,
+                }),
+                history: PathBuf::from(HISTORY_FILE),
+                show_status: true,
+                preview: preview || anonymous,
+                pull_request: !preview,
+                anonymous,
+            };
+            generate(&config, &target, &Progress::new(), strict)
+        }
+        Command::Batch { file, jobs, strict } => {
+            // Only a single `generate` previews without a token.
+            if !replaying {
+                github_token().ok_or("GITHUB_TOKEN not set")?;
+            }
+            let entries = parse_batch_file(&fs::read_to_string(&file)?)?;
+            let failures = run_batch(&entries, jobs, |entry| {
+                // Everyone shares the owner's config, but keeps their own history.
+                let mut config = config()?;
+                config.username = entry.username.clone();
+                config.changelog = None;
+                config.diff_animation = None;
+                let target = Target {
+                    readme: entry.output.clone(),
+                    history: entry.output.with_extension("history.json"),
+                    show_status: false,
+                    preview: replaying,
+                    pull_request: false,
+                    anonymous: false,
+                };
+                let progress = Progress::labelled(&entry.username);
+                generate(&config, &target, &progress, strict).map_err(|e| e.to_string())
+            });
+
+            if failures.is_empty() {
+                println!("✅ Generated {} profiles.", entries.len());
+                return Ok(());
+            }
+            for (username, e) in &failures {
+                eprintln!("❌ {}: {}", username, e);
+            }
+            Err(format!("{} of {} profiles failed", failures.len(), entries.len()).into())
+        }
+        Command::Export { anonymize, output } => export(&config()?, anonymize, output.as_deref()),
+        Command::Cv { output } => write_cv(&config()?, output.as_deref()),
+        Command::Backfill { since } => {
+            let config = config()?;
+            let token = github_token().ok_or("GITHUB_TOKEN not set")?;
+            let today = current_time(&config).date_naive();
+            let path = Path::new(HISTORY_FILE);
+            let mut history = load_history(path)?;
+            let added = backfill_history(&mut history, &config.username, &token, since, today)?;
+            save_history(path, &history);
+            println!("✅ Added {} snapshots to {}.", added, HISTORY_FILE);
+            Ok(())
+        }
+        Command::Demo { output } => record_demo(&config()?, &output),
+        #[cfg(feature = "image")]
+        Command::Art { picture, columns } => {
+            let (art, threshold, score) = best_braille(&parse_pgm(&fs::read(&picture)?)?, columns);
+            print!("{}", art);
+            eprintln!("Threshold {}, SSIM {:.3}", threshold, score);
+            Ok(())
+        }
+        #[cfg(not(feature = "image"))]
+        Command::Art { .. } => Err("this build has no `image` feature".into()),
+        Command::ConfigSchema => {
+            println!("{}", config_schema());
+            Ok(())
+        }
+        Command::ConfigImport { source, file } => {
+            print!("{}", import(source, &fs::read_to_string(&file)?)?.to_toml());
+            Ok(())
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui => tui::run(&config_source),
+        #[cfg(not(feature = "tui"))]
+        Command::Tui => Err("this build has no `tui` feature".into()),
+        Command::StatusSet(status) => {
+            save_status(STATUS_FILE, &status);
+            println!("✅ Status saved to {}.", STATUS_FILE);
+            Ok(())
+        }
+        Command::StatusClear => {
+            clear_status(STATUS_FILE);
+            println!("✅ Status cleared.");
+            Ok(())
+        }
+        Command::Completions(shell) => {
+            print!("{}", completions::completions(shell));
+            Ok(())
+        }
+        Command::Man => {
+            print!("{}", completions::man_page());
+            Ok(())
+        }
+    };
+
+    // Saved even when the run failed, since that is often the run worth reproducing.
+    if let (Some(recorder), Some(dir)) = (recorder, &options.record) {
+        save_cassettes(Path::new(dir), &recorder.exchanges())?;
+        println!("📼 Responses recorded to {}.", dir);
+    }
+    result
+}
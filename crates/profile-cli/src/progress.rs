@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use profile_core::cache::track_data_age;
+use profile_github::assemble::Steps;
+use profile_github::github::{graphql_cost, requests_made};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{stderr, IsTerminal};
+use std::time::{Duration, Instant};
+
+/// Reports each fetch step as it runs: a spinner per step on a terminal, plain log lines in CI
+/// or when stderr is redirected.
+pub struct Progress {
+    spinners: Option<MultiProgress>,
+    /// Prefix for log lines, telling apart profiles generated side by side.
+    label: Option<String>,
+    /// When the oldest data each step used was fetched, by step name.
+    fetched: RefCell<BTreeMap<String, DateTime<Utc>>>,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        let interactive = stderr().is_terminal() && env::var_os("CI").is_none();
+        Progress {
+            spinners: interactive.then(MultiProgress::new),
+            label: None,
+            fetched: RefCell::default(),
+        }
+    }
+
+    /// Log lines only, prefixed with `label`, for runs where several profiles report at once.
+    pub fn labelled(label: &str) -> Self {
+        Progress {
+            spinners: None,
+            label: Some(label.to_string()),
+            fetched: RefCell::default(),
+        }
+    }
+}
+
+impl Steps for Progress {
+    /// Runs `fetch` as the step `name`, then reports how long it took, how many requests it
+    /// sent (cached responses don't count) and what its GraphQL queries cost. Remembers how old
+    /// the data it used is.
+    fn step<T>(&self, name: &str, fetch: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let requests_before = requests_made();
+        let (cost_before, _) = graphql_cost();
+
+        let spinner = self.spinners.as_ref().map(|spinners| {
+            let spinner = spinners.add(ProgressBar::new_spinner());
+            spinner.set_style(
+                ProgressStyle::with_template("{spinner} {msg}")
+                    .expect("Invalid progress template")
+                    .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏✓"),
+            );
+            spinner.set_message(format!("{}...", name));
+            spinner.enable_steady_tick(Duration::from_millis(100));
+            spinner
+        });
+        let prefix = match &self.label {
+            Some(label) => format!("{}: ", label),
+            None => String::new(),
+        };
+        if spinner.is_none() {
+            eprintln!("→ {}{}...", prefix, name);
+        }
+
+        let (result, fetched_at) = track_data_age(fetch);
+        self.fetched
+            .borrow_mut()
+            .insert(name.to_string(), fetched_at);
+
+        let mut summary = format!(
+            "{}{} ({:.1}s, {} requests",
+            prefix,
+            name,
+            started.elapsed().as_secs_f64(),
+            requests_made() - requests_before
+        );
+        match graphql_cost().0 - cost_before {
+            0 => summary.push(')'),
+            cost => summary += &format!(", GraphQL cost {})", cost),
+        }
+        match spinner {
+            Some(spinner) => spinner.finish_with_message(summary),
+            None => eprintln!("✓ {}", summary),
+        }
+        result
+    }
+
+    /// When the oldest data behind `steps` was fetched, ignoring steps that haven't run.
+    fn fetched_at(&self, steps: &[&str]) -> Option<DateTime<Utc>> {
+        let fetched = self.fetched.borrow();
+        steps
+            .iter()
+            .filter_map(|step| fetched.get(*step))
+            .min()
+            .copied()
+    }
+}
@@ -0,0 +1,392 @@
+use profile_core::backend::ansi::{nearest_16, nearest_256, ColorDepth};
+use profile_core::builder::{arrange, ProfileBuilder};
+use profile_core::cache::write_atomically;
+use profile_core::config::{load_config, ConfigSource, HeatmapPalette, LayoutConfig, StyleConfig};
+use profile_core::document::Section;
+use profile_core::remote::is_remote;
+use profile_core::render::hex_rgb;
+use profile_core::widget_cache::load_sections;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::fs;
+use std::path::Path;
+use toml_edit::{value, Array, DocumentMut, Item, Table};
+
+const HELP: &str =
+    "↑/↓ select  space show/hide  J/K move  c contents  t palette  PgUp/PgDn scroll  s save  q quit";
+
+struct Entry {
+    title: String,
+    visible: bool,
+}
+
+/// Layout and theme being edited, previewed from the sections of the last run.
+struct Editor {
+    username: String,
+    sections: Vec<Section>,
+    entries: Vec<Entry>,
+    /// Hidden headings that the last run didn't render, kept so saving doesn't unhide them.
+    hidden_elsewhere: Vec<String>,
+    table_of_contents: bool,
+    palette: HeatmapPalette,
+    /// The palette the cached sections' heatmaps were drawn with; a new one shows from the
+    /// next run.
+    rendered_palette: HeatmapPalette,
+    /// Swatches are drawn in the nearest colours the terminal has.
+    color_depth: ColorDepth,
+    /// Applied to the preview only; the editor doesn't change it.
+    style: StyleConfig,
+    list: ListState,
+    scroll: u16,
+    dirty: bool,
+    confirm_quit: bool,
+    message: Option<String>,
+}
+
+impl Editor {
+    fn new(
+        username: &str,
+        sections: Vec<Section>,
+        layout: &LayoutConfig,
+        palette: HeatmapPalette,
+        style: &StyleConfig,
+    ) -> Self {
+        let unhidden = LayoutConfig {
+            order: layout.order.clone(),
+            ..LayoutConfig::default()
+        };
+        let entries: Vec<Entry> = arrange(sections.clone(), &unhidden)
+            .into_iter()
+            .filter_map(|section| section.title)
+            .map(|title| Entry {
+                visible: !layout.hidden.contains(&title),
+                title,
+            })
+            .collect();
+        let hidden_elsewhere = layout
+            .hidden
+            .iter()
+            .filter(|title| !entries.iter().any(|entry| &&entry.title == title))
+            .cloned()
+            .collect();
+
+        let mut list = ListState::default();
+        list.select((!entries.is_empty()).then_some(0));
+        Editor {
+            username: username.to_string(),
+            sections,
+            entries,
+            hidden_elsewhere,
+            table_of_contents: layout.table_of_contents,
+            palette,
+            rendered_palette: palette,
+            color_depth: ColorDepth::detect(),
+            style: style.clone(),
+            list,
+            scroll: 0,
+            dirty: false,
+            confirm_quit: false,
+            message: None,
+        }
+    }
+
+    fn layout(&self) -> LayoutConfig {
+        let hidden = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.visible)
+            .map(|entry| entry.title.clone());
+        LayoutConfig {
+            order: self
+                .entries
+                .iter()
+                .map(|entry| entry.title.clone())
+                .collect(),
+            hidden: hidden
+                .chain(self.hidden_elsewhere.iter().cloned())
+                .collect(),
+            table_of_contents: self.table_of_contents,
+            ..LayoutConfig::default()
+        }
+    }
+
+    fn preview(&self) -> String {
+        let mut profile = ProfileBuilder::new(&self.username)
+            .with_layout(&self.layout())
+            .with_style(&self.style);
+        for section in &self.sections {
+            profile = profile.with_section(section.clone());
+        }
+        profile.build().markdown
+    }
+
+    /// Scrolls the preview to the heading of the selected section.
+    fn scroll_to_selected(&mut self) {
+        let Some(entry) = self.list.selected().and_then(|i| self.entries.get(i)) else {
+            return;
+        };
+        let heading = format!("#### {}", entry.title);
+        if let Some(line) = self.preview().lines().position(|line| line == heading) {
+            self.scroll = line as u16;
+        }
+    }
+
+    fn select(&mut self, offset: isize) {
+        if let Some(selected) = self.list.selected() {
+            let last = self.entries.len() as isize - 1;
+            self.list
+                .select(Some((selected as isize + offset).clamp(0, last) as usize));
+            self.scroll_to_selected();
+        }
+    }
+
+    fn move_selected(&mut self, offset: isize) {
+        let Some(selected) = self.list.selected() else {
+            return;
+        };
+        let target = selected as isize + offset;
+        if target < 0 || target >= self.entries.len() as isize {
+            return;
+        }
+        self.entries.swap(selected, target as usize);
+        self.list.select(Some(target as usize));
+        self.changed();
+        self.scroll_to_selected();
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some(entry) = self.list.selected().and_then(|i| self.entries.get_mut(i)) {
+            entry.visible = !entry.visible;
+            self.changed();
+        }
+    }
+
+    fn cycle_palette(&mut self) {
+        let current = HeatmapPalette::ALL
+            .iter()
+            .position(|palette| *palette == self.palette)
+            .unwrap_or(0);
+        self.palette = HeatmapPalette::ALL[(current + 1) % HeatmapPalette::ALL.len()];
+        self.changed();
+    }
+
+    fn changed(&mut self) {
+        self.dirty = true;
+        self.confirm_quit = false;
+        self.message = None;
+    }
+
+    /// Writes `[layout]` and the heatmap palette into the config, keeping everything else in
+    /// it (comments included) as it was.
+    fn save(&self, config_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut document = fs::read_to_string(config_path)
+            .unwrap_or_default()
+            .parse::<DocumentMut>()?;
+        let layout = self.layout();
+
+        let layout_table = child_table(document.as_table_mut(), "layout")?;
+        layout_table.insert(
+            "order",
+            value(Array::from_iter(layout.order.iter().map(String::as_str))),
+        );
+        layout_table.insert(
+            "hidden",
+            value(Array::from_iter(layout.hidden.iter().map(String::as_str))),
+        );
+        layout_table.insert("table_of_contents", value(layout.table_of_contents));
+        let theme = child_table(document.as_table_mut(), "theme")?;
+        child_table(theme, "heatmap")?.insert("palette", value(self.palette.name()));
+
+        write_atomically(Path::new(config_path), document.to_string())?;
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let [sidebar, preview] =
+            Layout::horizontal([Constraint::Length(36), Constraint::Fill(1)]).areas(main);
+        let [sections, theme] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(sidebar);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let style = if entry.visible {
+                    Style::new()
+                } else {
+                    Style::new().fg(Color::DarkGray)
+                };
+                let checkbox = if entry.visible { "[x]" } else { "[ ]" };
+                ListItem::new(format!("{} {}", checkbox, entry.title)).style(style)
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(" Sections "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, sections, &mut self.list);
+
+        let mut swatches = vec![Span::raw(format!("{:<11}", self.palette.name()))];
+        for color in self.palette.colors() {
+            swatches.push(Span::styled(
+                "██",
+                Style::new().fg(hex_color(color, self.color_depth)),
+            ));
+        }
+        frame.render_widget(
+            Paragraph::new(Line::from(swatches))
+                .block(Block::bordered().title(" Heatmap palette ")),
+            theme,
+        );
+
+        let title = if self.palette != self.rendered_palette {
+            format!(
+                " Preview (heatmaps keep the {} palette until the next run) ",
+                self.rendered_palette.name()
+            )
+        } else if self.dirty {
+            " Preview (unsaved) ".to_string()
+        } else {
+            " Preview ".to_string()
+        };
+        frame.render_widget(
+            Paragraph::new(self.preview())
+                .block(Block::bordered().title(title))
+                .scroll((self.scroll, 0)),
+            preview,
+        );
+
+        frame.render_widget(
+            Paragraph::new(self.message.as_deref().unwrap_or(HELP))
+                .style(Style::new().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn run(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        config_path: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                KeyCode::Char('q') | KeyCode::Esc => {
+                    if !self.dirty || self.confirm_quit {
+                        return Ok(());
+                    }
+                    self.confirm_quit = true;
+                    self.message =
+                        Some("Unsaved changes: press q again to discard them, s to save".into());
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::Char('K') => self.move_selected(-1),
+                KeyCode::Char('J') => self.move_selected(1),
+                KeyCode::Char(' ') => self.toggle_selected(),
+                KeyCode::Char('c') => {
+                    self.table_of_contents = !self.table_of_contents;
+                    self.changed();
+                }
+                KeyCode::Char('t') => self.cycle_palette(),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::Char('s') => {
+                    self.message = Some(match self.save(config_path) {
+                        Ok(()) => {
+                            self.dirty = false;
+                            format!("Saved to {}", config_path)
+                        }
+                        Err(e) => format!("Failed to save: {}", e),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// `parent[key]` as a table, created if missing; intermediate ones stay implicit so no empty
+/// `[theme]` header is written.
+fn child_table<'a>(parent: &'a mut Table, key: &str) -> Result<&'a mut Table, String> {
+    parent
+        .entry(key)
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| format!("`{}` in the config is not a table", key))
+}
+
+fn hex_color(hex: &str, depth: ColorDepth) -> Color {
+    let rgb = hex_rgb(hex);
+    match depth {
+        ColorDepth::TrueColor => Color::Rgb(rgb.0, rgb.1, rgb.2),
+        ColorDepth::Palette256 => Color::Indexed(nearest_256(rgb)),
+        ColorDepth::Palette16 => [
+            Color::Black,
+            Color::Red,
+            Color::Green,
+            Color::Yellow,
+            Color::Blue,
+            Color::Magenta,
+            Color::Cyan,
+            Color::Gray,
+            Color::DarkGray,
+            Color::LightRed,
+            Color::LightGreen,
+            Color::LightYellow,
+            Color::LightBlue,
+            Color::LightMagenta,
+            Color::LightCyan,
+            Color::White,
+        ][nearest_16(rgb) as usize],
+    }
+}
+
+/// Edits section order and visibility, the table of contents and the heatmap palette in
+/// `source`, previewing the README from the sections cached by the last run.
+pub fn run(source: &ConfigSource) -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = source.path.as_str();
+    if is_remote(config_path) {
+        return Err(format!("tui edits local configs only, not {}", config_path).into());
+    }
+    if !config_path.ends_with(".toml") {
+        return Err(format!("tui edits TOML configs only, not {}", config_path).into());
+    }
+    let config = load_config(source, None)?;
+    let sections = load_sections(&config.username).ok_or_else(|| {
+        format!(
+            "no cached sections for {}; generate the README once before using tui",
+            config.username
+        )
+    })?;
+    let mut editor = Editor::new(
+        &config.username,
+        sections,
+        &config.layout,
+        config.theme.heatmap.palette,
+        &config.style,
+    );
+
+    let mut terminal = ratatui::init();
+    let result = editor.run(&mut terminal, config_path);
+    ratatui::restore();
+    result
+}
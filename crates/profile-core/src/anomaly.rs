@@ -0,0 +1,20 @@
+use std::cell::RefCell;
+
+thread_local! {
+    // Per thread, so profiles generated side by side in batch mode keep their anomalies apart.
+    static ANOMALIES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Logs a data problem the run worked around (missing field, failed widget, truncated page).
+///
+/// In the default lenient mode that is all that happens; `--strict` fails the run if any were
+/// reported before the README is written.
+pub fn report_anomaly(message: impl Into<String>) {
+    let message = message.into();
+    eprintln!("⚠️ {}", message);
+    ANOMALIES.with(|anomalies| anomalies.borrow_mut().push(message));
+}
+
+pub fn take_anomalies() -> Vec<String> {
+    ANOMALIES.with(|anomalies| anomalies.take())
+}
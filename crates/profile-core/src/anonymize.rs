@@ -0,0 +1,62 @@
+use crate::profile::ProfileData;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Placeholder for the exporting user's own login.
+const USER_PLACEHOLDER: &str = "user";
+
+/// Hands out stable placeholders, so the same real name always maps to the same stand-in.
+struct Placeholders {
+    names: BTreeMap<String, String>,
+    prefix: &'static str,
+}
+
+impl Placeholders {
+    fn new(prefix: &'static str) -> Self {
+        Placeholders {
+            names: BTreeMap::new(),
+            prefix,
+        }
+    }
+
+    fn get(&mut self, name: &str) -> String {
+        let next = self.names.len() + 1;
+        self.names
+            .entry(name.to_lowercase())
+            .or_insert_with(|| format!("{}-{}", self.prefix, next))
+            .clone()
+    }
+}
+
+/// Replaces account and repository names in `data` with placeholders.
+///
+/// Events are reduced to the fields the renderers read (type, repository, time), since
+/// payloads carry commit messages, emails and other people's logins.
+pub fn anonymize(data: &mut ProfileData, username: &str) {
+    let mut owners = Placeholders::new("owner");
+    let mut repos = Placeholders::new("repo");
+
+    for activity in &mut data.activities {
+        let repo = activity["repo"]["name"].as_str().unwrap_or("");
+        let name = match repo.split_once('/') {
+            Some((owner, name)) => {
+                let owner = if owner.eq_ignore_ascii_case(username) {
+                    USER_PLACEHOLDER.to_string()
+                } else {
+                    owners.get(owner)
+                };
+                format!("{}/{}", owner, repos.get(name))
+            }
+            None => repos.get(repo),
+        };
+
+        *activity = json!({
+            "type": activity["type"].clone(),
+            "repo": { "name": name },
+            "created_at": activity["created_at"].clone(),
+        });
+    }
+
+    data.activities
+        .retain(|activity| activity["type"] != Value::Null);
+}
@@ -0,0 +1,271 @@
+//! Turns a picture into braille art for the header, picking the conversion threshold by how
+//! similar the result looks to the picture. Braille cells are a 2×4 grid of dots, so the art
+//! can be rasterized exactly without a font and compared with SSIM.
+//!
+//! Pictures are read as PGM (`convert picture.png picture.pgm`), which needs no image crates,
+//! only the `image` feature.
+//! The same round trip through dots mirrors and scales existing art, so one figure can serve a
+//! compact and a full layout.
+
+use crate::config::ArtCrop;
+
+/// An 8-bit grayscale picture, rows top to bottom.
+pub struct GrayImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl GrayImage {
+    fn get(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    /// The picture scaled to `width`×`height` by averaging the pixels each target pixel covers.
+    fn resize(&self, width: usize, height: usize) -> GrayImage {
+        let span = |index: usize, target: usize, source: usize| {
+            let start = index * source / target;
+            start..((index + 1) * source / target).max(start + 1).min(source)
+        };
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let (rows, columns) = (span(y, height, self.height), span(x, width, self.width));
+                let count = (rows.len() * columns.len()) as u64;
+                let sum: u64 = rows
+                    .flat_map(|sy| columns.clone().map(move |sx| (sx, sy)))
+                    .map(|(sx, sy)| self.get(sx, sy) as u64)
+                    .sum();
+                pixels.push((sum / count) as u8);
+            }
+        }
+        GrayImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// Reads a binary (`P5`) or plain (`P2`) PGM file.
+#[cfg(feature = "image")]
+pub fn parse_pgm(bytes: &[u8]) -> Result<GrayImage, String> {
+    // The header is four whitespace-separated tokens, with `#` comments to the end of the line.
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while tokens.len() < 4 && position < bytes.len() {
+        match bytes[position] {
+            b'#' => {
+                while position < bytes.len() && bytes[position] != b'\n' {
+                    position += 1;
+                }
+            }
+            c if c.is_ascii_whitespace() => position += 1,
+            _ => {
+                let start = position;
+                while position < bytes.len() && !bytes[position].is_ascii_whitespace() {
+                    position += 1;
+                }
+                tokens.push(String::from_utf8_lossy(&bytes[start..position]).into_owned());
+            }
+        }
+    }
+    let [magic, width, height, max] =
+        <[String; 4]>::try_from(tokens).map_err(|_| "truncated PGM header")?;
+    let number = |token: &str| {
+        token
+            .parse::<usize>()
+            .map_err(|_| format!("invalid PGM header value `{}`", token))
+    };
+    let (width, height, max) = (number(&width)?, number(&height)?, number(&max)?);
+    if width == 0 || height == 0 || max == 0 || max > 255 {
+        return Err("only 8-bit PGM pictures with a size are supported".to_string());
+    }
+    let scale = |value: usize| (value.min(max) * 255 / max) as u8;
+
+    let pixels: Vec<u8> = match magic.as_str() {
+        // A single whitespace byte separates the header from the pixels.
+        "P5" => bytes
+            .get(position + 1..position + 1 + width * height)
+            .ok_or("truncated PGM pixel data")?
+            .iter()
+            .map(|value| scale(*value as usize))
+            .collect(),
+        "P2" => String::from_utf8_lossy(&bytes[position..])
+            .split_ascii_whitespace()
+            .take(width * height)
+            .map(|value| number(value).map(scale))
+            .collect::<Result<_, _>>()?,
+        _ => return Err(format!("not a PGM file (magic `{}`)", magic)),
+    };
+    if pixels.len() != width * height {
+        return Err("truncated PGM pixel data".to_string());
+    }
+    Ok(GrayImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Bit of each dot of a braille cell, by row and column.
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// `image` at the resolution of `columns` braille cells, keeping its aspect ratio.
+fn dot_grid(image: &GrayImage, columns: usize) -> GrayImage {
+    let width = columns * 2;
+    let rows = ((image.height * width) as f64 / image.width as f64 / 4.0)
+        .round()
+        .max(1.0) as usize;
+    image.resize(width, rows * 4)
+}
+
+/// Braille art of a dot-resolution picture: a dot wherever the picture is darker than
+/// `threshold`.
+fn braille(dots: &GrayImage, threshold: u8) -> String {
+    let mut art = String::new();
+    for row in 0..dots.height / 4 {
+        for column in 0..dots.width / 2 {
+            let mut bits = 0;
+            for (dy, row_bits) in DOTS.iter().enumerate() {
+                for (dx, bit) in row_bits.iter().enumerate() {
+                    if dots.get(column * 2 + dx, row * 4 + dy) < threshold {
+                        bits |= bit;
+                    }
+                }
+            }
+            art.push(char::from_u32(0x2800 + bits).unwrap_or(' '));
+        }
+        art.push('\n');
+    }
+    art
+}
+
+/// Draws braille art back into pixels, one per dot: black where a dot is set, white elsewhere.
+pub fn rasterize_braille(art: &str) -> GrayImage {
+    let lines: Vec<Vec<char>> = art.lines().map(|line| line.chars().collect()).collect();
+    let width = lines.iter().map(Vec::len).max().unwrap_or(0) * 2;
+    let height = lines.len() * 4;
+    let mut pixels = vec![255; width * height];
+    for (row, line) in lines.iter().enumerate() {
+        for (column, c) in line.iter().enumerate() {
+            let bits = (*c as u32).wrapping_sub(0x2800);
+            for (dy, row_bits) in DOTS.iter().enumerate() {
+                for (dx, bit) in row_bits.iter().enumerate() {
+                    if bits < 0x100 && bits & bit != 0 {
+                        pixels[(row * 4 + dy) * width + column * 2 + dx] = 0;
+                    }
+                }
+            }
+        }
+    }
+    GrayImage {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// Mean structural similarity of two same-sized pictures over 8×8 windows: 1.0 for identical
+/// pictures, around 0 for unrelated ones.
+pub fn ssim(a: &GrayImage, b: &GrayImage) -> f64 {
+    const WINDOW: usize = 8;
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+    let mut total = 0.0;
+    let mut windows = 0;
+    for top in (0..a.height.min(b.height)).step_by(WINDOW) {
+        for left in (0..a.width.min(b.width)).step_by(WINDOW) {
+            let mut points = Vec::new();
+            for y in top..(top + WINDOW).min(a.height).min(b.height) {
+                for x in left..(left + WINDOW).min(a.width).min(b.width) {
+                    points.push((a.get(x, y) as f64, b.get(x, y) as f64));
+                }
+            }
+            let n = points.len() as f64;
+            let mean_a = points.iter().map(|p| p.0).sum::<f64>() / n;
+            let mean_b = points.iter().map(|p| p.1).sum::<f64>() / n;
+            let variance_a = points.iter().map(|p| (p.0 - mean_a).powi(2)).sum::<f64>() / n;
+            let variance_b = points.iter().map(|p| (p.1 - mean_b).powi(2)).sum::<f64>() / n;
+            let covariance = points
+                .iter()
+                .map(|p| (p.0 - mean_a) * (p.1 - mean_b))
+                .sum::<f64>()
+                / n;
+            total += ((2.0 * mean_a * mean_b + C1) * (2.0 * covariance + C2))
+                / ((mean_a.powi(2) + mean_b.powi(2) + C1) * (variance_a + variance_b + C2));
+            windows += 1;
+        }
+    }
+    if windows == 0 {
+        0.0
+    } else {
+        total / windows as f64
+    }
+}
+
+/// The conversion of `image` at `columns` cells wide whose rasterized art is most similar to
+/// the picture, as `(art, threshold, ssim)`.
+pub fn best_braille(image: &GrayImage, columns: usize) -> (String, u8, f64) {
+    let dots = dot_grid(image, columns);
+    (16..=240)
+        .step_by(8)
+        .map(|threshold| {
+            let art = braille(&dots, threshold as u8);
+            let score = ssim(&dots, &rasterize_braille(&art));
+            (art, threshold as u8, score)
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .expect("threshold range is not empty")
+}
+
+/// Whether every character of the art is a braille cell or whitespace, so it can be taken apart
+/// into dots.
+pub fn is_braille(lines: &[String]) -> bool {
+    lines
+        .iter()
+        .flat_map(|line| line.chars())
+        .all(|c| c.is_whitespace() || ('\u{2800}'..='\u{28ff}').contains(&c))
+}
+
+/// The art with `crop` cells trimmed off each edge; works on any text.
+pub fn crop_art(lines: &[String], crop: &ArtCrop) -> Vec<String> {
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let columns = width.saturating_sub(crop.left + crop.right);
+    let rows = lines.len().saturating_sub(crop.top + crop.bottom);
+    lines
+        .iter()
+        .skip(crop.top)
+        .take(rows)
+        .map(|line| line.chars().skip(crop.left).take(columns).collect())
+        .collect()
+}
+
+fn lines_of(art: &str) -> Vec<String> {
+    art.lines().map(str::to_string).collect()
+}
+
+/// Braille art flipped left to right, dot by dot.
+pub fn mirror_art(lines: &[String]) -> Vec<String> {
+    let mut dots = rasterize_braille(&lines.join("\n"));
+    let width = dots.width.max(1);
+    for row in dots.pixels.chunks_mut(width) {
+        row.reverse();
+    }
+    lines_of(&braille(&dots, 128))
+}
+
+/// Braille art redrawn `columns` cells wide, keeping its aspect ratio. The art is rasterized and
+/// converted back like a picture, so lines thinner than a cell at the new size survive where the
+/// threshold allows.
+pub fn scale_art(lines: &[String], columns: usize) -> Vec<String> {
+    let dots = rasterize_braille(&lines.join("\n"));
+    if dots.width == 0 || columns == 0 {
+        return lines.to_vec();
+    }
+    lines_of(&best_braille(&dots, columns).0)
+}
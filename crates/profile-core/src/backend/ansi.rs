@@ -0,0 +1,126 @@
+//! The document for a terminal, coloured with ANSI escape codes.
+
+use crate::backend::text::{callout_color, layout, LineStyle};
+use crate::document::Document;
+use crate::render::hex_rgb;
+use std::env;
+
+const RESET: &str = "\x1b[0m";
+
+/// The xterm defaults for the 16 basic colours; terminals differ, but not by much.
+const PALETTE_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Channel levels of the 6×6×6 cube in the 256-colour palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// How many colours the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorDepth {
+    TrueColor,
+    Palette256,
+    Palette16,
+}
+
+impl ColorDepth {
+    /// From `COLORTERM` and `TERM`, the way most terminal programs decide. Anything that
+    /// doesn't claim more, such as CI logs or a bare SSH session, gets the 16 basic colours.
+    pub fn detect() -> Self {
+        let var = |name| env::var(name).unwrap_or_default().to_lowercase();
+        if matches!(var("COLORTERM").as_str(), "truecolor" | "24bit") {
+            ColorDepth::TrueColor
+        } else if var("TERM").contains("256color") {
+            ColorDepth::Palette256
+        } else {
+            ColorDepth::Palette16
+        }
+    }
+}
+
+/// Squared distance, weighted towards green like the eye.
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    2 * d(r1, r2) + 4 * d(g1, g2) + 3 * d(b1, b2)
+}
+
+/// Index of the nearest of the 16 basic colours.
+pub fn nearest_16(rgb: (u8, u8, u8)) -> u8 {
+    (0..PALETTE_16.len())
+        .min_by_key(|&i| distance(rgb, PALETTE_16[i]))
+        .unwrap_or(0) as u8
+}
+
+/// Index of the nearest colour in the 256-colour palette's cube or grey ramp; the 16 basic
+/// colours at its start are skipped, since terminals theme them.
+pub fn nearest_256((r, g, b): (u8, u8, u8)) -> u8 {
+    let level = |channel: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - channel as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let mean = (r as u32 + g as u32 + b as u32) / 3;
+    let step = (mean.saturating_sub(3) / 10).min(23) as u8;
+    let grey = 8 + 10 * step;
+
+    match distance((r, g, b), (grey, grey, grey)) < distance((r, g, b), cube) {
+        true => 232 + step,
+        false => (16 + 36 * ri + 6 * gi + bi) as u8,
+    }
+}
+
+/// The escape code that sets the foreground to `hex`, as close as `depth` allows.
+pub fn foreground(hex: &str, depth: ColorDepth) -> String {
+    let rgb = hex_rgb(hex);
+    match depth {
+        ColorDepth::TrueColor => format!("\x1b[38;2;{};{};{}m", rgb.0, rgb.1, rgb.2),
+        ColorDepth::Palette256 => format!("\x1b[38;5;{}m", nearest_256(rgb)),
+        ColorDepth::Palette16 => match nearest_16(rgb) {
+            index @ 0..=7 => format!("\x1b[{}m", 30 + index),
+            index => format!("\x1b[{}m", 90 + index - 8),
+        },
+    }
+}
+
+pub fn render(document: &Document, depth: ColorDepth) -> String {
+    let mut output = String::new();
+    for line in layout(document) {
+        match line.style {
+            LineStyle::Plain => output += &line.text,
+            LineStyle::Heading => output += &format!("\x1b[1m{}{}", line.text, RESET),
+            LineStyle::Muted => output += &format!("\x1b[2m{}{}", line.text, RESET),
+            LineStyle::Callout(kind) => {
+                output += &format!(
+                    "{}{}{}",
+                    foreground(callout_color(kind), depth),
+                    line.text,
+                    RESET
+                );
+            }
+            LineStyle::Art => {
+                let color = document.art_color.as_deref().unwrap_or_default();
+                output += &format!("{}{}{}", foreground(color, depth), line.text, RESET);
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
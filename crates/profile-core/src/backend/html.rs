@@ -0,0 +1,150 @@
+//! A standalone HTML page, for looking at a profile outside GitHub.
+
+use crate::backend::text::callout_color;
+use crate::document::{Align, Block, Document, Image};
+use crate::sanitize::html_escape;
+
+const STYLE: &str = "\
+body { max-width: 60em; margin: 2em auto; padding: 0 1em; font-family: sans-serif; background: #0d1117; color: #e6edf3; }
+a { color: #4493f8; }
+pre { line-height: 1.2; overflow-x: auto; }
+table { border-collapse: collapse; }
+th, td { padding: 0.2em 0.8em; border: 1px solid #30363d; }
+blockquote { margin: 1em 0; padding: 0 1em; border-left: 0.25em solid; }
+nav, .center { text-align: center; }";
+
+/// An `<img>`, wrapped in a link if it has one. Shared with the markdown backend, whose image
+/// rows are inline HTML.
+pub fn image_tag(image: &Image) -> String {
+    let mut tag = format!("<img src=\"{}\"", html_escape(&image.src));
+    if let Some(size) = image.size {
+        tag += &format!(" width=\"{0}\" height=\"{0}\"", size);
+    }
+    tag += &format!(" alt=\"{}\"", html_escape(&image.alt));
+    if let Some(title) = &image.title {
+        tag += &format!(" title=\"{}\"", html_escape(title));
+    }
+    tag += "/>";
+    match &image.link {
+        Some(link) => format!("<a href=\"{}\">{}</a>", html_escape(link), tag),
+        None => tag,
+    }
+}
+
+fn text_align(align: Align) -> &'static str {
+    match align {
+        Align::Left => "left",
+        Align::Center => "center",
+        Align::Right => "right",
+    }
+}
+
+fn block(block: &Block) -> String {
+    match block {
+        Block::Art(art) => {
+            let lines: Vec<String> = art.lines().map(html_escape).collect();
+            format!("<pre>{}</pre>\n", lines.join("\n"))
+        }
+        Block::Table { columns, rows } => {
+            let mut table = "<table>\n<thead><tr>".to_string();
+            for column in columns {
+                table += &format!("<th>{}</th>", html_escape(&column.heading));
+            }
+            table += "</tr></thead>\n<tbody>\n";
+            for cells in rows {
+                table += "<tr>";
+                for (column, cell) in columns.iter().zip(cells) {
+                    table += &format!(
+                        "<td style=\"text-align: {}\">{}</td>",
+                        text_align(column.align),
+                        html_escape(cell)
+                    );
+                }
+                table += "</tr>\n";
+            }
+            table + "</tbody>\n</table>\n"
+        }
+        Block::Bars { bars, .. } => {
+            let mut table = "<table>\n".to_string();
+            for (label, percentage) in bars {
+                table += &format!(
+                    "<tr><td>{0}</td><td><progress max=\"100\" value=\"{1:.1}\"></progress></td><td style=\"text-align: right\">{1:.1}%</td></tr>\n",
+                    html_escape(label),
+                    percentage
+                );
+            }
+            table + "</table>\n"
+        }
+        Block::Text {
+            html,
+            centered: false,
+        } => format!("<p>{}</p>\n", html),
+        Block::Text {
+            html,
+            centered: true,
+        } => format!("<p class=\"center\">{}</p>\n", html),
+        Block::Images { caption, images } => {
+            let mut paragraph = "<p class=\"center\">\n".to_string();
+            if let Some(caption) = caption {
+                paragraph += caption;
+                paragraph += "<br/>\n";
+            }
+            for image in images {
+                paragraph += &image_tag(image);
+                paragraph.push('\n');
+            }
+            paragraph + "</p>\n"
+        }
+        Block::Callout { kind, blocks: inner } => format!(
+            "<blockquote style=\"border-color: {0}\">\n<p style=\"color: {0}\"><b>{1}</b></p>\n{2}</blockquote>\n",
+            callout_color(*kind),
+            kind.name(),
+            blocks(inner)
+        ),
+        Block::Quote(inner) => format!("<blockquote>\n{}</blockquote>\n", blocks(inner)),
+        Block::Rule => "<hr/>\n".to_string(),
+    }
+}
+
+fn blocks(blocks: &[Block]) -> String {
+    blocks.iter().map(block).collect()
+}
+
+pub fn render(document: &Document) -> String {
+    let mut body = match &document.header {
+        Some(header) => blocks(header),
+        None => format!("<h2>{}</h2>\n", html_escape(&document.title)),
+    };
+
+    let headings = document.headings();
+    if document.table_of_contents && !headings.is_empty() {
+        let links: Vec<String> = headings
+            .iter()
+            .map(|heading| {
+                format!(
+                    "<a href=\"#{}\">{}</a>",
+                    heading.anchor,
+                    html_escape(&heading.title)
+                )
+            })
+            .collect();
+        body += &format!("<nav>{}</nav>\n", links.join(" · "));
+    }
+
+    let mut anchors = headings.iter().map(|heading| &heading.anchor);
+    for section in &document.sections {
+        if let Some(title) = &section.title {
+            let anchor = anchors.next().map_or("", String::as_str);
+            body += &format!("<h4 id=\"{}\">{}</h4>\n", anchor, html_escape(title));
+        }
+        body += &blocks(&section.blocks);
+    }
+    body += &blocks(&document.footer);
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\"/>\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        html_escape(&document.title),
+        STYLE,
+        body
+    )
+}
@@ -0,0 +1,92 @@
+//! The README: GitHub-flavoured markdown with inline HTML, preformatted blocks in code fences.
+
+use crate::backend::html::image_tag;
+use crate::backend::text::{bars_lines, table_lines};
+use crate::document::{Block, Document, FenceLanguage};
+use crate::sanitize::{fence_safe, markdown_escape};
+
+fn fence(language: FenceLanguage, lines: impl IntoIterator<Item = String>) -> String {
+    let mut fenced = format!("```{}\n", language.tag());
+    for line in lines {
+        fenced += &fence_safe(&line);
+        fenced.push('\n');
+    }
+    fenced + "```\n\n"
+}
+
+/// `inner` as a blockquote under an optional first line such as `[!NOTE]`.
+fn quote(first_line: Option<String>, inner: &[Block], language: FenceLanguage) -> String {
+    let mut quoted = first_line.map_or(String::new(), |line| format!("> {}\n", line));
+    for line in blocks(inner, language).trim_end().lines() {
+        if line.is_empty() {
+            quoted += ">\n";
+        } else {
+            quoted += &format!("> {}\n", line);
+        }
+    }
+    quoted + "\n"
+}
+
+/// `block` followed by a blank line.
+fn block(block: &Block, language: FenceLanguage) -> String {
+    match block {
+        Block::Art(art) => fence(language, art.lines().map(str::to_string)),
+        Block::Table { columns, rows } => fence(language, table_lines(columns, rows)),
+        Block::Bars { bars, art } => fence(language, bars_lines(bars, art)),
+        Block::Text {
+            html,
+            centered: false,
+        } => format!("{}\n\n", html),
+        Block::Text {
+            html,
+            centered: true,
+        } => format!("<p align=\"center\">{}</p>\n\n", html),
+        Block::Images { caption, images } => {
+            let mut paragraph = "<p align=\"center\">\n".to_string();
+            if let Some(caption) = caption {
+                paragraph += caption;
+                paragraph += "<br/>\n";
+            }
+            for image in images {
+                paragraph += &image_tag(image);
+                paragraph.push('\n');
+            }
+            paragraph + "</p>\n\n"
+        }
+        Block::Callout {
+            kind,
+            blocks: inner,
+        } => quote(Some(format!("[!{}]", kind.name())), inner, language),
+        Block::Quote(inner) => quote(None, inner, language),
+        Block::Rule => "---\n\n".to_string(),
+    }
+}
+
+fn blocks(blocks: &[Block], language: FenceLanguage) -> String {
+    blocks.iter().map(|b| block(b, language)).collect()
+}
+
+pub fn render(document: &Document) -> String {
+    let mut markdown = match &document.header {
+        Some(header) => blocks(header, document.fence),
+        None => format!("## {}\n\n", markdown_escape(&document.title)),
+    };
+
+    let headings = document.headings();
+    if document.table_of_contents && !headings.is_empty() {
+        let links: Vec<String> = headings
+            .iter()
+            .map(|heading| format!("[{}](#{})", markdown_escape(&heading.title), heading.anchor))
+            .collect();
+        markdown += &format!("**Contents:** {}\n\n", links.join(" · "));
+    }
+
+    for section in &document.sections {
+        if let Some(title) = &section.title {
+            markdown += &format!("#### {}\n", markdown_escape(title));
+        }
+        markdown += &blocks(&section.blocks, section.fence.unwrap_or(document.fence));
+    }
+    markdown += &blocks(&document.footer, document.fence);
+    format!("{}\n", markdown.trim_end())
+}
@@ -0,0 +1,9 @@
+//! Layouts of a [`Document`](crate::document::Document): markdown for the README, a
+//! standalone HTML page, an SVG picture of it and ANSI-coloured text for the terminal.
+
+pub mod ansi;
+pub mod html;
+pub mod markdown;
+#[cfg(feature = "svg")]
+pub mod svg;
+pub mod text;
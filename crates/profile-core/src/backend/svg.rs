@@ -0,0 +1,46 @@
+//! A picture of the document as it looks in a terminal, one `<text>` element per line.
+
+use crate::backend::text::{callout_color, layout, LineStyle};
+use crate::document::Document;
+use crate::sanitize::html_escape;
+
+const CHAR_WIDTH: f64 = 8.4;
+const LINE_HEIGHT: usize = 18;
+const PADDING: usize = 16;
+
+pub fn render(document: &Document) -> String {
+    let lines = layout(document);
+    let columns = lines
+        .iter()
+        .map(|line| line.text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let width = (columns as f64 * CHAR_WIDTH).ceil() as usize + 2 * PADDING;
+    let height = lines.len() * LINE_HEIGHT + 2 * PADDING;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"monospace\" font-size=\"14\">\n<rect width=\"100%\" height=\"100%\" rx=\"6\" fill=\"#0d1117\"/>\n",
+        width, height
+    );
+    for (i, line) in lines.iter().enumerate() {
+        if line.text.is_empty() {
+            continue;
+        }
+        let (fill, weight) = match line.style {
+            LineStyle::Plain => ("#e6edf3", "normal"),
+            LineStyle::Heading => ("#e6edf3", "bold"),
+            LineStyle::Muted => ("#7d8590", "normal"),
+            LineStyle::Callout(kind) => (callout_color(kind), "normal"),
+            LineStyle::Art => (document.art_color.as_deref().unwrap_or_default(), "normal"),
+        };
+        svg += &format!(
+            "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-weight=\"{}\" xml:space=\"preserve\">{}</text>\n",
+            PADDING,
+            PADDING + (i + 1) * LINE_HEIGHT - 4,
+            html_escape(fill),
+            weight,
+            html_escape(&line.text)
+        );
+    }
+    svg + "</svg>\n"
+}
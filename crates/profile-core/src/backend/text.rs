@@ -0,0 +1,239 @@
+//! Plain-text layout of blocks, shared by the markdown code fences and the ANSI and SVG
+//! backends so a table or bar chart is drawn the same way everywhere.
+
+use crate::document::{Align, Block, CalloutKind, Column, Document};
+use crate::render::create_ascii_bar;
+
+const BAR_WIDTH: usize = 20;
+const BAR_LABEL_WIDTH: usize = 12;
+/// Label, bar and percentage: "Rust         [████████████████████] 100.0%".
+const BAR_LINE_WIDTH: usize = BAR_LABEL_WIDTH + 26;
+/// Width the art beside the bars is right-aligned in.
+const BAR_ART_OFFSET: usize = 50;
+
+const RULE_WIDTH: usize = 60;
+
+/// What a line is, for backends that style text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineStyle {
+    Plain,
+    Heading,
+    Muted,
+    Callout(CalloutKind),
+    /// The header's art, when [`Document::art_color`] recolours it.
+    Art,
+}
+
+pub struct StyledLine {
+    pub text: String,
+    pub style: LineStyle,
+}
+
+/// GitHub's alert colours.
+pub fn callout_color(kind: CalloutKind) -> &'static str {
+    match kind {
+        CalloutKind::Note => "#4493f8",
+        CalloutKind::Tip => "#3fb950",
+        CalloutKind::Important => "#ab7df8",
+        CalloutKind::Warning => "#d29922",
+        CalloutKind::Caution => "#f85149",
+    }
+}
+
+fn pad(text: &str, width: usize, align: Align) -> String {
+    match align {
+        Align::Left => format!("{:<width$}", text),
+        Align::Center => format!("{:^width$}", text),
+        Align::Right => format!("{:>width$}", text),
+    }
+}
+
+/// An ASCII grid with a centered heading row.
+pub fn table_lines(columns: &[Column], rows: &[Vec<String>]) -> Vec<String> {
+    let border = columns.iter().fold("+".to_string(), |line, column| {
+        line + &"-".repeat(column.width + 2) + "+"
+    });
+    let row = |cells: Vec<String>| {
+        cells
+            .iter()
+            .fold("|".to_string(), |line, cell| line + " " + cell + " |")
+    };
+
+    let mut lines = vec![
+        border.clone(),
+        row(columns
+            .iter()
+            .map(|column| pad(&column.heading, column.width, Align::Center))
+            .collect()),
+        border.clone(),
+    ];
+    for cells in rows {
+        lines.push(row(columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let cell = cells.get(i).map_or("", String::as_str);
+                pad(cell, column.width, column.align)
+            })
+            .collect()));
+    }
+    lines.push(border);
+    lines
+}
+
+/// One bar per row; the art is bottom-aligned with them, clipped at the top if there are
+/// fewer bars than art lines.
+pub fn bars_lines(bars: &[(String, f64)], art: &[String]) -> Vec<String> {
+    bars.iter()
+        .enumerate()
+        .map(|(i, (label, percentage))| {
+            let line = format!(
+                "{:<BAR_LABEL_WIDTH$} {} {:.1}%",
+                label,
+                create_ascii_bar(*percentage, BAR_WIDTH),
+                percentage
+            );
+            match (i + art.len()).checked_sub(bars.len()) {
+                Some(art_index) => format!(
+                    "{:<BAR_LINE_WIDTH$} {:>BAR_ART_OFFSET$}",
+                    line, art[art_index]
+                ),
+                None => line,
+            }
+        })
+        .collect()
+}
+
+/// The text of inline HTML: tags dropped and the entities `html_escape` produces decoded.
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn push(lines: &mut Vec<StyledLine>, text: impl Into<String>, style: LineStyle) {
+    lines.push(StyledLine {
+        text: text.into(),
+        style,
+    });
+}
+
+/// Lines of `block` followed by a blank one. `header` gives art blocks [`LineStyle::Art`], even
+/// inside an alert.
+fn block_lines(block: &Block, style: LineStyle, header: bool, lines: &mut Vec<StyledLine>) {
+    match block {
+        Block::Art(art) => {
+            let style = if header { LineStyle::Art } else { style };
+            for line in art.lines() {
+                push(lines, line, style);
+            }
+        }
+        Block::Table { columns, rows } => {
+            for line in table_lines(columns, rows) {
+                push(lines, line, style);
+            }
+        }
+        Block::Bars { bars, art } => {
+            for line in bars_lines(bars, art) {
+                push(lines, line, style);
+            }
+        }
+        Block::Text { html, .. } => push(lines, strip_tags(html), style),
+        Block::Images { caption, images } => {
+            if let Some(caption) = caption {
+                push(lines, strip_tags(caption), style);
+            }
+            for image in images {
+                push(lines, format!("[{}]", image.alt), style);
+            }
+        }
+        Block::Callout { kind, blocks } => {
+            let style = LineStyle::Callout(*kind);
+            let mut inner = Vec::new();
+            for block in blocks {
+                block_lines(block, style, header, &mut inner);
+            }
+            trim_blank_lines(&mut inner);
+
+            push(lines, format!("▌ {}", kind.name()), style);
+            for line in inner {
+                let line_style = match line.style {
+                    LineStyle::Art => LineStyle::Art,
+                    _ => style,
+                };
+                push(lines, format!("▌ {}", line.text).trim_end(), line_style);
+            }
+        }
+        Block::Quote(blocks) => {
+            let mut inner = Vec::new();
+            for block in blocks {
+                block_lines(block, style, header, &mut inner);
+            }
+            trim_blank_lines(&mut inner);
+            for line in inner {
+                push(lines, format!("▌ {}", line.text).trim_end(), line.style);
+            }
+        }
+        Block::Rule => push(lines, "─".repeat(RULE_WIDTH), LineStyle::Muted),
+    }
+    push(lines, "", LineStyle::Plain);
+}
+
+fn trim_blank_lines(lines: &mut Vec<StyledLine>) {
+    while lines.last().is_some_and(|line| line.text.is_empty()) {
+        lines.pop();
+    }
+}
+
+/// The whole document as lines of text, as the ANSI and SVG backends show it.
+pub fn layout(document: &Document) -> Vec<StyledLine> {
+    let mut lines = Vec::new();
+    match &document.header {
+        Some(header) => {
+            let recolored = document.art_color.is_some();
+            for block in header {
+                block_lines(block, LineStyle::Plain, recolored, &mut lines);
+            }
+        }
+        None => {
+            push(&mut lines, &document.title, LineStyle::Heading);
+            push(&mut lines, "", LineStyle::Plain);
+        }
+    }
+
+    let headings = document.headings();
+    if document.table_of_contents && !headings.is_empty() {
+        let titles: Vec<&str> = headings
+            .iter()
+            .map(|heading| heading.title.as_str())
+            .collect();
+        push(&mut lines, titles.join(" · "), LineStyle::Muted);
+        push(&mut lines, "", LineStyle::Plain);
+    }
+
+    for section in &document.sections {
+        if let Some(title) = &section.title {
+            push(&mut lines, title, LineStyle::Heading);
+        }
+        for block in &section.blocks {
+            block_lines(block, LineStyle::Plain, false, &mut lines);
+        }
+    }
+    for block in &document.footer {
+        block_lines(block, LineStyle::Plain, false, &mut lines);
+    }
+    trim_blank_lines(&mut lines);
+    lines
+}
@@ -0,0 +1,229 @@
+use crate::backend::markdown;
+use crate::config::{LayoutConfig, StyleConfig};
+use crate::document::{Block, Document, Section};
+use crate::profile::{format_activity, format_github_stats, format_languages};
+use crate::render::Asset;
+use serde_json::Value;
+
+/// Anything that can render itself as a README section, for widgets defined outside this crate.
+pub trait Widget {
+    fn title(&self) -> Option<String>;
+    fn render(&self) -> Vec<Block>;
+}
+
+/// The assembled profile, its README markdown and the images it embeds, which the caller
+/// writes out. `document` can be passed to the other backends.
+pub struct Profile {
+    pub document: Document,
+    pub markdown: String,
+    pub assets: Vec<Asset>,
+}
+
+/// Assembles a profile README from sections in the order they are added.
+///
+/// ```
+/// use profile_core::builder::{ProfileBuilder, Widget};
+/// use profile_core::document::Block;
+/// use serde_json::json;
+///
+/// struct Motto;
+///
+/// impl Widget for Motto {
+///     fn title(&self) -> Option<String> {
+///         Some("Motto".to_string())
+///     }
+///     fn render(&self) -> Vec<Block> {
+///         vec![Block::text("Ship it.")]
+///     }
+/// }
+///
+/// let languages = vec![("Rust".to_string(), 75.0), ("Python".to_string(), 25.0)];
+/// let stats = json!({ "total_stars": 42, "total_commits": 1337 });
+/// let profile = ProfileBuilder::new("octocat")
+///     .with_languages(&languages)
+///     .with_stats(&stats)
+///     .with_widget(Motto)
+///     .build();
+/// assert!(profile.markdown.contains("Rust"));
+/// assert!(profile.markdown.contains("Ship it."));
+/// ```
+pub struct ProfileBuilder {
+    username: String,
+    header: Option<Vec<Block>>,
+    sections: Vec<Section>,
+    assets: Vec<Asset>,
+    footer: Vec<Block>,
+    layout: LayoutConfig,
+    style: StyleConfig,
+    art_color: Option<String>,
+}
+
+impl ProfileBuilder {
+    pub fn new(username: impl Into<String>) -> Self {
+        ProfileBuilder {
+            username: username.into(),
+            header: None,
+            sections: Vec::new(),
+            assets: Vec::new(),
+            footer: Vec::new(),
+            layout: LayoutConfig::default(),
+            style: StyleConfig::default(),
+            art_color: None,
+        }
+    }
+
+    /// Blocks placed above every section; defaults to a `## @username` heading.
+    pub fn with_header(mut self, header: Vec<Block>) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Colour of the header's art blocks in the ANSI and SVG backends.
+    pub fn with_art_color(mut self, color: Option<String>) -> Self {
+        self.art_color = color;
+        self
+    }
+
+    /// Blocks placed after every section.
+    pub fn with_footer(mut self, footer: Vec<Block>) -> Self {
+        self.footer = footer;
+        self
+    }
+
+    pub fn with_section(mut self, section: Section) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Reorders and hides titled sections when the profile is built.
+    pub fn with_layout(mut self, layout: &LayoutConfig) -> Self {
+        self.layout = layout.clone();
+        self
+    }
+
+    /// Fence languages and wrapping of titled sections. The header and footer are left as
+    /// given; callers wrap them with `style.header` and `style.footer`.
+    pub fn with_style(mut self, style: &StyleConfig) -> Self {
+        self.style = style.clone();
+        self
+    }
+
+    /// Every section added so far, in the order added and including ones the layout hides.
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn with_asset(mut self, asset: Asset) -> Self {
+        self.assets.push(asset);
+        self
+    }
+
+    pub fn with_widget(self, widget: impl Widget) -> Self {
+        self.with_section(Section {
+            title: widget.title(),
+            blocks: widget.render(),
+            fence: None,
+            as_of: None,
+            stale: false,
+        })
+    }
+
+    /// Language bars from `(language, percentage)` rows.
+    pub fn with_languages(self, languages: &[(String, f64)]) -> Self {
+        self.with_section(Section::new("Languages", vec![format_languages(languages)]))
+    }
+
+    /// The stats table from the `total_*`, `repos_owned` and `contributed_to` counts.
+    pub fn with_stats(self, stats: &Value) -> Self {
+        self.with_section(Section::new("Stats", vec![format_github_stats(stats)]))
+    }
+
+    /// One line per public event, as returned by the events API.
+    pub fn with_activity(self, activities: &[Value]) -> Self {
+        let lines: Vec<String> = activities.iter().map(format_activity).collect();
+        self.with_section(Section::fenced("Activity", &lines.join("\n")))
+    }
+
+    pub fn build(self) -> Profile {
+        let document = Document {
+            title: format!("@{}", self.username),
+            header: self.header,
+            sections: arrange(self.sections, &self.layout)
+                .into_iter()
+                .map(|section| style_section(section, &self.style))
+                .collect(),
+            footer: self.footer,
+            table_of_contents: self.layout.table_of_contents,
+            fence: self.style.fence,
+            art_color: self.art_color,
+        };
+        Profile {
+            markdown: markdown::render(&document),
+            document,
+            assets: self.assets,
+        }
+    }
+}
+
+/// Drops hidden sections and sorts the titled ones by `layout.order` into the slots titled
+/// sections occupied, so untitled banners stay where they were added.
+pub fn arrange(sections: Vec<Section>, layout: &LayoutConfig) -> Vec<Section> {
+    let mut titled = Vec::new();
+    let slots: Vec<Option<Section>> = sections
+        .into_iter()
+        .filter(|section| {
+            section
+                .title
+                .as_ref()
+                .is_none_or(|title| !layout.hidden.contains(title))
+        })
+        .map(|section| match section.title {
+            Some(_) => {
+                titled.push(section);
+                None
+            }
+            None => Some(section),
+        })
+        .collect();
+
+    titled.sort_by_key(|section| {
+        section
+            .title
+            .as_ref()
+            .and_then(|title| layout.order.iter().position(|ordered| ordered == title))
+            .unwrap_or(layout.order.len())
+    });
+    let mut titled = titled.into_iter();
+    slots
+        .into_iter()
+        .filter_map(|slot| slot.or_else(|| titled.next()))
+        .collect()
+}
+
+/// Adds the "as of" note where `style` asks for it or the data is stale, then applies the
+/// `style.sections` entry for the section's heading, if there is one.
+fn style_section(mut section: Section, style: &StyleConfig) -> Section {
+    let section_style = section
+        .title
+        .as_ref()
+        .and_then(|title| style.sections.get(title));
+    let show_as_of = section_style
+        .and_then(|section_style| section_style.as_of)
+        .unwrap_or(style.as_of);
+    match section.as_of {
+        Some(as_of) if section.stale => section
+            .blocks
+            .push(Block::text(format!("<sub>⚠ stale: as of {}</sub>", as_of))),
+        Some(as_of) if show_as_of => section
+            .blocks
+            .push(Block::text(format!("<sub>as of {}</sub>", as_of))),
+        _ => {}
+    }
+
+    let Some(section_style) = section_style else {
+        return section;
+    };
+    section.fence = section_style.fence.or(section.fence);
+    section.blocks = section_style.wrap.apply(section.blocks);
+    section
+}
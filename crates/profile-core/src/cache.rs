@@ -0,0 +1,123 @@
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+use std::cell::Cell;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+pub const CACHE_DIR: &str = ".cache";
+
+static SKIP_READS: AtomicBool = AtomicBool::new(false);
+static SKIP_WRITES: AtomicBool = AtomicBool::new(false);
+
+/// Stops serving anything from the cache for the rest of the process, and with `writes` also
+/// stops storing to it, so every fetch reaches the HTTP layer (to record or replay it).
+pub fn bypass_cache(writes: bool) {
+    SKIP_READS.store(true, Ordering::SeqCst);
+    SKIP_WRITES.store(writes, Ordering::SeqCst);
+}
+
+// FNV-1a, so cache file names stay stable across toolchain versions.
+pub fn hash_key(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn cache_path(key: &str) -> PathBuf {
+    PathBuf::from(CACHE_DIR).join(format!("{:016x}.json", hash_key(key)))
+}
+
+thread_local! {
+    /// When the oldest entry served on this thread since the last `track_data_age` was fetched.
+    static OLDEST_SERVED: Cell<Option<DateTime<Utc>>> = const { Cell::new(None) };
+}
+
+fn note_served(fetched_at: DateTime<Utc>) {
+    OLDEST_SERVED.with(|oldest| {
+        oldest.set(Some(oldest.get().map_or(fetched_at, |t| t.min(fetched_at))));
+    });
+}
+
+/// Runs `fetch` and returns when the oldest cached data it was served had been fetched, or now
+/// if everything came fresh from the source.
+pub fn track_data_age<T>(fetch: impl FnOnce() -> T) -> (T, DateTime<Utc>) {
+    let outer = OLDEST_SERVED.with(Cell::take);
+    let result = fetch();
+    let inner = OLDEST_SERVED.with(Cell::take);
+    // Whatever encloses this call used the same data.
+    for fetched_at in [outer, inner].into_iter().flatten() {
+        note_served(fetched_at);
+    }
+    (result, inner.unwrap_or_else(Utc::now))
+}
+
+/// Runs `fetch` without counting what it is served towards the data age of an enclosing
+/// `track_data_age`, for lookups such as robots.txt that aren't data shown anywhere.
+pub fn untracked<T>(fetch: impl FnOnce() -> T) -> T {
+    let outer = OLDEST_SERVED.with(Cell::take);
+    let result = fetch();
+    OLDEST_SERVED.with(|oldest| oldest.set(outer));
+    result
+}
+
+fn read_entry(key: &str) -> Option<Value> {
+    if SKIP_READS.load(Ordering::SeqCst) {
+        return None;
+    }
+    let contents = fs::read_to_string(cache_path(key)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn fetched_at(entry: &Value) -> Option<DateTime<Utc>> {
+    let fetched_at = DateTime::parse_from_rfc3339(entry["fetched_at"].as_str()?).ok()?;
+    Some(fetched_at.with_timezone(&Utc))
+}
+
+/// Returns the cached value for `key` if it was stored less than `max_age` ago.
+pub fn read_cache(key: &str, max_age: Duration) -> Option<Value> {
+    let entry = read_entry(key)?;
+    let fetched_at = fetched_at(&entry)?;
+
+    if Utc::now().signed_duration_since(fetched_at) > max_age {
+        return None;
+    }
+    note_served(fetched_at);
+    Some(entry["data"].clone())
+}
+
+/// Returns the cached value for `key` however old it is, for when the source is unavailable.
+pub fn read_stale_cache(key: &str) -> Option<Value> {
+    let entry = read_entry(key)?;
+    if let Some(fetched_at) = fetched_at(&entry) {
+        note_served(fetched_at);
+    }
+    Some(entry["data"].clone())
+}
+
+/// Writes through a temporary file and a rename, so neither a concurrent reader (another
+/// profile in batch mode) nor an interrupted run ever leaves a half-written file behind.
+pub fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let temporary = path.with_extension(format!(
+        "{}-{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+pub fn write_cache(key: &str, data: &Value) {
+    let entry = json!({
+        "key": key,
+        "fetched_at": Utc::now().to_rfc3339(),
+        "data": data,
+    });
+
+    // A failed cache write only costs an extra request next run.
+    if !SKIP_WRITES.load(Ordering::SeqCst) && fs::create_dir_all(CACHE_DIR).is_ok() {
+        let _ = write_atomically(&cache_path(key), entry.to_string());
+    }
+}
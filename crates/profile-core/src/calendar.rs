@@ -0,0 +1,52 @@
+use crate::config::{on_vacation, Vacation};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Daily contribution counts, shared by every widget that looks at the contribution graph.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ContributionCalendar {
+    pub days: BTreeMap<NaiveDate, u64>,
+}
+
+impl ContributionCalendar {
+    /// Days within `from..=to`, in date order.
+    pub fn range(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> impl Iterator<Item = (&NaiveDate, &u64)> {
+        self.days.range(from..=to)
+    }
+
+    /// Consecutive days with contributions up to `today`. A quiet `today` doesn't break the
+    /// streak yet, since the day isn't over, and neither do quiet days on vacation, though only
+    /// days with contributions count towards it. Limited to the days the calendar was fetched for.
+    pub fn current_streak(&self, today: NaiveDate, vacations: &[Vacation]) -> u64 {
+        let mut days = self.days.range(..=today).rev().peekable();
+        if days
+            .peek()
+            .is_some_and(|(date, count)| **date == today && **count == 0)
+        {
+            days.next();
+        }
+        let mut streak = 0;
+        let mut expected = None;
+        for (date, count) in days {
+            if expected.is_some_and(|expected| *date != expected) {
+                break;
+            }
+            if *count > 0 {
+                streak += 1;
+            } else if !on_vacation(vacations, *date) {
+                break;
+            }
+            expected = date.pred_opt();
+        }
+        streak
+    }
+
+    pub fn total(&self, from: NaiveDate, to: NaiveDate) -> u64 {
+        self.range(from, to).map(|(_, count)| count).sum()
+    }
+}
@@ -0,0 +1,66 @@
+//! Captures timed terminal output as an asciinema v2 cast, which `asciinema play` and the
+//! asciinema web player replay.
+
+use serde_json::json;
+
+/// Terminal output with the time each piece appeared, on a virtual clock so nothing waits.
+pub struct Recorder {
+    width: usize,
+    height: usize,
+    /// Seconds since the recording started.
+    time: f64,
+    events: Vec<(f64, String)>,
+}
+
+impl Recorder {
+    pub fn new(width: usize, height: usize) -> Self {
+        Recorder {
+            width,
+            height,
+            time: 0.0,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn pause(&mut self, seconds: f64) {
+        self.time += seconds;
+    }
+
+    /// Writes `text` at the current time. Newlines become `\r\n`, as a terminal expects.
+    pub fn print(&mut self, text: &str) {
+        self.events.push((self.time, text.replace('\n', "\r\n")));
+    }
+
+    /// Writes `text` one character at a time, `delay` seconds apart, like someone typing.
+    pub fn type_text(&mut self, text: &str, delay: f64) {
+        for c in text.chars() {
+            self.print(&c.to_string());
+            self.pause(delay);
+        }
+    }
+
+    /// Writes each line of `text` `delay` seconds after the previous one.
+    pub fn print_lines(&mut self, text: &str, delay: f64) {
+        for line in text.lines() {
+            self.print(&format!("{}\n", line));
+            self.pause(delay);
+        }
+    }
+
+    /// The recording as a cast file: a header line, then one `[time, "o", text]` line per
+    /// event.
+    pub fn to_cast(&self, title: &str) -> String {
+        let header = json!({
+            "version": 2,
+            "width": self.width,
+            "height": self.height,
+            "title": title,
+            "env": { "TERM": "xterm-256color" },
+        });
+        let mut cast = format!("{}\n", header);
+        for (time, text) in &self.events {
+            cast += &format!("{}\n", json!([(time * 1000.0).round() / 1000.0, "o", text]));
+        }
+        cast
+    }
+}
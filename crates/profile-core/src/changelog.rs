@@ -0,0 +1,173 @@
+use crate::cache::write_atomically;
+use crate::config::ChangelogConfig;
+use crate::history::{metric_noun, Snapshot};
+use chrono::{DateTime, NaiveDate};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Commit subjects are cut to this many characters, as longer ones get truncated by most tools.
+pub const MAX_SUBJECT: usize = 100;
+
+/// Ends a commit subject that had to leave changes out.
+const MORE: &str = ", …";
+
+/// Round numbers worth a changelog line when a metric passes them, unless `[changelog]` lists
+/// its own.
+pub const MILESTONES: [u64; 12] = [
+    10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000,
+];
+
+/// Metrics watched for milestones and named in commit subjects.
+pub const TRACKED_METRICS: [&str; 5] = [
+    "followers",
+    "total_stars",
+    "total_commits",
+    "repos_owned",
+    "contributed_to",
+];
+
+/// Describes what changed between two snapshots, one sentence per notable delta.
+pub fn get_notable_changes(
+    config: &ChangelogConfig,
+    previous: &Snapshot,
+    current: &Snapshot,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for metric in &config.metrics {
+        let (Some(before), Some(after)) = (previous.get(metric), current.get(metric)) else {
+            continue;
+        };
+        // Only the highest one crossed, in case a metric jumped past several at once.
+        if let Some(milestone) = config
+            .milestones
+            .iter()
+            .filter(|milestone| before < **milestone && after >= **milestone)
+            .max()
+        {
+            changes.push(format!("crossed {} {}", milestone, metric_noun(metric)));
+        }
+    }
+
+    // Backfilled snapshots have no languages, which isn't a change worth a line.
+    if previous.languages.is_empty() {
+        return changes;
+    }
+    for lang in &current.languages {
+        if !previous.languages.contains(lang) {
+            changes.push(format!("picked up {}", lang));
+        }
+    }
+    for lang in &previous.languages {
+        if !current.languages.contains(lang) {
+            changes.push(format!("{} dropped out of the top languages", lang));
+        }
+    }
+
+    changes
+}
+
+/// Appends dated entries to the changelog, skipping any already written by an earlier run today.
+pub fn append_changelog(
+    config: &ChangelogConfig,
+    previous: &Snapshot,
+    current: &Snapshot,
+) -> io::Result<()> {
+    let path = Path::new(&config.path);
+    let mut contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            "# Profile changelog\n\nNotable moments, recorded automatically.\n\n".to_string()
+        }
+        // Starting over would lose every entry written so far.
+        Err(e) => return Err(e),
+    };
+
+    let mut changed = false;
+    for change in get_notable_changes(config, previous, current) {
+        let entry = format!("- {}: {}", current.date.format("%Y-%m-%d"), change);
+        if !contents.lines().any(|line| line == entry) {
+            contents += &entry;
+            contents += "\n";
+            changed = true;
+        }
+    }
+
+    if changed {
+        write_atomically(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Public events created on or after `since`, the day of the previous run.
+pub fn count_new_activities(activities: &[Value], since: NaiveDate) -> usize {
+    activities
+        .iter()
+        .filter_map(|activity| DateTime::parse_from_rfc3339(activity["created_at"].as_str()?).ok())
+        .filter(|created_at| created_at.date_naive() >= since)
+        .count()
+}
+
+/// A commit subject saying what a run changed, e.g. "🔄 Update README: stars 120 → 124, new
+/// language: Zig, 3 new activities". Falls back to the changed sections, then to a plain
+/// "🔄 Update README".
+pub fn commit_message(
+    previous: Option<&Snapshot>,
+    current: &Snapshot,
+    new_activities: usize,
+    changed_sections: &BTreeSet<String>,
+) -> String {
+    let mut changes = Vec::new();
+    if let Some(previous) = previous {
+        for metric in TRACKED_METRICS {
+            if let (Some(before), Some(after)) = (previous.get(metric), current.get(metric)) {
+                if before != after {
+                    changes.push(format!("{} {} → {}", metric_noun(metric), before, after));
+                }
+            }
+        }
+        // Backfilled snapshots have no languages, as in `get_notable_changes`.
+        if !previous.languages.is_empty() {
+            for lang in &current.languages {
+                if !previous.languages.contains(lang) {
+                    changes.push(format!("new language: {}", lang));
+                }
+            }
+        }
+    }
+    match new_activities {
+        0 => {}
+        1 => changes.push("1 new activity".to_string()),
+        n => changes.push(format!("{} new activities", n)),
+    }
+    if changes.is_empty() {
+        for (i, section) in changed_sections.iter().enumerate() {
+            changes.push(match i {
+                0 => format!("updated {}", section),
+                _ => section.clone(),
+            });
+        }
+    }
+
+    let mut message = "🔄 Update README".to_string();
+    for (i, change) in changes.iter().enumerate() {
+        let separator = if i == 0 { ": " } else { ", " };
+        // Unless this is the last change, leave room to say that more were left out.
+        let reserved = match i + 1 == changes.len() {
+            true => 0,
+            false => MORE.chars().count(),
+        };
+        if message.chars().count() + separator.len() + change.chars().count() + reserved
+            > MAX_SUBJECT
+        {
+            message += MORE;
+            break;
+        }
+        message += separator;
+        message += change;
+    }
+    message
+}
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const GITHUB_API: &str = "GitHub API";
+pub const GITHUB_WEB: &str = "github.com";
+
+/// Consecutive failures after which a provider is left alone for a while.
+const FAILURE_THRESHOLD: u32 = 3;
+const BASE_COOLDOWN: Duration = Duration::from_secs(60);
+const MAX_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+
+enum State {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// The cooldown is over and one request is out to see whether the provider recovered.
+    Probing,
+}
+
+struct Circuit {
+    state: State,
+    /// How often the circuit opened in a row; each time the cooldown doubles.
+    trips: u32,
+}
+
+static CIRCUITS: Mutex<BTreeMap<&'static str, Circuit>> = Mutex::new(BTreeMap::new());
+
+/// Returned instead of sending a request to a provider that is currently disabled.
+#[derive(Debug)]
+pub struct CircuitOpen {
+    pub provider: &'static str,
+}
+
+impl fmt::Display for CircuitOpen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} is temporarily disabled after repeated failures",
+            self.provider
+        )
+    }
+}
+
+impl std::error::Error for CircuitOpen {}
+
+impl Circuit {
+    fn new() -> Self {
+        Circuit {
+            state: State::Closed { failures: 0 },
+            trips: 0,
+        }
+    }
+
+    /// Whether a request may go out at `now`, taking the probe once the cooldown is over.
+    fn allow(&mut self, now: Instant) -> bool {
+        match self.state {
+            State::Closed { .. } => true,
+            State::Open { until } if now >= until => {
+                self.state = State::Probing;
+                true
+            }
+            State::Open { .. } | State::Probing => false,
+        }
+    }
+
+    fn succeed(&mut self) {
+        self.state = State::Closed { failures: 0 };
+        self.trips = 0;
+    }
+
+    fn fail(&mut self, now: Instant) {
+        let trip = match self.state {
+            State::Closed { failures } if failures + 1 < FAILURE_THRESHOLD => {
+                self.state = State::Closed {
+                    failures: failures + 1,
+                };
+                false
+            }
+            _ => true,
+        };
+        if trip {
+            let cooldown = BASE_COOLDOWN
+                .saturating_mul(2u32.saturating_pow(self.trips))
+                .min(MAX_COOLDOWN);
+            self.state = State::Open {
+                until: now + cooldown,
+            };
+            self.trips += 1;
+        }
+    }
+
+    /// Gives back a probe that was never sent, so the next request probes instead.
+    fn release(&mut self, now: Instant) {
+        if let State::Probing = self.state {
+            self.state = State::Open { until: now };
+        }
+    }
+}
+
+fn with_circuit<T>(provider: &'static str, f: impl FnOnce(&mut Circuit) -> T) -> T {
+    let mut circuits = CIRCUITS.lock().unwrap();
+    f(circuits.entry(provider).or_insert_with(Circuit::new))
+}
+
+/// Leave to send one request to a provider, from [`check_circuit`]. The outcome goes back with
+/// [`Permit::succeeded`] or [`Permit::failed`]; dropping it unreported, e.g. because the run was
+/// cancelled, hands a recovery probe on to the next request.
+#[must_use]
+pub struct Permit {
+    provider: &'static str,
+    reported: bool,
+}
+
+impl Permit {
+    pub fn succeeded(mut self) {
+        self.reported = true;
+        with_circuit(self.provider, Circuit::succeed);
+    }
+
+    /// Counts an outage-like failure (unreachable, 5xx, rate limited), opening the circuit at
+    /// the threshold or straight away when a recovery probe fails.
+    pub fn failed(mut self) {
+        self.reported = true;
+        with_circuit(self.provider, |circuit| circuit.fail(Instant::now()));
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if !self.reported {
+            with_circuit(self.provider, |circuit| circuit.release(Instant::now()));
+        }
+    }
+}
+
+/// Whether a request to `provider` may go out now. Once the cooldown has passed, a single
+/// probe is let through while everyone else keeps getting [`CircuitOpen`].
+pub fn check_circuit(provider: &'static str) -> Result<Permit, CircuitOpen> {
+    match with_circuit(provider, |circuit| circuit.allow(Instant::now())) {
+        true => Ok(Permit {
+            provider,
+            reported: false,
+        }),
+        false => Err(CircuitOpen { provider }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_open(circuit: &Circuit) -> Option<Instant> {
+        match circuit.state {
+            State::Open { until } => Some(until),
+            _ => None,
+        }
+    }
+
+    /// A circuit that just tripped at `now`.
+    fn tripped(now: Instant) -> Circuit {
+        let mut circuit = Circuit::new();
+        for _ in 0..FAILURE_THRESHOLD {
+            circuit.fail(now);
+        }
+        circuit
+    }
+
+    #[test]
+    fn opens_after_the_threshold() {
+        let now = Instant::now();
+        let mut circuit = Circuit::new();
+        for _ in 1..FAILURE_THRESHOLD {
+            circuit.fail(now);
+            assert!(circuit.allow(now));
+        }
+        circuit.fail(now);
+        assert_eq!(is_open(&circuit), Some(now + BASE_COOLDOWN));
+        assert!(!circuit.allow(now));
+    }
+
+    #[test]
+    fn lets_one_probe_through_after_the_cooldown() {
+        let now = Instant::now();
+        let mut circuit = tripped(now);
+        assert!(!circuit.allow(now + BASE_COOLDOWN - Duration::from_secs(1)));
+        assert!(circuit.allow(now + BASE_COOLDOWN));
+        assert!(matches!(circuit.state, State::Probing));
+        assert!(!circuit.allow(now + BASE_COOLDOWN));
+    }
+
+    #[test]
+    fn closes_when_the_probe_succeeds() {
+        let now = Instant::now();
+        let mut circuit = tripped(now);
+        assert!(circuit.allow(now + BASE_COOLDOWN));
+        circuit.succeed();
+        assert!(matches!(circuit.state, State::Closed { failures: 0 }));
+        assert_eq!(circuit.trips, 0);
+        assert!(circuit.allow(now + BASE_COOLDOWN));
+    }
+
+    #[test]
+    fn reopens_for_twice_as_long_when_the_probe_fails() {
+        let now = Instant::now();
+        let mut circuit = tripped(now);
+        let probed = now + BASE_COOLDOWN;
+        assert!(circuit.allow(probed));
+        circuit.fail(probed);
+        assert_eq!(is_open(&circuit), Some(probed + BASE_COOLDOWN * 2));
+    }
+
+    #[test]
+    fn unsent_probe_goes_to_the_next_request() {
+        let now = Instant::now();
+        let mut circuit = tripped(now);
+        let probed = now + BASE_COOLDOWN;
+        assert!(circuit.allow(probed));
+        circuit.release(probed);
+        assert!(circuit.allow(probed));
+        assert!(matches!(circuit.state, State::Probing));
+    }
+}
@@ -0,0 +1,1567 @@
+use crate::anomaly::report_anomaly;
+use crate::changelog::{MILESTONES, TRACKED_METRICS};
+use crate::document::{FenceLanguage, Wrap};
+use crate::import::translate_metrics_inputs;
+use crate::remote::{is_remote, join_source, read_source, resolve_url};
+use crate::render::contrast::check_theme_contrast;
+use chrono::{Duration, NaiveDate};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Config {
+    pub username: String,
+    /// Where the README is written.
+    pub output: String,
+    pub header: HeaderConfig,
+    /// IANA timezone name such as `Europe/Vienna`; defaults to the runner's local time.
+    pub timezone: Option<String>,
+    pub mode: OutputMode,
+    pub responsiveness: Option<ResponsivenessConfig>,
+    pub triage: Option<TriageConfig>,
+    pub discussions: Option<DiscussionsConfig>,
+    pub contributors: Option<ContributorsConfig>,
+    pub releases: Option<ReleasesConfig>,
+    pub changelog: Option<ChangelogConfig>,
+    pub celebrations: Option<CelebrationsConfig>,
+    pub clock: Option<ClockConfig>,
+    pub about: Option<AboutConfig>,
+    pub location: Option<LocationConfig>,
+    pub qr: Option<QrConfig>,
+    pub goals: Vec<GoalConfig>,
+    /// Pull request events such as Hacktoberfest, shown only while they run.
+    pub events: Vec<EventConfig>,
+    pub mentorship: Option<MentorshipConfig>,
+    pub talks: Option<TalksConfig>,
+    /// What the `cv` command puts on the CV besides the stats.
+    pub cv: Option<CvConfig>,
+    /// Days off: they don't break the streak and are marked on the heatmap.
+    pub vacations: Vec<Vacation>,
+    pub keys: Option<KeysConfig>,
+    pub proofs: Option<ProofsConfig>,
+    /// Scrape GitHub Achievements from the profile page (there is no API for them).
+    pub achievements: bool,
+    pub scraping: ScrapingConfig,
+    pub stargazers: Option<StargazersConfig>,
+    pub spotlight: Option<SpotlightConfig>,
+    /// Bars of how many repositories were created each year.
+    pub repo_timeline: bool,
+    pub pr_sizes: Option<PrSizesConfig>,
+    /// Review comments given on others' pull requests against those received, last 90 days.
+    pub review_balance: bool,
+    /// Contribution heatmap of this year stacked on last year's.
+    pub year_comparison: bool,
+    /// Conway's Game of Life seeded with the contribution calendar, as an animated SVG.
+    pub life: bool,
+    /// "Last week: 37 commits across 5 repos" summary, refreshed once a week.
+    pub weekly_digest: bool,
+    pub custom: Vec<CustomWidgetConfig>,
+    pub languages: LanguagesConfig,
+    pub stats: StatsConfig,
+    pub growth: Option<GrowthConfig>,
+    pub eta: Option<EtaConfig>,
+    pub theme: ThemeConfig,
+    pub layout: LayoutConfig,
+    pub style: StyleConfig,
+    pub lint: LintConfig,
+    pub links: Option<LinksConfig>,
+    pub spelling: Option<SpellingConfig>,
+    pub extras: ExtrasConfig,
+    pub diff_animation: Option<DiffAnimationConfig>,
+    pub freshness: FreshnessConfig,
+    pub sampling: SamplingConfig,
+    /// Layout changes made when a metric crosses a threshold, checked in order.
+    pub rules: Vec<LayoutRule>,
+    /// Hand-set values for displayed metrics, by metric name as in `rules`.
+    pub overrides: BTreeMap<String, MetricOverride>,
+    pub plausibility: PlausibilityConfig,
+    pub canary: CanaryConfig,
+    pub pull_request: Option<PullRequestConfig>,
+    pub locales: Option<LocalesConfig>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct HeaderConfig {
+    pub layout: HeaderLayout,
+    /// Badges in display order.
+    pub badges: Vec<HeaderBadge>,
+    /// A file, URL or `github:owner/repo/path@ref` to take the art from instead of the
+    /// built-in figure.
+    pub art: Option<String>,
+    /// Checksum the fetched `art` must have.
+    pub art_sha256: Option<String>,
+    /// Cells trimmed off the edges of the art, before it is mirrored or scaled.
+    pub art_crop: ArtCrop,
+    /// Flip braille art left to right.
+    pub art_mirror: bool,
+    /// Redraw braille art this many columns wide, keeping its aspect ratio.
+    pub art_width: Option<usize>,
+    /// `#rrggbb` colour of the art in the terminal and SVG renderings.
+    pub art_color: Option<String>,
+}
+
+impl Default for HeaderConfig {
+    fn default() -> Self {
+        HeaderConfig {
+            layout: HeaderLayout::default(),
+            badges: vec![HeaderBadge::Followers, HeaderBadge::Stars],
+            art: None,
+            art_sha256: None,
+            art_crop: ArtCrop::default(),
+            art_mirror: false,
+            art_width: None,
+            art_color: None,
+        }
+    }
+}
+
+/// Cells trimmed off each edge of the header art.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ArtCrop {
+    pub left: usize,
+    pub right: usize,
+    pub top: usize,
+    pub bottom: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderBadge {
+    Followers,
+    Stars,
+    /// Days in a row with contributions, from the contribution calendar.
+    Streak,
+}
+
+/// Where the header art goes relative to the badges.
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderLayout {
+    /// Art with the badges stacked to its right.
+    #[default]
+    ArtLeft,
+    /// Badges stacked to the left of the art.
+    ArtRight,
+    /// Art above a centered row of badges.
+    ArtTop,
+    /// A row of badges without the art.
+    BadgesOnly,
+}
+
+/// Whether widgets that have a richer form render as plain ASCII or as inline HTML.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    #[default]
+    Ascii,
+    Html,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            username: "m4ster-slave".to_string(),
+            output: "README.md".to_string(),
+            header: HeaderConfig::default(),
+            timezone: None,
+            mode: OutputMode::default(),
+            responsiveness: None,
+            triage: None,
+            discussions: None,
+            contributors: None,
+            releases: None,
+            changelog: None,
+            celebrations: None,
+            clock: None,
+            about: None,
+            location: None,
+            qr: None,
+            goals: Vec::new(),
+            events: Vec::new(),
+            mentorship: None,
+            talks: None,
+            cv: None,
+            vacations: Vec::new(),
+            keys: None,
+            proofs: None,
+            achievements: false,
+            scraping: ScrapingConfig::default(),
+            stargazers: None,
+            spotlight: None,
+            repo_timeline: false,
+            pr_sizes: None,
+            review_balance: false,
+            year_comparison: false,
+            life: false,
+            weekly_digest: false,
+            custom: Vec::new(),
+            languages: LanguagesConfig::default(),
+            stats: StatsConfig::default(),
+            growth: None,
+            eta: None,
+            theme: ThemeConfig::default(),
+            layout: LayoutConfig::default(),
+            style: StyleConfig::default(),
+            lint: LintConfig::default(),
+            links: None,
+            spelling: None,
+            extras: ExtrasConfig::default(),
+            diff_animation: None,
+            freshness: FreshnessConfig::default(),
+            sampling: SamplingConfig::default(),
+            rules: Vec::new(),
+            overrides: BTreeMap::new(),
+            plausibility: PlausibilityConfig::default(),
+            canary: CanaryConfig::default(),
+            pull_request: None,
+            locales: None,
+        }
+    }
+}
+
+impl Config {
+    /// The configured timezone. [`load_config`] rejects names that don't parse, so this only
+    /// comes out `None` when none is set.
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone
+            .as_deref()
+            .and_then(|name| parse_timezone(name).ok())
+    }
+}
+
+fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse().map_err(|_| {
+        format!(
+            "unknown timezone \"{}\"; use an IANA name such as \"Europe/Vienna\"",
+            name
+        )
+    })
+}
+
+/// Flagship repo whose issue handling is summarised in the responsiveness box.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResponsivenessConfig {
+    pub repo: String,
+    /// How many of the most recent issues get their timeline inspected.
+    #[serde(default = "default_issue_sample")]
+    pub sample: usize,
+}
+
+fn default_issue_sample() -> usize {
+    30
+}
+
+/// Share of new issues on maintained repos that were triaged (labeled, assigned, milestoned or
+/// closed) promptly.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct TriageConfig {
+    /// `owner/name` of each repository.
+    pub repos: Vec<String>,
+    /// Triage within this many hours of an issue being opened counts as prompt.
+    pub hours: i64,
+    /// Issues opened in this many days are looked at.
+    pub days: i64,
+}
+
+impl Default for TriageConfig {
+    fn default() -> Self {
+        TriageConfig {
+            repos: Vec::new(),
+            hours: 48,
+            days: 30,
+        }
+    }
+}
+
+/// Answers the user gave in GitHub Discussions, and how many were accepted.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DiscussionsConfig {
+    /// `owner/name` of the repositories to count; all of them when empty.
+    pub repos: Vec<String>,
+    /// How many recently answered threads are listed.
+    pub recent: usize,
+}
+
+impl Default for DiscussionsConfig {
+    fn default() -> Self {
+        DiscussionsConfig {
+            repos: Vec::new(),
+            recent: 5,
+        }
+    }
+}
+
+/// Thank-you row for the people contributing to one of the user's repos.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ContributorsConfig {
+    /// Defaults to the user's most starred repository.
+    pub repo: Option<String>,
+    #[serde(default = "default_contributor_limit")]
+    pub limit: usize,
+}
+
+fn default_contributor_limit() -> usize {
+    8
+}
+
+/// Excerpt of the latest release notes of the showcased repo.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReleasesConfig {
+    /// Defaults to the user's most starred repository.
+    pub repo: Option<String>,
+    #[serde(default = "default_release_lines")]
+    pub lines: usize,
+    #[serde(default = "default_release_width")]
+    pub width: usize,
+}
+
+fn default_release_lines() -> usize {
+    5
+}
+
+fn default_release_width() -> usize {
+    72
+}
+
+/// Narrative log of milestones derived from the stats history.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ChangelogConfig {
+    #[serde(default = "default_changelog_path")]
+    pub path: String,
+    /// Round numbers worth an entry when a metric passes them.
+    #[serde(default = "default_milestones")]
+    pub milestones: Vec<u64>,
+    /// History metrics watched for milestones, e.g. "followers" or "total_stars".
+    #[serde(default = "default_changelog_metrics")]
+    pub metrics: Vec<String>,
+}
+
+fn default_changelog_path() -> String {
+    "PROFILE_CHANGELOG.md".to_string()
+}
+
+fn default_milestones() -> Vec<u64> {
+    MILESTONES.to_vec()
+}
+
+fn default_changelog_metrics() -> Vec<String> {
+    TRACKED_METRICS
+        .iter()
+        .map(|metric| metric.to_string())
+        .collect()
+}
+
+/// Temporary banners for freshly crossed milestones.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CelebrationsConfig {
+    /// How long a banner stays up after the milestone was crossed.
+    #[serde(default = "default_celebration_days")]
+    pub days: i64,
+    /// Optional Slack/Discord-compatible webhook notified on the day of crossing.
+    pub webhook: Option<String>,
+    #[serde(default)]
+    pub milestones: Vec<MilestoneConfig>,
+}
+
+fn default_celebration_days() -> i64 {
+    7
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MilestoneConfig {
+    /// A metric name from the stats history, e.g. `followers` or `total_commits`.
+    pub metric: String,
+    pub threshold: u64,
+    pub message: Option<String>,
+}
+
+/// Local time shown in the header, based on the top-level `timezone`.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct ClockConfig {
+    /// Defaults to the city part of the timezone name.
+    pub city: Option<String>,
+}
+
+/// The hand-written "about me" facts, rendered as a box.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AboutConfig {
+    pub pronouns: Option<String>,
+    pub location: Option<String>,
+    pub role: Option<String>,
+    /// ISO 639-1 codes (`de`, or `de-AT` for a regional flag); unknown entries are shown as-is.
+    pub languages: Vec<String>,
+}
+
+/// World map with a pin at the given coordinate.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LocationConfig {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Caption under the map; defaults to `about.location`.
+    pub label: Option<String>,
+}
+
+/// QR code in the bottom corner pointing at a website or vCard.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct QrConfig {
+    pub url: String,
+    /// Draw light modules instead of dark ones, for readers on a dark theme.
+    #[serde(default)]
+    pub invert: bool,
+}
+
+/// A target for one of the history metrics, shown as a progress bar.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GoalConfig {
+    pub label: String,
+    /// A metric name from the stats history, e.g. `total_stars`.
+    pub metric: String,
+    pub target: u64,
+    /// Count only growth within the current week/month/year instead of the total.
+    pub period: Option<GoalPeriod>,
+    /// Defaults to the end of `period`, if one is set.
+    pub deadline: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalPeriod {
+    Week,
+    Month,
+    Year,
+}
+
+/// A yearly event counting pull requests opened between `from` and `to`. Every field defaults
+/// to Hacktoberfest's.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct EventConfig {
+    pub name: String,
+    /// First day, `MM-DD`.
+    pub from: String,
+    /// Last day, `MM-DD`, included.
+    pub to: String,
+    /// Pull requests needed to complete the event.
+    pub goal: u64,
+    /// Extra search qualifiers a pull request has to match, e.g. `is:public -label:spam`.
+    pub qualifiers: String,
+}
+
+impl Default for EventConfig {
+    fn default() -> Self {
+        EventConfig {
+            name: "Hacktoberfest".to_string(),
+            from: "10-01".to_string(),
+            to: "10-31".to_string(),
+            goal: 4,
+            qualifiers: "is:public -label:invalid -label:spam".to_string(),
+        }
+    }
+}
+
+/// Mentorship programs taken part in, such as Google Summer of Code, as a table.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MentorshipConfig {
+    /// Check that linked GitHub repositories and pull requests still exist, and unlink them
+    /// if not.
+    pub validate: bool,
+    pub entries: Vec<MentorshipEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MentorshipEntry {
+    pub program: String,
+    pub year: i32,
+    /// E.g. `Contributor` or `Mentor`.
+    #[serde(default)]
+    pub role: String,
+    pub project: String,
+    /// The repository, pull request or project page.
+    pub link: Option<String>,
+}
+
+/// Conference talks and publications, grouped by year.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct TalksConfig {
+    /// A YAML list of talks, or a BibTeX file when it ends in `.bib`; local or remote.
+    pub source: String,
+    /// Show only the newest this many.
+    pub limit: Option<usize>,
+}
+
+impl Default for TalksConfig {
+    fn default() -> Self {
+        TalksConfig {
+            source: "talks.yaml".to_string(),
+            limit: None,
+        }
+    }
+}
+
+/// The one-page CV written by the `cv` command.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CvConfig {
+    /// Written as an HTML page when it ends in `.html`, as markdown otherwise.
+    pub output: String,
+    /// Defaults to the username.
+    pub name: Option<String>,
+    /// A line under the name, e.g. `Backend engineer, Rust and distributed systems`.
+    pub headline: Option<String>,
+    pub location: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
+    /// Newest first, as listed.
+    pub experience: Vec<CvEntry>,
+    pub education: Vec<CvEntry>,
+}
+
+impl Default for CvConfig {
+    fn default() -> Self {
+        CvConfig {
+            output: "cv.md".to_string(),
+            name: None,
+            headline: None,
+            location: None,
+            email: None,
+            website: None,
+            experience: Vec::new(),
+            education: Vec::new(),
+        }
+    }
+}
+
+/// A job, degree or similar stretch on the CV.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CvEntry {
+    /// The role or degree.
+    pub title: String,
+    pub organization: Option<String>,
+    /// Free-form, e.g. `2021` or `Mar 2021`.
+    pub from: String,
+    /// Shown as "present" when unset.
+    pub to: Option<String>,
+    pub summary: Option<String>,
+    pub link: Option<String>,
+}
+
+/// A stretch of days off, `from` and `to` included.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct Vacation {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+/// Whether `date` falls in one of `vacations`.
+pub fn on_vacation(vacations: &[Vacation], date: NaiveDate) -> bool {
+    vacations
+        .iter()
+        .any(|vacation| (vacation.from..=vacation.to).contains(&date))
+}
+
+/// Public GPG and SSH keys, so signed commits can be checked against the profile.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct KeysConfig {
+    /// Only show how many keys there are plus the links, not every key ID and fingerprint.
+    pub summary: bool,
+}
+
+/// Identity claims linking the GitHub account to a key, domains and other accounts, in the
+/// style of a Keybase proof.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ProofsConfig {
+    pub keybase: Option<String>,
+    /// OpenPGP fingerprint, with or without spaces.
+    pub fingerprint: Option<String>,
+    /// Domains proven with a `/.well-known/keybase.txt` file.
+    pub domains: Vec<String>,
+    pub accounts: Vec<ProofAccount>,
+    /// Check that every proof URL resolves, reporting the ones that don't.
+    pub verify: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProofAccount {
+    /// E.g. `Mastodon` or `Hacker News`.
+    pub service: String,
+    pub username: String,
+    /// Where the proof is posted.
+    pub proof: Option<String>,
+}
+
+/// Etiquette for the widgets that read HTML pages instead of an API.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ScrapingConfig {
+    /// Switches off every scraping-based widget at once.
+    pub enabled: bool,
+    /// Minimum pause between two uncached page requests.
+    pub delay_ms: u64,
+}
+
+impl Default for ScrapingConfig {
+    fn default() -> Self {
+        ScrapingConfig {
+            enabled: true,
+            delay_ms: 2000,
+        }
+    }
+}
+
+/// Fun facts about where and when people star the user's repos.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StargazersConfig {
+    /// Most starred repos to sample.
+    pub repos: usize,
+    /// Most recent stargazers to sample per repo.
+    pub per_repo: usize,
+}
+
+impl Default for StargazersConfig {
+    fn default() -> Self {
+        StargazersConfig {
+            repos: 10,
+            per_repo: 30,
+        }
+    }
+}
+
+/// Histogram of how many lines the user's recent pull requests change.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PrSizesConfig {
+    /// How many of the most recent pull requests are counted, at most 100.
+    pub sample: usize,
+}
+
+impl Default for PrSizesConfig {
+    fn default() -> Self {
+        PrSizesConfig { sample: 50 }
+    }
+}
+
+/// A different one of the user's repositories featured each week, in rotation.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SpotlightConfig {
+    pub order: SpotlightOrder,
+    /// The rotation for `order = "listed"`.
+    pub repos: Vec<String>,
+    /// Repository names never featured.
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SpotlightOrder {
+    /// Most starred first.
+    #[default]
+    Stars,
+    /// Most recently pushed to first.
+    Recent,
+    /// Alphabetically.
+    Name,
+    /// Only the repositories in `repos`, in that order.
+    Listed,
+}
+
+/// A user-defined section backed by an arbitrary read-only GraphQL query.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CustomWidgetConfig {
+    pub title: String,
+    /// `{username}` is replaced with the configured username.
+    pub query: String,
+    /// Template placeholder name -> dotted path into the response `data`.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Text with `{field}` placeholders.
+    pub template: String,
+}
+
+/// How the stats section is laid out.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StatsConfig {
+    pub style: StatsStyle,
+    /// Widest a row of cards gets before the next card wraps onto a new row.
+    pub max_width: usize,
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        StatsConfig {
+            style: StatsStyle::Table,
+            max_width: 60,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsStyle {
+    /// One wide table with two metrics per row.
+    #[default]
+    Table,
+    /// A small card per metric with its change since the last run.
+    Cards,
+}
+
+/// Rules applied to repository languages before percentages are calculated.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LanguagesConfig {
+    /// Language name -> displayed group, e.g. `"Jupyter Notebook" = "Python"`.
+    pub aliases: BTreeMap<String, String>,
+    /// Languages with a smaller share are folded into an "Other" row.
+    pub min_percentage: f64,
+    /// Most rows shown, including the "Other" row.
+    pub max_rows: usize,
+}
+
+impl Default for LanguagesConfig {
+    fn default() -> Self {
+        LanguagesConfig {
+            aliases: BTreeMap::new(),
+            min_percentage: 1.0,
+            max_rows: 10,
+        }
+    }
+}
+
+impl LanguagesConfig {
+    /// Rejects alias sets whose result would depend on rule order: chains (`A -> B`, `B -> C`)
+    /// and the same language listed twice with different casing.
+    pub fn validate(&self) -> Result<(), String> {
+        for (language, group) in &self.aliases {
+            if let Some(next) = self.aliases.get(group).filter(|next| *next != group) {
+                return Err(format!(
+                    "language alias chain: \"{}\" -> \"{}\" -> \"{}\"; map \"{}\" to \"{}\" directly",
+                    language, group, next, language, next
+                ));
+            }
+        }
+
+        let mut seen: BTreeMap<String, &str> = BTreeMap::new();
+        for language in self.aliases.keys() {
+            if let Some(other) = seen.insert(language.to_lowercase(), language) {
+                return Err(format!(
+                    "language \"{}\" is aliased twice (also as \"{}\")",
+                    language, other
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The name a language is counted under.
+    pub fn resolve<'a>(&'a self, language: &'a str) -> &'a str {
+        self.aliases
+            .get(language)
+            .map(String::as_str)
+            .unwrap_or(language)
+    }
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub heatmap: HeatmapTheme,
+    /// Icons and number styles of the header badges.
+    pub badges: BTreeMap<HeaderBadge, BadgeTheme>,
+    /// What to do about text colours below WCAG AA contrast against their background.
+    pub contrast: ContrastPolicy,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContrastPolicy {
+    /// Report them as anomalies.
+    #[default]
+    Warn,
+    /// Lighten or darken them until they pass.
+    Adjust,
+    Off,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct BadgeTheme {
+    /// Shown before the value, e.g. `★` for "★ 128".
+    pub icon: Option<String>,
+    pub value: ValueStyle,
+}
+
+impl BadgeTheme {
+    pub fn format_value(&self, value: u64) -> String {
+        let value = self.value.format(value);
+        match &self.icon {
+            Some(icon) => format!("{} {}", icon, value),
+            None => value,
+        }
+    }
+}
+
+/// How a badge writes its number.
+#[derive(Debug, Default, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueStyle {
+    /// `12345`
+    #[default]
+    Plain,
+    /// `12,345`
+    Grouped,
+    /// `12.3k`
+    Compact,
+}
+
+impl ValueStyle {
+    pub fn format(&self, value: u64) -> String {
+        match self {
+            ValueStyle::Plain => value.to_string(),
+            ValueStyle::Grouped => {
+                let digits = value.to_string();
+                let mut grouped = String::new();
+                for (i, digit) in digits.chars().enumerate() {
+                    if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                        grouped.push(',');
+                    }
+                    grouped.push(digit);
+                }
+                grouped
+            }
+            ValueStyle::Compact => match value {
+                0..1_000 => value.to_string(),
+                // Anything that would round up to "1000.0k" is shown in millions.
+                1_000..999_950 => format!("{:.1}k", value as f64 / 1e3),
+                _ => format!("{:.1}M", value as f64 / 1e6),
+            },
+        }
+    }
+}
+
+/// Colour ramps for SVG heatmaps, from no activity to the busiest cell.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HeatmapPalette {
+    /// GitHub's contribution green.
+    #[default]
+    Green,
+    /// Blue ramp that stays distinguishable with red-green colour blindness.
+    Colorblind,
+    /// Perceptually uniform purple-to-yellow ramp.
+    Viridis,
+}
+
+impl HeatmapPalette {
+    pub const ALL: [HeatmapPalette; 3] = [
+        HeatmapPalette::Green,
+        HeatmapPalette::Colorblind,
+        HeatmapPalette::Viridis,
+    ];
+
+    /// The name used for it in profile.toml.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeatmapPalette::Green => "green",
+            HeatmapPalette::Colorblind => "colorblind",
+            HeatmapPalette::Viridis => "viridis",
+        }
+    }
+
+    pub fn colors(&self) -> [&'static str; 5] {
+        match self {
+            HeatmapPalette::Green => ["#161b22", "#0e4429", "#006d32", "#26a641", "#39d353"],
+            HeatmapPalette::Colorblind => ["#161b22", "#0a3069", "#0969da", "#54aeff", "#b6e3ff"],
+            HeatmapPalette::Viridis => ["#161b22", "#440154", "#31688e", "#35b779", "#fde725"],
+        }
+    }
+}
+
+/// How heatmap cells are drawn: one glyph and colour per intensity level.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct HeatmapTheme {
+    /// Glyph for cells without any activity.
+    pub empty: char,
+    /// One glyph per intensity level, lowest first.
+    pub glyphs: Vec<char>,
+    /// Upper bound of each level as a fraction of the busiest cell, ascending and ending at 1.0.
+    pub thresholds: Vec<f64>,
+    pub palette: HeatmapPalette,
+    /// Overrides the palette: the empty colour followed by one colour per level.
+    pub colors: Option<Vec<String>>,
+}
+
+impl Default for HeatmapTheme {
+    fn default() -> Self {
+        HeatmapTheme {
+            empty: '·',
+            glyphs: vec!['░', '▒', '▓', '█'],
+            thresholds: vec![0.25, 0.5, 0.75, 1.0],
+            palette: HeatmapPalette::default(),
+            colors: None,
+        }
+    }
+}
+
+impl HeatmapTheme {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.glyphs.is_empty() || self.glyphs.len() != self.thresholds.len() {
+            return Err(format!(
+                "heatmap theme needs one threshold per glyph (got {} glyphs, {} thresholds)",
+                self.glyphs.len(),
+                self.thresholds.len()
+            ));
+        }
+        if self.thresholds.windows(2).any(|pair| pair[0] >= pair[1])
+            || self.thresholds[0] <= 0.0
+            || self.thresholds[self.thresholds.len() - 1] != 1.0
+        {
+            return Err("heatmap thresholds must be ascending, above 0 and end at 1.0".to_string());
+        }
+        if let Some(colors) = self
+            .colors
+            .as_ref()
+            .filter(|c| c.len() != self.glyphs.len() + 1)
+        {
+            return Err(format!(
+                "heatmap theme needs {} colours (empty plus one per level), got {}",
+                self.glyphs.len() + 1,
+                colors.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Intensity level of `value` relative to `max`: 0 for nothing, then 1 up to the number of
+    /// glyphs.
+    pub fn level(&self, value: u64, max: u64) -> usize {
+        if value == 0 || max == 0 {
+            return 0;
+        }
+        let ratio = value as f64 / max as f64;
+        self.thresholds
+            .iter()
+            .position(|threshold| ratio <= *threshold)
+            .unwrap_or(self.thresholds.len() - 1)
+            + 1
+    }
+
+    /// Fill colour for a level; palettes are stretched over however many levels are configured.
+    pub fn color(&self, level: usize) -> &str {
+        match &self.colors {
+            Some(colors) => &colors[level],
+            None => {
+                let palette = self.palette.colors();
+                let steps = palette.len() - 1;
+                palette[(level * steps).div_ceil(self.glyphs.len())]
+            }
+        }
+    }
+}
+
+/// Trend of history metrics: sparklines in ASCII mode, SVG line charts in HTML mode.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GrowthConfig {
+    pub metrics: Vec<String>,
+    pub days: i64,
+}
+
+impl Default for GrowthConfig {
+    fn default() -> Self {
+        GrowthConfig {
+            metrics: vec!["followers".to_string(), "total_stars".to_string()],
+            days: 90,
+        }
+    }
+}
+
+/// "At this rate: 500 stars by ~March 2027" from the trend of history metrics.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct EtaConfig {
+    pub metrics: Vec<String>,
+    /// The pace is taken from this many days of history.
+    pub days: i64,
+    /// Target per metric; the next round number (10, 25, 50, 100, ...) when unset or reached.
+    pub targets: BTreeMap<String, u64>,
+}
+
+impl Default for EtaConfig {
+    fn default() -> Self {
+        EtaConfig {
+            metrics: vec!["total_stars".to_string(), "followers".to_string()],
+            days: 180,
+            targets: BTreeMap::new(),
+        }
+    }
+}
+
+/// Order and visibility of the titled sections, by heading. Banners without a heading keep
+/// their place.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// Headings in display order; sections not listed follow in their default order.
+    pub order: Vec<String>,
+    /// Headings of sections to leave out.
+    pub hidden: Vec<String>,
+    /// A line of anchor links to every section under the header, for long profiles.
+    pub table_of_contents: bool,
+    /// Leave out widgets whose data couldn't be fetched, rather than keeping their slot with a
+    /// "data unavailable" placeholder.
+    pub omit_failed: bool,
+}
+
+/// How the markdown sets sections apart: code-fence languages and the alerts or blockquotes
+/// around the header, footer and individual sections.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StyleConfig {
+    /// Language of code fences in sections without their own.
+    pub fence: FenceLanguage,
+    pub header: Wrap,
+    pub footer: Wrap,
+    /// A small "as of" date under sections, from when their data was fetched.
+    pub as_of: bool,
+    /// Per-section overrides, by heading.
+    pub sections: BTreeMap<String, SectionStyle>,
+}
+
+impl Default for StyleConfig {
+    fn default() -> Self {
+        StyleConfig {
+            fence: FenceLanguage::None,
+            header: Wrap::Warning,
+            footer: Wrap::Note,
+            as_of: false,
+            sections: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SectionStyle {
+    pub fence: Option<FenceLanguage>,
+    pub wrap: Wrap,
+    pub as_of: Option<bool>,
+}
+
+/// A config file as written: `Config` plus the keys `load_config` takes out before parsing it,
+/// or translates in the case of `[metrics]`.
+/// Only its schema is used.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(title = "Config")]
+struct ConfigFile {
+    /// Path or URL of a config this one is merged over.
+    extends: Option<String>,
+    /// SHA-256 the `extends` config must hash to, in hex; required for a plain `http://` URL.
+    extends_sha256: Option<String>,
+    /// Named overlays of this config, applied with `--profile <name>`.
+    profile: Option<BTreeMap<String, Value>>,
+    /// lowlighter/metrics inputs, as in the `with:` block of its workflow; settings made here
+    /// the crate's own way win over them.
+    metrics: Option<BTreeMap<String, MetricsInput>>,
+    #[schemars(flatten)]
+    config: Config,
+}
+
+/// One lowlighter/metrics input; its workflows pass every value as text, but TOML and YAML read
+/// some as numbers or booleans.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(untagged)]
+enum MetricsInput {
+    Text(String),
+    Number(f64),
+    Flag(bool),
+}
+
+/// A JSON Schema of the config, for completion and validation in editors.
+pub fn config_schema() -> String {
+    serde_json::to_string_pretty(&schemars::schema_for!(ConfigFile))
+        .expect("Failed to serialize config schema")
+}
+
+/// Limits the rendered README is checked against before it is written.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LintConfig {
+    /// GitHub stops rendering READMEs somewhat above 500 KB.
+    pub max_bytes: usize,
+    /// Widest line allowed inside a code fence, in columns.
+    pub max_line_width: usize,
+    pub allow_tabs: bool,
+    /// Abort the run on any problem instead of reporting it as an anomaly.
+    pub fail: bool,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            max_bytes: 500_000,
+            max_line_width: 120,
+            allow_tabs: false,
+            fail: false,
+        }
+    }
+}
+
+/// Checking the README's links before it is written.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LinksConfig {
+    /// Links checked at once.
+    pub concurrency: usize,
+    /// URL prefixes that are never checked, e.g. sites that block automated requests.
+    pub ignore: Vec<String>,
+}
+
+impl Default for LinksConfig {
+    fn default() -> Self {
+        LinksConfig {
+            concurrency: 8,
+            ignore: Vec::new(),
+        }
+    }
+}
+
+/// Spell-checking the prose nobody proofreads: the header and footer quotes, custom widgets
+/// and release notes.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SpellingConfig {
+    /// Word list with one word per line, such as the `wamerican` or `wbritish` package's.
+    pub dictionary: String,
+    /// A file or remote source with more accepted words, one per line.
+    pub user_dictionary: Option<String>,
+    /// More accepted words: names, jargon, deliberate misspellings.
+    pub words: Vec<String>,
+}
+
+impl Default for SpellingConfig {
+    fn default() -> Self {
+        SpellingConfig {
+            dictionary: "/usr/share/dict/words".to_string(),
+            user_dictionary: None,
+            words: Vec::new(),
+        }
+    }
+}
+
+/// Small files written to `assets/` next to the README for use elsewhere, coloured (or tuned)
+/// with the heatmap theme.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ExtrasConfig {
+    /// `avatar-frame.svg`: a ring for a round avatar, brighter the longer the current streak.
+    pub avatar_frame: bool,
+    /// `favicon.svg`: the follower count on a square icon, e.g. for a personal site.
+    pub favicon: bool,
+    /// `contributions.wav`: the last year as one note per week, higher the busier. Needs a
+    /// build with the `audio` feature.
+    pub melody: bool,
+}
+
+/// An odometer animation of the stats that changed since the last run, written to
+/// `assets/stats-diff.svg` whenever any did.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DiffAnimationConfig {
+    /// Slack/Discord-compatible webhook told about the changes, with a link to the animation.
+    pub webhook: Option<String>,
+}
+
+/// How old the data behind a section may be before it is marked stale, which happens when a
+/// source is down and the cache has to stand in for it.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct FreshnessConfig {
+    /// Limit for every section that fetches data, in hours.
+    pub max_age_hours: Option<i64>,
+    /// Per-section limits in hours, by heading, overriding `max_age_hours`.
+    pub sections: BTreeMap<String, i64>,
+}
+
+impl FreshnessConfig {
+    /// The limit for the section titled `title`, if there is one.
+    pub fn max_age(&self, title: &str) -> Option<Duration> {
+        self.sections
+            .get(title)
+            .copied()
+            .or(self.max_age_hours)
+            .map(Duration::hours)
+    }
+}
+
+/// Which repositories analyses that cost a request per repository (such as the languages) look
+/// at, for users with more repositories than is worth a request each.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SamplingConfig {
+    pub strategy: SampleStrategy,
+    /// Most repositories analysed, i.e. requests spent per analysis.
+    pub repos: usize,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        SamplingConfig {
+            strategy: SampleStrategy::default(),
+            repos: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SampleStrategy {
+    /// The most starred repositories.
+    #[default]
+    Stars,
+    /// The most recently pushed to.
+    Recent,
+    /// The most starred repository of each primary language in turn.
+    Stratified,
+}
+
+/// A layout change made while a metric is within a range, e.g. hiding the stars badge while
+/// there are fewer than 10 stars.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LayoutRule {
+    /// `total_stars`, `total_commits`, `total_prs`, `total_issues`, `repos_owned`,
+    /// `contributed_to`, `followers` or `languages` (rows in the language list).
+    pub metric: String,
+    /// The rule applies while the metric is less than this.
+    pub below: Option<f64>,
+    /// The rule applies while the metric is at least this.
+    pub at_least: Option<f64>,
+    pub hide_badges: Vec<HeaderBadge>,
+    /// Headings of sections to leave out, as in `layout.hidden`.
+    pub hide_sections: Vec<String>,
+    /// Show the languages as one line of names and percentages instead of bars.
+    pub compact_languages: bool,
+}
+
+/// A hand-set value for a metric the public API undercounts. Either part can be given alone;
+/// the README marks the metric either way.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MetricOverride {
+    /// Shown instead of the fetched number.
+    pub value: Option<u64>,
+    /// Shown after the number, e.g. "+ private work".
+    pub note: Option<String>,
+}
+
+/// Warnings for metrics that changed implausibly since the previous snapshot, which usually
+/// means a truncated response or a token problem rather than reality.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PlausibilityConfig {
+    /// Largest believable drop between runs, as a fraction.
+    pub max_drop: f64,
+    /// Largest believable growth between runs, as a factor.
+    pub max_growth: f64,
+    /// Metrics smaller than this are not checked.
+    pub min_value: u64,
+    /// Show and record the previous value instead of an implausible one.
+    pub keep_previous: bool,
+    /// Slack/Discord-compatible webhook told about implausible changes.
+    pub webhook: Option<String>,
+}
+
+impl Default for PlausibilityConfig {
+    fn default() -> Self {
+        PlausibilityConfig {
+            max_drop: 0.3,
+            max_growth: 10.0,
+            min_value: 20,
+            keep_previous: false,
+            webhook: None,
+        }
+    }
+}
+
+/// Where `--canary` puts the regenerated README for review instead of replacing the real one.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CanaryConfig {
+    pub output: String,
+    /// Also push the README to this branch of the profile repository and open a pull request
+    /// for it.
+    pub branch: Option<String>,
+}
+
+impl Default for CanaryConfig {
+    fn default() -> Self {
+        CanaryConfig {
+            output: "README.preview.md".to_string(),
+            branch: None,
+        }
+    }
+}
+
+/// Publish each run as a pull request on the profile repository rather than leaving the files
+/// for the workflow to push, so the default branch can require reviews.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PullRequestConfig {
+    /// Reset to the default branch and recommitted on every run.
+    pub branch: String,
+    /// Let GitHub merge the pull request once its required reviews and checks pass.
+    pub auto_merge: bool,
+    pub merge_method: MergeMethod,
+}
+
+impl Default for PullRequestConfig {
+    fn default() -> Self {
+        PullRequestConfig {
+            branch: "profile-update".to_string(),
+            auto_merge: false,
+            merge_method: MergeMethod::Squash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+/// Translated copies of the README, written next to it with a row of links between them.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct LocalesConfig {
+    /// How the main README's language is named in the links.
+    pub name: String,
+    pub translations: Vec<LocaleConfig>,
+}
+
+impl Default for LocalesConfig {
+    fn default() -> Self {
+        LocalesConfig {
+            name: "English".to_string(),
+            translations: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct LocaleConfig {
+    /// Language code, as in `README.de.md`.
+    pub code: String,
+    /// The language's own name, e.g. "Deutsch".
+    pub name: String,
+    /// Defaults to the README's path with `code` before the extension.
+    pub output: Option<String>,
+    /// Section title -> its translation. Sections left out keep their title.
+    #[serde(default)]
+    pub titles: BTreeMap<String, String>,
+}
+
+/// Config files looked for in the working directory, in order of preference.
+pub const CONFIG_FILES: [&str; 4] = [
+    "profile.toml",
+    "profile.yaml",
+    "profile.yml",
+    "profile.json",
+];
+
+/// Environment variable naming the config file, for when the tool is installed system-wide.
+pub const CONFIG_ENV: &str = "PROFILE_GEN_CONFIG";
+
+/// Directory under `$XDG_CONFIG_HOME` (or `~/.config`) searched for `config.toml`, `.yaml`, `.yml`
+/// or `.json` when the working directory has none of [`CONFIG_FILES`].
+pub const CONFIG_DIR: &str = "profile-gen";
+
+/// `$XDG_CONFIG_HOME/profile-gen`, falling back to `~/.config/profile-gen` as the XDG base
+/// directory spec says when the variable is unset or not absolute.
+fn xdg_config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join(CONFIG_DIR))
+}
+
+/// A config file to load and how it was chosen.
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    pub path: String,
+    /// Named by `--config` or [`CONFIG_ENV`] rather than found by looking around, so it must
+    /// exist.
+    pub explicit: bool,
+    /// SHA-256 checksum the file must have, e.g. to pin a remote config.
+    pub sha256: Option<String>,
+}
+
+/// The config to load: `explicit` (from `--config`) if given, else [`CONFIG_ENV`], else the first
+/// of [`CONFIG_FILES`] in the working directory, else `config.*` in the XDG config directory.
+/// With none of them existing it is `profile.toml`, which loads as the defaults.
+pub fn find_config(explicit: Option<&str>) -> ConfigSource {
+    let named = explicit.map(str::to_string).or_else(|| {
+        std::env::var(CONFIG_ENV)
+            .ok()
+            .filter(|path| !path.is_empty())
+    });
+    if let Some(path) = named {
+        return ConfigSource {
+            path,
+            explicit: true,
+            sha256: None,
+        };
+    }
+    let path = CONFIG_FILES
+        .into_iter()
+        .find(|path| Path::new(path).exists())
+        .map(str::to_string)
+        .or_else(|| {
+            xdg_config_dir()
+                .into_iter()
+                .flat_map(|dir| {
+                    CONFIG_FILES.map(|file| dir.join(file.replacen("profile", "config", 1)))
+                })
+                .find(|path| path.exists())
+                .map(|path| path.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| CONFIG_FILES[0].to_string());
+    ConfigSource {
+        path,
+        explicit: false,
+        sha256: None,
+    }
+}
+
+/// Base configs an `extends` chain may go through before it is taken to be a cycle.
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Parses `contents` as YAML or JSON when `path` says so by its extension, and as TOML
+/// otherwise.
+pub fn parse_config<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T, String> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("yaml" | "yml") => serde_yaml::from_str(contents).map_err(|e| e.to_string()),
+        Some("json") => serde_json::from_str(contents).map_err(|e| e.to_string()),
+        _ => toml::from_str(contents).map_err(|e| e.to_string()),
+    }
+}
+
+/// Overlays `overlay` on `base`: tables are merged key by key, anything else is replaced.
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// The config at `source` merged over the one its `extends` names, which is resolved relative
+/// to it and may be remote, pinned by `extends_sha256`.
+fn load_config_value(source: &str, sha256: Option<&str>, depth: usize) -> Result<Value, String> {
+    if depth > MAX_EXTENDS_DEPTH {
+        return Err(format!(
+            "{}: more than {} levels of `extends`; is there a cycle?",
+            source, MAX_EXTENDS_DEPTH
+        ));
+    }
+    let contents = read_source(source, sha256)?;
+    let url = resolve_url(source);
+    let format_path = Path::new(url.split('?').next().unwrap_or(&url));
+    let mut value: Value =
+        parse_config(format_path, &contents).map_err(|e| format!("{}: {}", source, e))?;
+
+    let (extends, pin) = match value.as_object_mut() {
+        Some(table) => (table.remove("extends"), table.remove("extends_sha256")),
+        None => (None, None),
+    };
+    let extends = match extends {
+        None => return Ok(value),
+        Some(Value::String(extends)) => extends,
+        Some(_) => return Err(format!("{}: `extends` must be a path or URL", source)),
+    };
+    let pin = match pin {
+        None => None,
+        Some(Value::String(pin)) => Some(pin),
+        Some(_) => return Err(format!("{}: `extends_sha256` must be a string", source)),
+    };
+    let mut base = load_config_value(&join_source(source, &extends), pin.as_deref(), depth + 1)?;
+    merge(&mut base, value);
+    Ok(base)
+}
+
+/// Loads the config at `source`, which may be remote, following `extends`, with the
+/// `[profile.<name>]` table of `profile` applied on top. A missing local file gives the defaults
+/// unless it was named explicitly.
+pub fn load_config(source: &ConfigSource, profile: Option<&str>) -> Result<Config, String> {
+    let path = source.path.as_str();
+    let mut value = if is_remote(path) || Path::new(path).exists() {
+        load_config_value(path, source.sha256.as_deref(), 0)?
+    } else if source.explicit {
+        return Err(format!("config file {} not found", path));
+    } else {
+        Value::Object(Default::default())
+    };
+    let mut profiles = value
+        .as_object_mut()
+        .and_then(|table| table.remove("profile"))
+        .unwrap_or(Value::Null);
+    if let Some(name) = profile {
+        let overlay = profiles
+            .get_mut(name)
+            .map(Value::take)
+            .ok_or_else(|| format!("no profile `{}` in {}", name, path))?;
+        merge(&mut value, overlay);
+    }
+    if let Some(Value::Object(inputs)) = value
+        .as_object_mut()
+        .and_then(|table| table.remove("metrics"))
+    {
+        // Settings made the crate's own way win over the translated inputs.
+        let translated = translate_metrics_inputs(&inputs);
+        for note in &translated.notes {
+            report_anomaly(format!("[metrics] not carried over: {}", note));
+        }
+        let mut compat = serde_json::to_value(&translated.config).map_err(|e| e.to_string())?;
+        merge(&mut compat, value);
+        value = compat;
+    }
+    let mut config: Config = serde_json::from_value(value)
+        .map_err(|e| format!("Failed to parse profile config {}: {}", path, e))?;
+    if let Some(name) = &config.timezone {
+        parse_timezone(name)?;
+    }
+    // Every command that draws a heatmap indexes into it.
+    config.theme.heatmap.validate()?;
+    check_theme_contrast(&mut config);
+    Ok(config)
+}
@@ -0,0 +1,111 @@
+//! A one-page CV from the config's experience and the fetched stats, built from the same
+//! document model as the README so every backend can lay it out.
+
+use crate::anomaly::report_anomaly;
+use crate::builder::{Profile, ProfileBuilder};
+use crate::config::{Config, CvConfig, CvEntry};
+use crate::document::{Block, Section};
+use crate::profile::{format_github_stats, format_languages_compact, ProfileData};
+use crate::sanitize::html_escape;
+use crate::widgets::mentorship::format_mentorship;
+use crate::widgets::talks::{format_talks, load_talks};
+
+/// Languages named under "Open source"; the long tail says little on a CV.
+const CV_LANGUAGES: usize = 6;
+
+/// Whether the CV at `path` is written as an HTML page rather than markdown.
+pub fn is_html(path: &str) -> bool {
+    let path = path.to_lowercase();
+    path.ends_with(".html") || path.ends_with(".htm")
+}
+
+fn format_entry(entry: &CvEntry) -> Block {
+    let title = html_escape(&entry.title);
+    let mut html = match &entry.link {
+        Some(link) => format!("<b><a href=\"{}\">{}</a></b>", html_escape(link), title),
+        None => format!("<b>{}</b>", title),
+    };
+    if let Some(organization) = &entry.organization {
+        html += &format!(" · {}", html_escape(organization));
+    }
+    html += &format!(
+        " · <i>{} – {}</i>",
+        html_escape(&entry.from),
+        html_escape(entry.to.as_deref().unwrap_or("present"))
+    );
+    if let Some(summary) = &entry.summary {
+        html += &format!("<br>{}", html_escape(summary));
+    }
+    Block::text(html)
+}
+
+fn header(cv: &CvConfig, username: &str) -> Vec<Block> {
+    let mut title = format!(
+        "<b>{}</b>",
+        html_escape(cv.name.as_deref().unwrap_or(username))
+    );
+    if let Some(headline) = &cv.headline {
+        title += &format!("<br>{}", html_escape(headline));
+    }
+    let mut contact = Vec::new();
+    if let Some(location) = &cv.location {
+        contact.push(html_escape(location));
+    }
+    if let Some(email) = &cv.email {
+        contact.push(format!(
+            "<a href=\"mailto:{0}\">{0}</a>",
+            html_escape(email)
+        ));
+    }
+    if let Some(website) = &cv.website {
+        contact.push(format!("<a href=\"{0}\">{0}</a>", html_escape(website)));
+    }
+    contact.push(format!(
+        "<a href=\"https://github.com/{0}\">github.com/{0}</a>",
+        html_escape(username)
+    ));
+    vec![Block::centered(title), Block::centered(contact.join(" · "))]
+}
+
+/// Experience and education from `cv`, the stats and top languages from `data`, and the
+/// mentorship and talks sections when the README has them.
+pub fn build_cv(data: &ProfileData, config: &Config, cv: &CvConfig) -> Profile {
+    let mut builder = ProfileBuilder::new(&config.username)
+        .with_header(header(cv, &config.username))
+        .with_style(&config.style);
+    for (title, entries) in [("Experience", &cv.experience), ("Education", &cv.education)] {
+        if !entries.is_empty() {
+            builder = builder.with_section(Section::new(
+                title,
+                entries.iter().map(format_entry).collect(),
+            ));
+        }
+    }
+
+    let languages = &data.languages[..data.languages.len().min(CV_LANGUAGES)];
+    builder = builder.with_section(Section::new(
+        "Open source",
+        vec![
+            format_github_stats(&data.stats),
+            format_languages_compact(languages),
+        ],
+    ));
+
+    if let Some(mentorship) = config.mentorship.as_ref().filter(|m| !m.entries.is_empty()) {
+        builder = builder.with_section(Section::new(
+            "Mentorship",
+            vec![format_mentorship(&mentorship.entries)],
+        ));
+    }
+    if let Some(talks_config) = &config.talks {
+        match load_talks(talks_config) {
+            Ok(mut talks) if !talks.is_empty() => {
+                let talks = format_talks(&mut talks, talks_config.limit);
+                builder = builder.with_section(Section::new("Talks & publications", talks));
+            }
+            Ok(_) => {}
+            Err(e) => report_anomaly(format!("Skipping talks on the CV: {}", e)),
+        }
+    }
+    builder.build()
+}
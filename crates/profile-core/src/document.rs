@@ -0,0 +1,303 @@
+//! The backend-independent form of a profile: sections of typed blocks that the widgets fill in
+//! and the backends in [`crate::backend`] lay out as markdown, HTML, SVG or ANSI text.
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How cell text sits in a table column.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Column {
+    pub heading: String,
+    /// Width of the cell text in characters, not counting padding.
+    pub width: usize,
+    pub align: Align,
+}
+
+impl Column {
+    pub fn new(heading: impl Into<String>, width: usize, align: Align) -> Self {
+        Column {
+            heading: heading.into(),
+            width,
+            align,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Image {
+    pub src: String,
+    pub alt: String,
+    /// Tooltip text.
+    pub title: Option<String>,
+    /// Page the image links to.
+    pub link: Option<String>,
+    /// Width and height in pixels; the image's own size if unset.
+    pub size: Option<u32>,
+}
+
+impl Image {
+    pub fn new(src: impl Into<String>, alt: impl Into<String>) -> Self {
+        Image {
+            src: src.into(),
+            alt: alt.into(),
+            title: None,
+            link: None,
+            size: None,
+        }
+    }
+}
+
+/// The kinds of GitHub alert.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CalloutKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl CalloutKind {
+    /// The name used in a `> [!NAME]` alert.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CalloutKind::Note => "NOTE",
+            CalloutKind::Tip => "TIP",
+            CalloutKind::Important => "IMPORTANT",
+            CalloutKind::Warning => "WARNING",
+            CalloutKind::Caution => "CAUTION",
+        }
+    }
+}
+
+/// The language tag on markdown code fences, which picks the colours GitHub highlights the
+/// art inside them with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FenceLanguage {
+    /// A bare fence.
+    #[default]
+    None,
+    Text,
+    Css,
+}
+
+impl FenceLanguage {
+    /// What follows the opening backticks.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            FenceLanguage::None => "",
+            FenceLanguage::Text => "text",
+            FenceLanguage::Css => "css",
+        }
+    }
+}
+
+/// How a run of blocks is set apart: not at all, as a plain blockquote or as an alert.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Wrap {
+    #[default]
+    None,
+    Quote,
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl Wrap {
+    pub fn apply(self, blocks: Vec<Block>) -> Vec<Block> {
+        let kind = match self {
+            Wrap::None => return blocks,
+            Wrap::Quote => return vec![Block::Quote(blocks)],
+            Wrap::Note => CalloutKind::Note,
+            Wrap::Tip => CalloutKind::Tip,
+            Wrap::Important => CalloutKind::Important,
+            Wrap::Warning => CalloutKind::Warning,
+            Wrap::Caution => CalloutKind::Caution,
+        };
+        vec![Block::Callout { kind, blocks }]
+    }
+}
+
+/// One piece of a section. `Text` and captions are inline HTML that the widget has already
+/// escaped; every other string is plain text that each backend escapes for its own format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Block {
+    /// Preformatted text such as ASCII art, kept exactly as given in a monospace font.
+    Art(String),
+    /// A grid under a heading row, each cell padded to its column's width.
+    Table {
+        columns: Vec<Column>,
+        rows: Vec<Vec<String>>,
+    },
+    /// `(label, percentage)` bars, with `art` bottom-aligned to their right.
+    Bars {
+        bars: Vec<(String, f64)>,
+        art: Vec<String>,
+    },
+    /// A paragraph of inline HTML (`<b>`, `<i>`, `<a>`), which the text backends strip.
+    Text { html: String, centered: bool },
+    /// A centered row of images under an optional inline HTML caption.
+    Images {
+        caption: Option<String>,
+        images: Vec<Image>,
+    },
+    /// A highlighted aside, shown as a GitHub alert in markdown.
+    Callout {
+        kind: CalloutKind,
+        blocks: Vec<Block>,
+    },
+    /// A plain blockquote.
+    Quote(Vec<Block>),
+    /// A horizontal divider.
+    Rule,
+}
+
+impl Block {
+    pub fn text(html: impl Into<String>) -> Self {
+        Block::Text {
+            html: html.into(),
+            centered: false,
+        }
+    }
+
+    pub fn centered(html: impl Into<String>) -> Self {
+        Block::Text {
+            html: html.into(),
+            centered: true,
+        }
+    }
+
+    /// A small placeholder box for a widget whose data couldn't be fetched.
+    pub fn unavailable() -> Self {
+        Block::Art(
+            "╭────────────────────╮\n│ ⚠ data unavailable │\n╰────────────────────╯".to_string(),
+        )
+    }
+}
+
+/// A run of blocks under an optional heading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    pub title: Option<String>,
+    pub blocks: Vec<Block>,
+    /// Overrides [`Document::fence`] for this section's code fences.
+    #[serde(default)]
+    pub fence: Option<FenceLanguage>,
+    /// When the data behind the section was fetched, for an "as of" note.
+    #[serde(default)]
+    pub as_of: Option<NaiveDate>,
+    /// The data is older than the freshness policy allows; shown with a warning.
+    #[serde(default)]
+    pub stale: bool,
+}
+
+impl Section {
+    pub fn new(title: impl Into<String>, blocks: Vec<Block>) -> Self {
+        Section {
+            title: Some(title.into()),
+            blocks,
+            fence: None,
+            as_of: None,
+            stale: false,
+        }
+    }
+
+    /// Blocks without a heading, such as a banner.
+    pub fn untitled(blocks: Vec<Block>) -> Self {
+        Section {
+            title: None,
+            blocks,
+            fence: None,
+            as_of: None,
+            stale: false,
+        }
+    }
+
+    /// Dates the section's data, so `style.as_of` can note how old it is.
+    pub fn with_as_of(mut self, as_of: Option<NaiveDate>) -> Self {
+        self.as_of = as_of;
+        self
+    }
+
+    /// A heading over preformatted `content`, which markdown puts in a code fence.
+    pub fn fenced(title: impl Into<String>, content: &str) -> Self {
+        Section::new(title, vec![Block::Art(content.to_string())])
+    }
+
+    /// A section of just [`Block::unavailable`].
+    pub fn unavailable(title: impl Into<String>) -> Self {
+        Section::new(title, vec![Block::unavailable()])
+    }
+}
+
+/// A section heading with the anchor GitHub gives it.
+pub struct Heading {
+    pub title: String,
+    pub anchor: String,
+}
+
+/// A whole profile, its sections already in display order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    /// The top heading when there is no `header`, and the page title.
+    pub title: String,
+    pub header: Option<Vec<Block>>,
+    pub sections: Vec<Section>,
+    pub footer: Vec<Block>,
+    /// A line of links to every section under the header.
+    pub table_of_contents: bool,
+    /// The language of code fences outside sections that set their own.
+    #[serde(default)]
+    pub fence: FenceLanguage,
+    /// `#rrggbb` colour of the header's art in backends that colour text.
+    #[serde(default)]
+    pub art_color: Option<String>,
+}
+
+impl Document {
+    /// Headings of the titled sections in order. Anchors of repeated headings get `-1`, `-2`,
+    /// ... appended, as GitHub does.
+    pub fn headings(&self) -> Vec<Heading> {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        self.sections
+            .iter()
+            .filter_map(|section| section.title.as_ref())
+            .map(|title| {
+                let base = slug(title);
+                let count = seen.entry(base.clone()).or_insert(0);
+                let anchor = match *count {
+                    0 => base,
+                    n => format!("{}-{}", base, n),
+                };
+                *count += 1;
+                Heading {
+                    title: title.clone(),
+                    anchor,
+                }
+            })
+            .collect()
+    }
+}
+
+/// GitHub's heading anchor: lowercased, punctuation dropped and spaces turned into hyphens.
+pub fn slug(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
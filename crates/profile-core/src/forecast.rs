@@ -0,0 +1,117 @@
+//! Straight-line forecasts over the stats history, with guards against reading too much into
+//! a short or erratic series.
+
+use chrono::{Duration, NaiveDate};
+
+/// Fewer snapshots than this give no forecast.
+pub const MIN_SNAPSHOTS: usize = 5;
+/// Nor does a series spanning fewer days than this.
+pub const MIN_SPAN_DAYS: i64 = 30;
+/// Dates further out than this are not worth naming.
+const MAX_HORIZON_DAYS: i64 = 5 * 365;
+
+/// A least-squares line through a metric's history.
+#[derive(Debug, Clone, Copy)]
+pub struct Trend {
+    pub per_day: f64,
+    /// R² of the fit: 1 for a perfectly steady pace, towards 0 for a noisy one.
+    pub fit: f64,
+    /// The fitted value at the last snapshot, which smooths over a one-off jump.
+    pub last: f64,
+}
+
+/// How far a forecast can be trusted, from how well the line fits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Confidence {
+    Steady,
+    Rough,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Forecast {
+    Reached,
+    NotEnoughHistory,
+    /// Not growing, or growing too erratically to name a date.
+    NoTrend,
+    /// Growing, but not within five years.
+    Distant,
+    Eta {
+        date: NaiveDate,
+        confidence: Confidence,
+    },
+}
+
+/// Fits a line through `series` (oldest first), or `None` when it is too short to say anything.
+pub fn fit_trend(series: &[(NaiveDate, u64)]) -> Option<Trend> {
+    let (first, last) = (series.first()?.0, series.last()?.0);
+    if series.len() < MIN_SNAPSHOTS || (last - first).num_days() < MIN_SPAN_DAYS {
+        return None;
+    }
+    let points: Vec<(f64, f64)> = series
+        .iter()
+        .map(|(date, value)| ((*date - first).num_days() as f64, *value as f64))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let syy: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+
+    let per_day = sxy / sxx;
+    let last_x = points.last()?.0;
+    Some(Trend {
+        per_day,
+        fit: if syy == 0.0 {
+            1.0
+        } else {
+            (sxy * sxy) / (sxx * syy)
+        },
+        last: mean_y + per_day * (last_x - mean_x),
+    })
+}
+
+/// When `series` reaches `target` if it keeps its pace since the first snapshot.
+pub fn forecast(series: &[(NaiveDate, u64)], target: u64) -> Forecast {
+    let Some(&(last_date, current)) = series.last() else {
+        return Forecast::NotEnoughHistory;
+    };
+    if current >= target {
+        return Forecast::Reached;
+    }
+    let Some(trend) = fit_trend(series) else {
+        return Forecast::NotEnoughHistory;
+    };
+    let confidence = match trend.fit {
+        fit if fit >= 0.9 => Confidence::Steady,
+        fit if fit >= 0.5 => Confidence::Rough,
+        _ => return Forecast::NoTrend,
+    };
+    if trend.per_day <= 0.0 {
+        return Forecast::NoTrend;
+    }
+    let days = ((target as f64 - trend.last.max(current as f64)) / trend.per_day).ceil() as i64;
+    match days {
+        days if days > MAX_HORIZON_DAYS => Forecast::Distant,
+        days => Forecast::Eta {
+            date: last_date + Duration::days(days.max(1)),
+            confidence,
+        },
+    }
+}
+
+/// The next round number above `value`: 10, 25, 50, 100, 250, 500, 1000, ...
+pub fn next_milestone(value: u64) -> u64 {
+    let mut scale = 1;
+    loop {
+        for step in [10, 25, 50] {
+            if step * scale > value {
+                return step * scale;
+            }
+        }
+        scale *= 10;
+    }
+}
@@ -0,0 +1,145 @@
+use crate::cache::write_atomically;
+use crate::widgets::spotlight::SpotlightRotation;
+use crate::OTHER_LANGUAGE;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// The metrics of a single run, keyed by the same names as the stats JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub date: NaiveDate,
+    pub metrics: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+impl Snapshot {
+    pub fn new(
+        date: NaiveDate,
+        stats: &Value,
+        followers: u64,
+        languages: &[(String, f64)],
+    ) -> Self {
+        let mut metrics: BTreeMap<String, u64> = stats
+            .as_object()
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(name, value)| Some((name.clone(), value.as_u64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        metrics.insert("followers".to_string(), followers);
+
+        Snapshot {
+            date,
+            metrics,
+            languages: languages
+                .iter()
+                .map(|(lang, _)| lang.clone())
+                .filter(|lang| lang != OTHER_LANGUAGE)
+                .collect(),
+        }
+    }
+
+    pub fn metric(&self, name: &str) -> u64 {
+        self.get(name).unwrap_or(0)
+    }
+
+    /// The metric, or `None` when the snapshot doesn't know it, as with backfilled follower and
+    /// star counts.
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.metrics.get(name).copied()
+    }
+}
+
+/// How a snapshot metric reads in a sentence ("crossed 100 stars").
+pub fn metric_noun(name: &str) -> &str {
+    match name {
+        "total_stars" => "stars",
+        "total_commits" => "commits",
+        "total_prs" => "pull requests",
+        "total_issues" => "issues",
+        "repos_owned" => "repositories",
+        "contributed_to" => "contributed-to repositories",
+        _ => name,
+    }
+}
+
+/// Summary of one Monday-to-Sunday week, computed once and kept in the history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyDigest {
+    pub week_start: NaiveDate,
+    pub commits: u64,
+    pub repos: usize,
+    pub prs_merged: u64,
+    pub follower_delta: i64,
+}
+
+/// One snapshot per day, oldest first. Committed next to the README so it survives between runs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    pub snapshots: Vec<Snapshot>,
+    /// The most recent weekly digest, so it is only recomputed once a week.
+    #[serde(default)]
+    pub digest: Option<WeeklyDigest>,
+    /// Which repository the spotlight features this week and which it featured before.
+    #[serde(default)]
+    pub spotlight: SpotlightRotation,
+}
+
+impl History {
+    /// Stores `snapshot`, replacing an earlier snapshot from the same day.
+    pub fn record(&mut self, snapshot: Snapshot) {
+        self.snapshots
+            .retain(|existing| existing.date != snapshot.date);
+        self.snapshots.push(snapshot);
+        self.snapshots.sort_by_key(|existing| existing.date);
+    }
+
+    /// The date `metric` most recently went from below `threshold` to at least `threshold`.
+    ///
+    /// Returns `None` if it was already above the threshold in the oldest snapshot, since the
+    /// actual crossing date is unknown then. Snapshots without the metric are skipped.
+    pub fn crossed_on(&self, metric: &str, threshold: u64) -> Option<NaiveDate> {
+        let series: Vec<(NaiveDate, u64)> = self
+            .snapshots
+            .iter()
+            .filter_map(|snapshot| Some((snapshot.date, snapshot.get(metric)?)))
+            .collect();
+        series
+            .windows(2)
+            .rev()
+            .find(|pair| pair[0].1 < threshold && pair[1].1 >= threshold)
+            .map(|pair| pair[1].0)
+    }
+
+    /// The latest snapshot taken strictly before `date`.
+    pub fn previous(&self, date: NaiveDate) -> Option<&Snapshot> {
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| snapshot.date < date)
+    }
+}
+
+/// The history at `path`, empty if there is no file yet. A file that can't be read or parsed is
+/// an error rather than an empty history, which the next save would write over.
+pub fn load_history(path: &Path) -> Result<History, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(History::default()),
+        Err(e) => return Err(format!("can't read {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("{} is not a valid history file: {}", path.display(), e))
+}
+
+pub fn save_history(path: &Path, history: &History) {
+    let contents = serde_json::to_string_pretty(history).expect("Failed to serialize history");
+    write_atomically(path, &contents).expect("Failed to write history file");
+}
@@ -0,0 +1,275 @@
+//! Starting configs for people coming from other profile tools: the options of
+//! github-readme-stats cards, read from the image URLs in an existing README, and the inputs of a
+//! lowlighter/metrics workflow step. Whatever has no counterpart here is listed, not dropped
+//! silently.
+
+use toml::{Table, Value};
+use url::Url;
+
+/// The tool an import reads the options of.
+#[derive(Debug, Clone, Copy)]
+pub enum Source {
+    /// Card URLs of github-readme-stats and github-readme-streak-stats in a README.
+    ReadmeStats,
+    /// A GitHub Actions workflow running lowlighter/metrics.
+    Metrics,
+}
+
+impl Source {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "readme-stats" => Some(Source::ReadmeStats),
+            "metrics" => Some(Source::Metrics),
+            _ => None,
+        }
+    }
+}
+
+/// A translated config and the options that couldn't be carried over.
+pub struct Import {
+    pub config: Table,
+    pub notes: Vec<String>,
+}
+
+impl Import {
+    /// The config as TOML, with the notes as comments on top.
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::new();
+        if !self.notes.is_empty() {
+            toml += "# Not carried over:\n";
+            for note in &self.notes {
+                toml += &format!("# - {}\n", note);
+            }
+            toml += "\n";
+        }
+        toml + &toml::to_string(&self.config).expect("Failed to serialize imported config")
+    }
+
+    fn set(&mut self, path: &[&str], value: Value) {
+        let (key, tables) = path.split_last().expect("empty config path");
+        let mut table = &mut self.config;
+        for name in tables {
+            table = table
+                .entry(name.to_string())
+                .or_insert_with(|| Value::Table(Table::new()))
+                .as_table_mut()
+                .expect("config path runs through a value");
+        }
+        table.insert(key.to_string(), value);
+    }
+
+    fn note(&mut self, note: String) {
+        if !self.notes.contains(&note) {
+            self.notes.push(note);
+        }
+    }
+}
+
+pub fn import(source: Source, contents: &str) -> Result<Import, String> {
+    match source {
+        Source::ReadmeStats => import_readme_stats(contents),
+        Source::Metrics => import_metrics(contents),
+    }
+}
+
+/// Every github-readme-stats or streak-stats card URL in `readme`.
+fn card_urls(readme: &str) -> Vec<Url> {
+    readme
+        .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '(' | ')' | '<' | '>'))
+        .filter(|word| word.starts_with("https://"))
+        .filter_map(|word| Url::parse(&word.replace("&amp;", "&")).ok())
+        .filter(|url| {
+            let host = url.host_str().unwrap_or("");
+            host.starts_with("github-readme-stats") || host.contains("streak-stats")
+        })
+        .collect()
+}
+
+pub fn import_readme_stats(readme: &str) -> Result<Import, String> {
+    let cards = card_urls(readme);
+    if cards.is_empty() {
+        return Err("no github-readme-stats or streak-stats cards found".to_string());
+    }
+    let mut import = Import {
+        config: Table::new(),
+        notes: Vec::new(),
+    };
+
+    for card in cards {
+        let streak = card.host_str().unwrap_or("").contains("streak-stats");
+        let card_name = match card.path() {
+            _ if streak => "streak",
+            "/api/top-langs" | "/api/top-langs/" => "top languages",
+            "/api/pin" | "/api/pin/" => "pin",
+            "/api/wakatime" => "wakatime",
+            _ => "stats",
+        };
+        match card_name {
+            "streak" => import.set(
+                &["header", "badges"],
+                Value::Array(
+                    ["followers", "stars", "streak"]
+                        .map(|badge| Value::String(badge.to_string()))
+                        .to_vec(),
+                ),
+            ),
+            "pin" | "wakatime" => {
+                import.note(format!("the {} card has no counterpart", card_name));
+                continue;
+            }
+            _ => {}
+        }
+
+        for (name, value) in card.query_pairs() {
+            match (card_name, name.as_ref()) {
+                (_, "username" | "user") => {
+                    import.set(&["username"], Value::String(value.to_string()))
+                }
+                ("top languages", "langs_count") => match value.parse::<i64>() {
+                    // Their count leaves out the "Other" row this crate adds.
+                    Ok(count) => import.set(&["languages", "max_rows"], Value::Integer(count + 1)),
+                    Err(_) => import.note(format!("invalid langs_count `{}`", value)),
+                },
+                (_, "theme") => import.note(format!(
+                    "theme `{}`; colours are set under [theme] instead",
+                    value
+                )),
+                (_, "hide") => import.note(format!(
+                    "hidden {} `{}`",
+                    if card_name == "top languages" {
+                        "languages"
+                    } else {
+                        "stats"
+                    },
+                    value
+                )),
+                (_, "show_icons" | "hide_border" | "hide_title" | "layout" | "card_width")
+                | (_, "bg_color" | "title_color" | "text_color" | "icon_color" | "border_color")
+                | (_, "border_radius" | "locale" | "cache_seconds" | "disable_animations") => {}
+                (_, other) => import.note(format!("{} card option `{}`", card_name, other)),
+            }
+        }
+    }
+    Ok(import)
+}
+
+/// Whether a metrics input is switched on; the action accepts `yes`, `true` and `on`.
+fn is_enabled(value: &serde_yaml::Value) -> bool {
+    match value {
+        serde_yaml::Value::Bool(enabled) => *enabled,
+        serde_yaml::Value::String(text) => matches!(text.as_str(), "yes" | "true" | "on"),
+        _ => false,
+    }
+}
+
+fn as_text(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(text) => Some(text.clone()),
+        serde_yaml::Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// The `with:` inputs of the first lowlighter/metrics step in `workflow`.
+fn metrics_inputs(workflow: &serde_yaml::Value) -> Option<&serde_yaml::Mapping> {
+    workflow["jobs"]
+        .as_mapping()?
+        .values()
+        .filter_map(|job| job["steps"].as_sequence())
+        .flatten()
+        .find(|step| {
+            step["uses"]
+                .as_str()
+                .is_some_and(|uses| uses.starts_with("lowlighter/metrics"))
+        })?["with"]
+        .as_mapping()
+}
+
+pub fn import_metrics(workflow: &str) -> Result<Import, String> {
+    let workflow: serde_yaml::Value = serde_yaml::from_str(workflow).map_err(|e| e.to_string())?;
+    let inputs = metrics_inputs(&workflow).ok_or("no lowlighter/metrics step found")?;
+    let mut import = Import {
+        config: Table::new(),
+        notes: Vec::new(),
+    };
+    for (name, value) in inputs {
+        if let Some(name) = name.as_str() {
+            import.metrics_input(name, as_text(value), is_enabled(value));
+        }
+    }
+    Ok(import)
+}
+
+/// The config a `[metrics]` table of lowlighter/metrics inputs stands for, so the `with:` block
+/// of a metrics workflow can be pasted into the config as it is.
+pub fn translate_metrics_inputs(inputs: &serde_json::Map<String, serde_json::Value>) -> Import {
+    let mut import = Import {
+        config: Table::new(),
+        notes: Vec::new(),
+    };
+    for (name, value) in inputs {
+        let text = match value {
+            serde_json::Value::String(text) => Some(text.clone()),
+            serde_json::Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        };
+        let enabled = value.as_bool().unwrap_or(false)
+            || matches!(value.as_str(), Some("yes" | "true" | "on"));
+        import.metrics_input(name, text, enabled);
+    }
+    import
+}
+
+impl Import {
+    /// Carries over one lowlighter/metrics input, given as text when it has a value.
+    fn metrics_input(&mut self, name: &str, text: Option<String>, enabled: bool) {
+        match name {
+            "user" => {
+                if let Some(user) = text {
+                    self.set(&["username"], Value::String(user));
+                }
+            }
+            "filename" => match text {
+                Some(file) if file.ends_with(".md") => self.set(&["output"], Value::String(file)),
+                Some(file) => self.note(format!(
+                    "output `{}`; this crate writes markdown, not images",
+                    file
+                )),
+                None => {}
+            },
+            "config_timezone" => {
+                if let Some(timezone) = text {
+                    self.set(&["timezone"], Value::String(timezone));
+                }
+            }
+            "plugin_languages_limit" => match text.and_then(|v| v.parse::<i64>().ok()) {
+                Some(limit) => self.set(&["languages", "max_rows"], Value::Integer(limit + 1)),
+                None => self.note("invalid plugin_languages_limit".to_string()),
+            },
+            // The languages table is always there unless it is explicitly switched off.
+            "plugin_languages" if !enabled => self.set(
+                &["layout", "hidden"],
+                Value::Array(vec![Value::String("Languages".to_string())]),
+            ),
+            "plugin_achievements" if enabled => self.set(&["achievements"], Value::Boolean(true)),
+            "plugin_isocalendar" | "plugin_calendar" if enabled => {
+                self.set(&["year_comparison"], Value::Boolean(true))
+            }
+            "plugin_stargazers" if enabled => self.set(&["stargazers"], Value::Table(Table::new())),
+            "plugin_contributors" if enabled => {
+                self.set(&["contributors"], Value::Table(Table::new()))
+            }
+            "plugin_followup" if enabled => self.note(
+                "the followup plugin; [responsiveness] needs the repository to watch".to_string(),
+            ),
+            "plugin_languages" | "plugin_activity" => {}
+            name if name.starts_with("plugin_") && !name[7..].contains('_') && enabled => {
+                self.note(format!("the `{}` plugin has no counterpart", &name[7..]))
+            }
+            name if name.starts_with("plugin_") && !name[7..].contains('_') => {}
+            name if name.starts_with("plugin_") => self.note(format!("plugin option `{}`", name)),
+            // Tokens, commit settings and SVG styling only make sense for the action itself.
+            _ => {}
+        }
+    }
+}
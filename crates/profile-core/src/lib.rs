@@ -0,0 +1,42 @@
+//! The profile's data model and everything that renders it: the config, the document and its
+//! backends, the history, and the formatting side of every widget. Nothing here talks to the
+//! network; the GitHub providers are in the `profile-github` crate.
+
+pub mod anomaly;
+pub mod anonymize;
+pub mod art;
+pub mod backend;
+pub mod builder;
+pub mod cache;
+pub mod calendar;
+pub mod cancel;
+pub mod cast;
+pub mod changelog;
+pub mod circuit;
+pub mod config;
+pub mod cv;
+pub mod document;
+pub mod forecast;
+pub mod history;
+pub mod import;
+pub mod lint;
+pub mod locales;
+pub mod overrides;
+pub mod percent;
+pub mod plausibility;
+pub mod profile;
+pub mod query_budget;
+pub mod remote;
+pub mod render;
+pub mod rules;
+pub mod sampling;
+pub mod sanitize;
+pub mod secrets;
+pub mod spell;
+pub mod status;
+pub mod validate;
+pub mod widget_cache;
+pub mod widgets;
+
+/// Row that collects the languages too small to list individually.
+pub const OTHER_LANGUAGE: &str = "Other";
@@ -0,0 +1,55 @@
+//! Checks on the rendered README that GitHub won't flag itself: it truncates very large files,
+//! and a wide line in a code fence makes the whole block scroll sideways.
+
+use crate::config::LintConfig;
+use crate::render::display_width;
+
+/// The line with any `> ` blockquote markers in front of it removed.
+fn unquote(line: &str) -> &str {
+    let mut line = line;
+    while let Some(rest) = line.strip_prefix('>') {
+        line = rest.strip_prefix(' ').unwrap_or(rest);
+    }
+    line
+}
+
+/// Everything in `markdown` that breaks `limits`, one message per problem. Line width is only
+/// checked inside code fences, since text outside them wraps.
+pub fn lint_markdown(markdown: &str, limits: &LintConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+    if markdown.len() > limits.max_bytes {
+        problems.push(format!(
+            "README is {} bytes, over the limit of {}",
+            markdown.len(),
+            limits.max_bytes
+        ));
+    }
+
+    let mut in_fence = false;
+    for (index, line) in markdown.lines().enumerate() {
+        let content = unquote(line);
+        if content.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            continue;
+        }
+        let width = display_width(content);
+        if width > limits.max_line_width {
+            problems.push(format!(
+                "line {} is {} columns wide, over the limit of {}",
+                index + 1,
+                width,
+                limits.max_line_width
+            ));
+        }
+        if !limits.allow_tabs && content.contains('\t') {
+            problems.push(format!(
+                "line {} has a tab inside a code fence, which GitHub renders 8 columns wide",
+                index + 1
+            ));
+        }
+    }
+    problems
+}
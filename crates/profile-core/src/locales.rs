@@ -0,0 +1,97 @@
+//! Translated copies of the README: the finished document again, with section titles from the
+//! locale's table and a row of links between every version under the header.
+
+use crate::backend::markdown;
+use crate::config::{LocaleConfig, LocalesConfig};
+use crate::document::{Block, Document, Section};
+use crate::sanitize::html_escape;
+use std::path::Path;
+
+/// Where `locale` is written: its `output`, or `readme` with the code before the extension.
+pub fn locale_output(locale: &LocaleConfig, readme: &str) -> String {
+    if let Some(output) = &locale.output {
+        return output.clone();
+    }
+    let path = Path::new(readme);
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(readme);
+    let file = match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) => format!("{}.{}.{}", stem, locale.code, extension),
+        None => format!("{}.{}", stem, locale.code),
+    };
+    path.with_file_name(file).to_string_lossy().into_owned()
+}
+
+/// "English · **Deutsch** · 日本語", with the current version in bold and the others linked.
+/// Links are relative, so the versions are expected to sit in the same directory.
+fn switcher(config: &LocalesConfig, readme: &str, current: Option<&LocaleConfig>) -> Block {
+    let file_name = |path: &str| {
+        Path::new(path)
+            .file_name()
+            .map_or(path.to_string(), |name| name.to_string_lossy().into_owned())
+    };
+    let mut links = vec![match current {
+        None => format!("<b>{}</b>", html_escape(&config.name)),
+        Some(_) => format!(
+            "<a href=\"{}\">{}</a>",
+            html_escape(&file_name(readme)),
+            html_escape(&config.name)
+        ),
+    }];
+    for locale in &config.translations {
+        links.push(match current {
+            Some(current) if current.code == locale.code => {
+                format!("<b>{}</b>", html_escape(&locale.name))
+            }
+            _ => format!(
+                "<a href=\"{}\">{}</a>",
+                html_escape(&file_name(&locale_output(locale, readme))),
+                html_escape(&locale.name)
+            ),
+        });
+    }
+    Block::centered(links.join(" · "))
+}
+
+/// `document` with the language links added under its header, in the version for `current`
+/// (`None` for the main README).
+fn with_switcher(
+    mut document: Document,
+    config: &LocalesConfig,
+    readme: &str,
+    current: Option<&LocaleConfig>,
+) -> Document {
+    document.sections.insert(
+        0,
+        Section::untitled(vec![switcher(config, readme, current)]),
+    );
+    document
+}
+
+/// The main README with the language links, followed by each translation's path and markdown.
+pub fn localize(
+    document: &Document,
+    config: &LocalesConfig,
+    readme: &str,
+) -> (String, Vec<(String, String)>) {
+    let main = markdown::render(&with_switcher(document.clone(), config, readme, None));
+    let translations = config
+        .translations
+        .iter()
+        .map(|locale| {
+            let mut translated = document.clone();
+            for section in &mut translated.sections {
+                if let Some(title) = section.title.as_mut() {
+                    if let Some(translation) = locale.titles.get(title) {
+                        *title = translation.clone();
+                    }
+                }
+            }
+            let translated = with_switcher(translated, config, readme, Some(locale));
+            (locale_output(locale, readme), markdown::render(&translated))
+        })
+        .collect();
+    (main, translations)
+}
@@ -0,0 +1,69 @@
+//! Hand-set values for displayed metrics, for users whose public API numbers undercount their
+//! real activity (private repositories, other forges). Overridden numbers stay marked as such:
+//! the stats keep the fetched number next to the override, and the README shows a `*`.
+
+use crate::anomaly::report_anomaly;
+use crate::config::MetricOverride;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Key in the stats under which each overridden metric keeps its fetched number and note.
+pub const OVERRIDES_KEY: &str = "overrides";
+
+/// Metrics that can be overridden besides the numbers in the stats.
+const OTHER_METRICS: [&str; 1] = ["followers"];
+
+/// Overrides `stats` in place, recording `{"fetched": n, "note": ...}` under
+/// [`OVERRIDES_KEY`] for every metric changed. Overrides of metrics the stats don't have (other
+/// than followers, see [`override_count`]) are reported and skipped.
+pub fn apply_overrides(stats: &mut Value, overrides: &BTreeMap<String, MetricOverride>) {
+    let mut applied = serde_json::Map::new();
+    for (metric, pin) in overrides {
+        if OTHER_METRICS.contains(&metric.as_str()) {
+            continue;
+        }
+        let Some(fetched) = stats.get(metric).and_then(Value::as_u64) else {
+            report_anomaly(format!(
+                "Ignoring the override of unknown metric `{}`",
+                metric
+            ));
+            continue;
+        };
+        stats[metric] = json!(pin.value.unwrap_or(fetched));
+        applied.insert(
+            metric.clone(),
+            json!({ "fetched": fetched, "note": pin.note }),
+        );
+    }
+    if !applied.is_empty() {
+        stats[OVERRIDES_KEY] = Value::Object(applied);
+    }
+}
+
+/// `fetched`, or the value pinned for it, for a metric kept outside the stats such as the
+/// follower count. A pin is recorded under [`OVERRIDES_KEY`] in `stats` like any other, so the
+/// README still explains the `*` it gets.
+pub fn override_count(
+    stats: &mut Value,
+    metric: &str,
+    fetched: u64,
+    pin: Option<&MetricOverride>,
+) -> u64 {
+    let Some(pin) = pin else {
+        return fetched;
+    };
+    if !stats[OVERRIDES_KEY].is_object() {
+        stats[OVERRIDES_KEY] = json!({});
+    }
+    stats[OVERRIDES_KEY][metric] = json!({ "fetched": fetched, "note": pin.note });
+    pin.value.unwrap_or(fetched)
+}
+
+/// `value` as displayed: marked with a `*` and followed by the note when it was overridden.
+pub fn format_overridden(value: &str, note: Option<&str>, overridden: bool) -> String {
+    match (overridden, note) {
+        (false, _) => value.to_string(),
+        (true, Some(note)) => format!("{}* {}", value, note),
+        (true, None) => format!("{}*", value),
+    }
+}
@@ -0,0 +1,29 @@
+/// Turns raw shares into percentages rounded to `decimals` places that add up to exactly 100.
+///
+/// Uses the largest remainder method: every value is rounded down, and the units still missing
+/// from 100 go to the values that lost the most in rounding (earlier values win ties).
+/// All-zero input stays all zero.
+pub fn normalize_percentages(values: &[f64], decimals: u32) -> Vec<f64> {
+    let total: f64 = values.iter().sum();
+    if values.is_empty() || total <= 0.0 {
+        return vec![0.0; values.len()];
+    }
+
+    let scale = 10u64.pow(decimals) as f64;
+    let target = (100.0 * scale).round() as u64;
+    let exact: Vec<f64> = values.iter().map(|v| v / total * 100.0 * scale).collect();
+    let mut units: Vec<u64> = exact.iter().map(|v| v.floor() as u64).collect();
+
+    let mut by_remainder: Vec<usize> = (0..values.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        (exact[b] - exact[b].floor())
+            .total_cmp(&(exact[a] - exact[a].floor()))
+            .then(a.cmp(&b))
+    });
+    let missing = target.saturating_sub(units.iter().sum());
+    for &index in by_remainder.iter().cycle().take(missing as usize) {
+        units[index] += 1;
+    }
+
+    units.into_iter().map(|unit| unit as f64 / scale).collect()
+}
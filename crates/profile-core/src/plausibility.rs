@@ -0,0 +1,78 @@
+//! Catches metrics that moved implausibly since the last run. A follower count dropping by a
+//! third overnight is almost always a truncated API response or a token without access, not
+//! reality, and shouldn't end up on the profile unnoticed.
+
+use crate::config::PlausibilityConfig;
+use crate::history::{metric_noun, Snapshot};
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+
+pub struct ImplausibleChange {
+    pub metric: String,
+    pub before: u64,
+    pub after: u64,
+    /// Date of the snapshot `before` comes from.
+    pub since: NaiveDate,
+}
+
+impl ImplausibleChange {
+    /// "followers went from 120 to 12 since 2026-10-15".
+    pub fn describe(&self) -> String {
+        format!(
+            "{} went from {} to {} since {}",
+            metric_noun(&self.metric),
+            self.before,
+            self.after,
+            self.since
+        )
+    }
+}
+
+/// Metrics of `current` that dropped by more than `config.max_drop` or grew more than
+/// `config.max_growth` times over `previous`. Metrics below `config.min_value` in `previous`
+/// are left alone, since small counts legitimately jump around.
+pub fn implausible_changes(
+    previous: &Snapshot,
+    current: &Snapshot,
+    config: &PlausibilityConfig,
+) -> Vec<ImplausibleChange> {
+    current
+        .metrics
+        .iter()
+        .filter_map(|(metric, &after)| {
+            let before = previous.get(metric)?;
+            if before < config.min_value {
+                return None;
+            }
+            let ratio = after as f64 / before as f64;
+            (ratio < 1.0 - config.max_drop || ratio > config.max_growth).then(|| {
+                ImplausibleChange {
+                    metric: metric.clone(),
+                    before,
+                    after,
+                    since: previous.date,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Puts the previous values of `changes` back into the snapshot, the stats and the follower
+/// count, so neither the history nor the README takes the suspicious numbers.
+pub fn keep_previous(
+    changes: &[ImplausibleChange],
+    snapshot: &mut Snapshot,
+    stats: &mut Value,
+    followers: &mut u64,
+) {
+    for change in changes {
+        snapshot
+            .metrics
+            .insert(change.metric.clone(), change.before);
+        if change.metric == "followers" {
+            *followers = change.before;
+        } else if let Some(value) = stats.get_mut(&change.metric) {
+            *value = json!(change.before);
+        }
+    }
+}
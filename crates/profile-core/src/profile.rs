@@ -0,0 +1,219 @@
+use crate::anomaly::report_anomaly;
+use crate::builder::ProfileBuilder;
+use crate::calendar::ContributionCalendar;
+use crate::config::Config;
+use crate::document::{Align, Block, Column, Section};
+use crate::history::Snapshot;
+use crate::overrides::{format_overridden, OVERRIDES_KEY};
+#[cfg(feature = "svg")]
+use crate::render::chart::render_line_chart_svg;
+use crate::render::layout::wrap;
+use crate::render::{create_ascii_box, display_width, pad_end};
+use crate::sanitize::{fence_safe, html_escape};
+use crate::widgets::year_comparison::format_year_comparison;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Everything the core sections are rendered from, as fetched from GitHub or pasted into the
+/// playground as JSON.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileData {
+    pub generated_on: NaiveDate,
+    /// `(language, percentage)` rows, already grouped and normalized.
+    pub languages: Vec<(String, f64)>,
+    /// The `total_*`, `repos_owned` and `contributed_to` counts.
+    pub stats: Value,
+    /// Public events as returned by the events API, newest first.
+    #[serde(default)]
+    pub activities: Vec<Value>,
+    #[serde(default)]
+    pub calendar: Option<ContributionCalendar>,
+}
+
+pub fn format_activity(activity: &Value) -> String {
+    let event_type = activity["type"].as_str().unwrap_or("").replace("Event", "");
+    let repo = activity["repo"]["name"].as_str().unwrap_or("");
+    let created_at = activity["created_at"].as_str().unwrap_or("");
+    let dt = DateTime::parse_from_rfc3339(created_at).unwrap_or_else(|_| {
+        report_anomaly(format!(
+            "Activity event without a valid created_at: {:?}",
+            created_at
+        ));
+        Utc::now().into()
+    });
+    format!(
+        "{:<16} | {:<15} | {}",
+        dt.format("%Y-%m-%d %H:%M"),
+        fence_safe(&event_type),
+        fence_safe(repo)
+    )
+}
+
+/// The stats table from the `total_*`, `repos_owned` and `contributed_to` counts.
+pub fn format_github_stats(stats: &Value) -> Block {
+    let overrides = &stats[OVERRIDES_KEY];
+    let count = |key: &str| {
+        format_overridden(
+            &stats[key].as_u64().unwrap_or(0).to_string(),
+            overrides[key]["note"].as_str(),
+            overrides.get(key).is_some(),
+        )
+    };
+    Block::Table {
+        columns: vec![
+            Column::new("Metric", 11, Align::Center),
+            Column::new("Value", 22, Align::Right),
+            Column::new("Metric", 14, Align::Center),
+            Column::new("Value", 36, Align::Right),
+        ],
+        rows: vec![
+            vec![
+                "Commits".to_string(),
+                count("total_commits"),
+                "Issues opened".to_string(),
+                count("total_issues"),
+            ],
+            vec![
+                "PRs opened".to_string(),
+                count("total_prs"),
+                "Stars received".to_string(),
+                count("total_stars"),
+            ],
+            vec![
+                "Repos owned".to_string(),
+                count("repos_owned"),
+                "Contributed to".to_string(),
+                count("contributed_to"),
+            ],
+        ],
+    }
+}
+
+/// `(stats key, icon, label)` of each stat card, in display order.
+const STAT_CARDS: [(&str, &str, &str); 6] = [
+    ("total_commits", "📝", "Commits"),
+    ("total_prs", "🔀", "PRs opened"),
+    ("total_issues", "🐛", "Issues opened"),
+    ("total_stars", "🌟", "Stars received"),
+    ("repos_owned", "📦", "Repos owned"),
+    ("contributed_to", "🤝", "Contributed to"),
+];
+
+/// The stats as equally wide cards, each with its change since `previous` in the fetched number,
+/// wrapped into rows no wider than `max_width` columns.
+pub fn format_stats_cards(stats: &Value, previous: Option<&Snapshot>, max_width: usize) -> Block {
+    let overrides = &stats[OVERRIDES_KEY];
+    let cards: Vec<(String, [String; 2])> = STAT_CARDS
+        .iter()
+        .map(|(key, icon, label)| {
+            let value = stats[*key].as_u64().unwrap_or(0);
+            // The history keeps fetched numbers, so an override is compared by what it replaced.
+            let fetched = overrides[*key]["fetched"].as_u64().unwrap_or(value);
+            let delta = match previous.and_then(|previous| previous.get(key)) {
+                Some(before) if fetched > before => format!("▲ {}", fetched - before),
+                Some(before) if fetched < before => format!("▼ {}", before - fetched),
+                Some(_) => "± 0".to_string(),
+                None => String::new(),
+            };
+            let value = format_overridden(&value.to_string(), None, overrides.get(*key).is_some());
+            (format!("{} {}", icon, label), [value, delta])
+        })
+        .collect();
+    let inner_width = cards
+        .iter()
+        .map(|(title, lines)| {
+            lines
+                .iter()
+                .map(|line| display_width(line))
+                .chain([display_width(title) + 2])
+                .max()
+                .unwrap_or(0)
+        })
+        .max()
+        .unwrap_or(0);
+    let boxes: Vec<Vec<String>> = cards
+        .iter()
+        .map(|(title, lines)| {
+            // Padding the first line makes every box as wide as the widest.
+            let lines = [pad_end(&lines[0], inner_width), lines[1].clone()];
+            create_ascii_box(title, &lines)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .collect();
+    Block::Art(wrap(&boxes, 1, max_width).join("\n"))
+}
+
+/// Shown bottom-aligned beside the language bars.
+const LANGUAGES_ART: [&str; 9] = [
+    "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⣀⣀⣀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀",
+    "⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢇⠀⠃⣈⠇⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀",
+    "⠀⠀⠀⠀⠀⠀⠀⣤⣤⣤⣄⣀⡀⠙⠞⠁⠀⠀⠀⣀⣀⣀⣀⠀⠀⠀⠀⠀",
+    "⠀⠀⠀⠀⠀⠀⢰⡏⢻⣫⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⢿⠟⣿⠀⠀⠀⠀⠀",
+    "⠀⠀⠀⠀⡐⡄⣸⣰⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣿⣷⣄⣿⠀⠀⠀⠀⠀",
+    "⠀⠀⣀⠠⢝⡜⣿⣿⡟⢉⣭⡝⢿⣿⣿⣿⡟⣭⣭⠉⢻⣿⡿⡠⠒⠀⠀⠀",
+    "⡴⣟⣿⣻⣆⢰⣿⣿⠀⢸⣿⣿⢸⣿⣿⣿⠙⣿⣿⠇⠈⣿⣿⠱⠭⠄⠀⠀",
+    "⢷⣿⡀⣸⣿⡞⣿⣿⣄⠀⠉⠁⣼⣿⢿⣿⣧⠈⠁⠀⣰⣿⣿⣠⣴⣶⣦⣄",
+    "⠈⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠉⠙⠒⠓⠒⠛⠛⠛⠛⠛⠛⠓⠻⡏⣿⣿⠿",
+];
+
+/// Language bars with the small art beside them.
+pub fn format_languages(languages: &[(String, f64)]) -> Block {
+    Block::Bars {
+        bars: languages.to_vec(),
+        art: LANGUAGES_ART.iter().map(|line| line.to_string()).collect(),
+    }
+}
+
+/// The languages as one line of names and percentages, for lists too short to be worth bars.
+pub fn format_languages_compact(languages: &[(String, f64)]) -> Block {
+    let languages: Vec<String> = languages
+        .iter()
+        .map(|(name, percentage)| format!("<b>{}</b> {:.1}%", html_escape(name), percentage))
+        .collect();
+    Block::text(languages.join(" · "))
+}
+
+/// Renders the sections that depend only on `data` and the theme, without any I/O.
+pub fn render_preview(data: &ProfileData, config: &Config) -> String {
+    let mut profile = ProfileBuilder::new(&config.username)
+        .with_header(Vec::new())
+        .with_style(&config.style)
+        .with_languages(&data.languages)
+        .with_stats(&data.stats);
+    if let Some(calendar) = &data.calendar {
+        profile = profile.with_section(Section::fenced(
+            "This year vs last year",
+            &format_year_comparison(
+                calendar,
+                data.generated_on,
+                &config.theme.heatmap,
+                &config.vacations,
+            ),
+        ));
+    }
+    profile
+        .with_activity(&data.activities[..data.activities.len().min(5)])
+        .build()
+        .markdown
+}
+
+/// [`render_preview`] across a serialized boundary: `metrics_json` is a [`ProfileData`] and
+/// `config_toml` the contents of a `profile.toml`. Shared by the wasm, C and Python bindings.
+pub fn render_preview_json(metrics_json: &str, config_toml: &str) -> Result<String, String> {
+    let data: ProfileData = serde_json::from_str(metrics_json).map_err(|e| e.to_string())?;
+    let config: Config = toml::from_str(config_toml).map_err(|e| e.to_string())?;
+    config.languages.validate()?;
+    config.theme.heatmap.validate()?;
+    Ok(render_preview(&data, &config))
+}
+
+/// Renders an SVG line chart from `series_json`, an array of `["YYYY-MM-DD", value]` pairs.
+#[cfg(feature = "svg")]
+pub fn render_chart_json(title: &str, series_json: &str) -> Result<String, String> {
+    let series: Vec<(NaiveDate, u64)> =
+        serde_json::from_str(series_json).map_err(|e| e.to_string())?;
+    Ok(render_line_chart_svg(title, &series))
+}
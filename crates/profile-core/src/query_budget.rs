@@ -0,0 +1,186 @@
+//! Keeping GraphQL queries within what GitHub accepts. A query is rejected outright
+//! (`MAX_NODE_LIMIT_EXCEEDED`) if the page sizes of its nested connections multiply out to more
+//! than [`MAX_NODES`], e.g. 100 repositories × 100 pull requests × 100 comments. Queries are
+//! checked before they are sent, and the largest page sizes shrunk until they fit.
+
+use std::ops::Range;
+
+/// Most nodes a single GitHub GraphQL query may ask for.
+pub const MAX_NODES: u64 = 500_000;
+
+/// The `first:`/`last:` page sizes in `query`, as the byte range of each number and its value.
+fn page_sizes(query: &str) -> Vec<(Range<usize>, u64)> {
+    let bytes = query.as_bytes();
+    let is_name = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let mut sizes = Vec::new();
+    let mut in_string = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if in_string => i += 1,
+            b'"' => in_string = !in_string,
+            _ if in_string || (i > 0 && is_name(bytes[i - 1])) => {}
+            _ => {
+                for keyword in ["first", "last"] {
+                    if !bytes[i..].starts_with(keyword.as_bytes()) {
+                        continue;
+                    }
+                    let mut j = i + keyword.len();
+                    while bytes.get(j).is_some_and(u8::is_ascii_whitespace) {
+                        j += 1;
+                    }
+                    if bytes.get(j) != Some(&b':') {
+                        continue;
+                    }
+                    j += 1;
+                    while bytes.get(j).is_some_and(u8::is_ascii_whitespace) {
+                        j += 1;
+                    }
+                    let start = j;
+                    while bytes.get(j).is_some_and(u8::is_ascii_digit) {
+                        j += 1;
+                    }
+                    if let Ok(size) = query[start..j].parse() {
+                        sizes.push((start..j, size));
+                    }
+                }
+            }
+        }
+        i += 1;
+    }
+    sizes
+}
+
+/// The most nodes `query` could return, counted the way GitHub does: every connection's page
+/// size times the page sizes of the connections it is nested in. Page sizes given as variables
+/// count as 1.
+pub fn estimate_nodes(query: &str) -> u64 {
+    let sizes = page_sizes(query);
+    let mut sizes = sizes.iter().peekable();
+    // Multiplier of each open selection set, outermost first.
+    let mut multipliers = vec![1u64];
+    let mut pending = None;
+    let mut nodes = 0u64;
+    let mut parentheses = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in query.char_indices() {
+        if let Some((range, size)) = sizes.peek() {
+            if range.start == i {
+                pending = Some(*size);
+                sizes.next();
+            }
+        }
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' => parentheses += 1,
+            ')' => parentheses -= 1,
+            // Braces inside arguments are input objects such as `orderBy: {...}`.
+            '{' if parentheses == 0 => {
+                let parent = *multipliers.last().unwrap_or(&1);
+                let multiplier = match pending.take() {
+                    Some(size) => {
+                        let multiplier = parent.saturating_mul(size);
+                        nodes = nodes.saturating_add(multiplier);
+                        multiplier
+                    }
+                    None => parent,
+                };
+                multipliers.push(multiplier);
+            }
+            '}' if parentheses == 0 => {
+                multipliers.pop();
+            }
+            _ => {}
+        }
+    }
+    nodes
+}
+
+/// `query` with its largest page sizes halved until it is within [`MAX_NODES`], and a note on
+/// what was cut if anything was. Fewer items then come back than asked for, which beats the
+/// query failing.
+pub fn fit_node_limit(query: &str) -> (String, Option<String>) {
+    let estimate = estimate_nodes(query);
+    if estimate <= MAX_NODES {
+        return (query.to_string(), None);
+    }
+    let mut query = query.to_string();
+    while estimate_nodes(&query) > MAX_NODES {
+        let Some((range, size)) = page_sizes(&query)
+            .into_iter()
+            .filter(|(_, size)| *size > 1)
+            .max_by_key(|(_, size)| *size)
+        else {
+            break;
+        };
+        query.replace_range(range, &(size / 2).to_string());
+    }
+    let sizes: Vec<String> = page_sizes(&query)
+        .iter()
+        .map(|(_, size)| size.to_string())
+        .collect();
+    let note = format!(
+        "query could return {} nodes, more than GitHub's {}; page sizes cut to {}",
+        estimate,
+        MAX_NODES,
+        sizes.join(", ")
+    );
+    (query, Some(note))
+}
+
+/// `query` asking for its own `rateLimit` cost too, and whether that was added (rather than
+/// already asked for by the query itself). The field goes into the operation's selection set,
+/// never into a fragment defined ahead of it.
+pub fn with_rate_limit(query: &str) -> (String, bool) {
+    if query.contains("rateLimit") {
+        return (query.to_string(), false);
+    }
+    let mut parentheses = 0;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    // Where the top-level definition being read starts.
+    let mut definition = 0;
+    for (i, c) in query.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' => parentheses += 1,
+            ')' => parentheses -= 1,
+            '{' if parentheses == 0 => {
+                if depth == 0 && !query[definition..i].trim_start().starts_with("fragment") {
+                    let mut query = query.to_string();
+                    query.insert_str(i + 1, " rateLimit { cost remaining }");
+                    return (query, true);
+                }
+                depth += 1;
+            }
+            '}' if parentheses == 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    definition = i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    (query.to_string(), false)
+}
@@ -0,0 +1,117 @@
+//! Text files such as a base config or header art that can live outside the repository: at a
+//! URL, or at `github:owner/repo/path@ref` in another repository. Pinning a SHA-256 checksum
+//! makes a run fail instead of silently picking up a changed file.
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Reads a remote source: its URL or `github:` path in, its text out.
+pub type Fetcher = fn(&str) -> Result<String, String>;
+
+static FETCHER: OnceLock<Fetcher> = OnceLock::new();
+
+const GITHUB_PREFIX: &str = "github:";
+
+/// Whether `source` is a URL or a `github:` path rather than a local file.
+pub fn is_remote(source: &str) -> bool {
+    source.starts_with("https://")
+        || source.starts_with("http://")
+        || source.starts_with(GITHUB_PREFIX)
+}
+
+/// The URL `source` is fetched from: `github:owner/repo/path@ref` becomes the raw file URL,
+/// on the default branch when there is no `@ref`. Other sources are returned as they are.
+pub fn resolve_url(source: &str) -> String {
+    let Some(path) = source.strip_prefix(GITHUB_PREFIX) else {
+        return source.to_string();
+    };
+    let (path, reference) = path.rsplit_once('@').unwrap_or((path, "HEAD"));
+    let mut parts = path.splitn(3, '/');
+    let (owner, repo, file) = (
+        parts.next().unwrap_or(""),
+        parts.next().unwrap_or(""),
+        parts.next().unwrap_or(""),
+    );
+    format!(
+        "https://raw.githubusercontent.com/{}/{}/{}/{}",
+        owner, repo, reference, file
+    )
+}
+
+/// `relative` resolved against the directory of `source`, whether that is a URL or a path.
+pub fn join_source(source: &str, relative: &str) -> String {
+    if is_remote(relative) || Path::new(relative).is_absolute() {
+        return relative.to_string();
+    }
+    if is_remote(source) {
+        let url = resolve_url(source);
+        let directory = url.rsplit_once('/').map_or(url.as_str(), |(dir, _)| dir);
+        return format!("{}/{}", directory, relative);
+    }
+    Path::new(source)
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(relative)
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn sha256_hex(contents: &str) -> String {
+    Sha256::digest(contents.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn verify(source: &str, contents: &str, sha256: Option<&str>) -> Result<(), String> {
+    let Some(expected) = sha256 else {
+        return Ok(());
+    };
+    let actual = sha256_hex(contents);
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} doesn't match its pinned checksum (expected sha256 {}, got {})",
+            source, expected, actual
+        ))
+    }
+}
+
+/// Makes `fetch` the way remote sources are read, e.g. `profile_github::remote::fetch`. Only the
+/// first call counts; until then a remote source is an error.
+pub fn install_fetcher(fetch: Fetcher) -> Result<(), String> {
+    FETCHER
+        .set(fetch)
+        .map_err(|_| "a remote fetcher is already installed".to_string())
+}
+
+fn fetch(source: &str) -> Result<String, String> {
+    match FETCHER.get() {
+        Some(fetch) => fetch(source),
+        None => Err(format!(
+            "{} is remote, and this build can't fetch it",
+            source
+        )),
+    }
+}
+
+/// Reads a local file or fetches a remote one, checking it against `sha256` when given. A plain
+/// `http://` URL could be tampered with on the way, so it is only read with a pin.
+pub fn read_source(source: &str, sha256: Option<&str>) -> Result<String, String> {
+    if source.starts_with("http://") && sha256.is_none() {
+        return Err(format!(
+            "{} is fetched over plain HTTP; use https:// or pin its sha256 checksum",
+            source
+        ));
+    }
+    let contents = if is_remote(source) {
+        fetch(source)?
+    } else {
+        fs::read_to_string(source).map_err(|e| format!("{}: {}", source, e))?
+    };
+    verify(source, &contents, sha256)?;
+    Ok(contents)
+}
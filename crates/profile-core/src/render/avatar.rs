@@ -0,0 +1,66 @@
+use crate::config::{HeatmapTheme, ValueStyle};
+use crate::sanitize::html_escape;
+
+const FRAME_SIZE: u32 = 128;
+const RING_WIDTH: u32 = 8;
+const FAVICON_SIZE: u32 = 64;
+/// A streak this long gets the brightest heatmap colour.
+const FULL_STREAK_DAYS: u64 = 30;
+
+const BACKGROUND: &str = "#161b22";
+const TEXT_COLOR: &str = "#e6edf3";
+
+/// A ring to lay over a round avatar, coloured like a heatmap cell by the current streak, with
+/// the streak length in a pill at the bottom. The middle is transparent.
+pub fn render_avatar_frame(streak: u64, theme: &HeatmapTheme) -> String {
+    let center = FRAME_SIZE / 2;
+    let color = html_escape(theme.color(theme.level(streak, FULL_STREAK_DAYS)));
+    let label = format!("{}d", streak);
+    let pill_width = 12 + 8 * label.len() as u32;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\" font-family=\"monospace\" font-size=\"13\">\n",
+        FRAME_SIZE
+    );
+    svg += &format!(
+        "  <circle cx=\"{0}\" cy=\"{0}\" r=\"{1}\" stroke=\"{2}\" stroke-width=\"{3}\" fill=\"none\"><title>{4}-day streak</title></circle>\n",
+        center,
+        center - RING_WIDTH / 2,
+        color,
+        RING_WIDTH,
+        streak
+    );
+    svg += &format!(
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"18\" rx=\"9\" fill=\"{}\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+        center - pill_width / 2,
+        FRAME_SIZE - 20,
+        pill_width,
+        BACKGROUND,
+        color
+    );
+    svg += &format!(
+        "  <text x=\"{}\" y=\"{}\" fill=\"{}\" text-anchor=\"middle\">{}</text>\n</svg>\n",
+        center,
+        FRAME_SIZE - 7,
+        TEXT_COLOR,
+        label
+    );
+    svg
+}
+
+/// A square icon with the follower count in compact form ("1.2k"), in the brightest heatmap
+/// colour so it matches the rest of the profile.
+pub fn render_favicon(followers: u64, theme: &HeatmapTheme) -> String {
+    let count = ValueStyle::Compact.format(followers);
+    let font_size = if count.len() <= 3 { 28 } else { 20 };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\" font-family=\"monospace\" font-weight=\"bold\">\n  <rect width=\"{0}\" height=\"{0}\" rx=\"12\" fill=\"{1}\"/>\n  <text x=\"{2}\" y=\"{2}\" dy=\"0.35em\" font-size=\"{3}\" fill=\"{4}\" text-anchor=\"middle\"><title>{5} followers</title>{6}</text>\n</svg>\n",
+        FAVICON_SIZE,
+        BACKGROUND,
+        FAVICON_SIZE / 2,
+        font_size,
+        html_escape(theme.color(theme.glyphs.len())),
+        followers,
+        count
+    )
+}
@@ -0,0 +1,117 @@
+use crate::sanitize::html_escape;
+use chrono::NaiveDate;
+
+const WIDTH: f64 = 480.0;
+const HEIGHT: f64 = 160.0;
+const PAD_LEFT: f64 = 48.0;
+const PAD_RIGHT: f64 = 12.0;
+const PAD_TOP: f64 = 28.0;
+const PAD_BOTTOM: f64 = 24.0;
+
+const LINE_COLOR: &str = "#58a6ff";
+const AXIS_COLOR: &str = "#30363d";
+const TEXT_COLOR: &str = "#8b949e";
+
+/// Builds a cubic Bézier path through `points` (Catmull-Rom), so the line is smoothed without
+/// overshooting the data as much as a plain spline would.
+fn smooth_path(points: &[(f64, f64)]) -> String {
+    let mut path = format!("M{:.1},{:.1}", points[0].0, points[0].1);
+    for i in 0..points.len() - 1 {
+        let p0 = points[i.saturating_sub(1)];
+        let (p1, p2) = (points[i], points[i + 1]);
+        let p3 = points[(i + 2).min(points.len() - 1)];
+        let c1 = (p1.0 + (p2.0 - p0.0) / 6.0, p1.1 + (p2.1 - p0.1) / 6.0);
+        let c2 = (p2.0 - (p3.0 - p1.0) / 6.0, p2.1 - (p3.1 - p1.1) / 6.0);
+        path += &format!(
+            " C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}",
+            c1.0, c1.1, c2.0, c2.1, p2.0, p2.1
+        );
+    }
+    path
+}
+
+/// Renders a dated series as a standalone SVG line chart with axes and min/max labels.
+pub fn render_line_chart_svg(title: &str, series: &[(NaiveDate, u64)]) -> String {
+    let plot_width = WIDTH - PAD_LEFT - PAD_RIGHT;
+    let plot_height = HEIGHT - PAD_TOP - PAD_BOTTOM;
+    let bottom = PAD_TOP + plot_height;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"monospace\" font-size=\"11\">\n",
+        WIDTH, HEIGHT
+    );
+    svg += &format!(
+        "  <text x=\"{}\" y=\"16\" fill=\"{}\" font-size=\"13\">{}</text>\n",
+        PAD_LEFT,
+        TEXT_COLOR,
+        html_escape(title)
+    );
+    svg += &format!(
+        "  <path d=\"M{0},{1} V{2} H{3}\" stroke=\"{4}\" fill=\"none\"/>\n",
+        PAD_LEFT,
+        PAD_TOP,
+        bottom,
+        PAD_LEFT + plot_width,
+        AXIS_COLOR
+    );
+
+    if series.is_empty() {
+        svg += &format!(
+            "  <text x=\"{}\" y=\"{}\" fill=\"{}\">not enough history yet</text>\n</svg>\n",
+            PAD_LEFT + 8.0,
+            PAD_TOP + plot_height / 2.0,
+            TEXT_COLOR
+        );
+        return svg;
+    }
+
+    let min = series.iter().map(|(_, v)| *v).min().unwrap_or(0);
+    let max = series
+        .iter()
+        .map(|(_, v)| *v)
+        .max()
+        .unwrap_or(0)
+        .max(min + 1);
+    let first_day = series[0].0;
+    let span_days = (series[series.len() - 1].0 - first_day).num_days().max(1) as f64;
+
+    let points: Vec<(f64, f64)> = series
+        .iter()
+        .map(|(date, value)| {
+            let x = PAD_LEFT + (*date - first_day).num_days() as f64 / span_days * plot_width;
+            let y = bottom - (*value - min) as f64 / (max - min) as f64 * plot_height;
+            (x, y)
+        })
+        .collect();
+
+    for (value, y) in [(max, PAD_TOP), (min, bottom)] {
+        svg += &format!(
+            "  <text x=\"{}\" y=\"{:.1}\" fill=\"{}\" text-anchor=\"end\">{}</text>\n",
+            PAD_LEFT - 6.0,
+            y + 4.0,
+            TEXT_COLOR,
+            value
+        );
+    }
+    for (date, x, anchor) in [
+        (first_day, PAD_LEFT, "start"),
+        (series[series.len() - 1].0, PAD_LEFT + plot_width, "end"),
+    ] {
+        svg += &format!(
+            "  <text x=\"{}\" y=\"{}\" fill=\"{}\" text-anchor=\"{}\">{}</text>\n",
+            x,
+            HEIGHT - 6.0,
+            TEXT_COLOR,
+            anchor,
+            date.format("%Y-%m-%d")
+        );
+    }
+
+    svg += &format!(
+        "  <path d=\"{}\" stroke=\"{}\" stroke-width=\"2\" fill=\"none\" stroke-linecap=\"round\"/>\n",
+        smooth_path(&points),
+        LINE_COLOR
+    );
+    svg += "</svg>\n";
+    svg
+}
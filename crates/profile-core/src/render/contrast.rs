@@ -0,0 +1,92 @@
+//! WCAG contrast between the colours a theme writes text in and the backgrounds the SVG and
+//! HTML renderings put it on.
+
+use crate::anomaly::report_anomaly;
+use crate::config::{Config, ContrastPolicy};
+use crate::render::hex_rgb;
+
+/// WCAG AA for normal-sized text.
+pub const MIN_TEXT_CONTRAST: f64 = 4.5;
+/// Page background of the SVG and HTML renderings, which the header art is drawn on.
+const PAGE_BACKGROUND: &str = "#0d1117";
+/// Background of the favicon, whose count is written in the brightest heatmap colour.
+const CARD_BACKGROUND: &str = "#161b22";
+
+fn linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.03928 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Relative luminance of a `#rrggbb` colour, from 0 for black to 1 for white.
+pub fn relative_luminance(color: &str) -> f64 {
+    let (r, g, b) = hex_rgb(color);
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// The WCAG contrast ratio of two colours, from 1 (identical) to 21 (black on white).
+pub fn contrast_ratio(a: &str, b: &str) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// `color` mixed towards white or black, whichever contrasts more with `background`, just
+/// far enough to reach `min`.
+pub fn adjust_contrast(color: &str, background: &str, min: f64) -> String {
+    let target =
+        match contrast_ratio("#ffffff", background) >= contrast_ratio("#000000", background) {
+            true => 255.0,
+            false => 0.0,
+        };
+    let (r, g, b) = hex_rgb(color);
+    let mut mixed = color.to_string();
+    for step in 0..=20 {
+        let t = step as f64 / 20.0;
+        let mix = |channel: u8| (channel as f64 + (target - channel as f64) * t).round() as u8;
+        mixed = format!("#{:02x}{:02x}{:02x}", mix(r), mix(g), mix(b));
+        if contrast_ratio(&mixed, background) >= min {
+            break;
+        }
+    }
+    mixed
+}
+
+fn check(policy: ContrastPolicy, what: &str, color: &mut String, background: &str) {
+    let ratio = contrast_ratio(color, background);
+    if ratio >= MIN_TEXT_CONTRAST {
+        return;
+    }
+    match policy {
+        ContrastPolicy::Warn => report_anomaly(format!(
+            "{} {} has a contrast of {:.1}:1 against {}, below the {}:1 WCAG AA asks for text",
+            what, color, ratio, background, MIN_TEXT_CONTRAST
+        )),
+        ContrastPolicy::Adjust => *color = adjust_contrast(color, background, MIN_TEXT_CONTRAST),
+        ContrastPolicy::Off => {}
+    }
+}
+
+/// Checks the configured text colours against their backgrounds, warning about or adjusting
+/// those below [`MIN_TEXT_CONTRAST`] as `theme.contrast` says. The built-in heatmap palettes
+/// all pass, so only custom `colors` are looked at.
+pub fn check_theme_contrast(config: &mut Config) {
+    let policy = config.theme.contrast;
+    if policy == ContrastPolicy::Off {
+        return;
+    }
+    if let Some(color) = config.header.art_color.as_mut() {
+        check(policy, "Header art colour", color, PAGE_BACKGROUND);
+    }
+    if let Some(color) = config
+        .theme
+        .heatmap
+        .colors
+        .as_mut()
+        .and_then(|colors| colors.last_mut())
+    {
+        check(policy, "Brightest heatmap colour", color, CARD_BACKGROUND);
+    }
+}
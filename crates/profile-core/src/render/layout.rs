@@ -0,0 +1,88 @@
+//! Composes blocks of text lines side by side or on top of each other, padding with spaces so
+//! the pieces line up in a monospace font.
+
+use crate::render::{display_width, pad_end};
+
+/// Columns taken by the widest line.
+pub fn width(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0)
+}
+
+/// `right` placed `gap` columns after the widest line of `left`, starting `offset` lines down.
+/// Trailing spaces are trimmed.
+pub fn beside(left: &[String], right: &[String], gap: usize, offset: usize) -> Vec<String> {
+    let left_width = width(left);
+    let height = left.len().max(right.len() + offset);
+    (0..height)
+        .map(|i| {
+            let left_part = left.get(i).map_or("", String::as_str);
+            let right_part = i
+                .checked_sub(offset)
+                .and_then(|i| right.get(i))
+                .map_or("", String::as_str);
+            let line = format!(
+                "{}{}{}",
+                pad_end(left_part, left_width),
+                " ".repeat(gap),
+                right_part
+            );
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Blocks next to each other, top-aligned and `gap` columns apart.
+pub fn row(blocks: &[Vec<String>], gap: usize) -> Vec<String> {
+    let mut blocks = blocks.iter().filter(|block| !block.is_empty());
+    let Some(first) = blocks.next() else {
+        return Vec::new();
+    };
+    blocks.fold(first.clone(), |lines, block| beside(&lines, block, gap, 0))
+}
+
+/// Blocks in rows no wider than `max_width`, starting a new row whenever the next block would
+/// overflow. A block wider than `max_width` gets a row of its own.
+pub fn wrap(blocks: &[Vec<String>], gap: usize, max_width: usize) -> Vec<String> {
+    let mut rows: Vec<Vec<Vec<String>>> = Vec::new();
+    let mut row_width = 0;
+    for block in blocks.iter().filter(|block| !block.is_empty()) {
+        let block_width = width(block);
+        match rows.last_mut() {
+            Some(current) if row_width + gap + block_width <= max_width => {
+                current.push(block.clone());
+                row_width += gap + block_width;
+            }
+            _ => {
+                rows.push(vec![block.clone()]);
+                row_width = block_width;
+            }
+        }
+    }
+    let rows: Vec<Vec<String>> = rows.iter().map(|blocks| row(blocks, gap)).collect();
+    stack(&rows, 0)
+}
+
+/// Blocks on top of each other with `gap` blank lines between them.
+pub fn stack(blocks: &[Vec<String>], gap: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for block in blocks.iter().filter(|block| !block.is_empty()) {
+        if !lines.is_empty() {
+            lines.extend(std::iter::repeat_n(String::new(), gap));
+        }
+        lines.extend(block.iter().cloned());
+    }
+    lines
+}
+
+/// Every line shifted right so the block sits in the middle of `width` columns.
+pub fn center(lines: &[String], width: usize) -> Vec<String> {
+    let indent = " ".repeat(width.saturating_sub(self::width(lines)) / 2);
+    lines
+        .iter()
+        .map(|line| format!("{}{}", indent, line).trim_end().to_string())
+        .collect()
+}
@@ -0,0 +1,126 @@
+//! Conway's Game of Life on the contribution grid: the first frame is the last year's calendar,
+//! every day with contributions a live cell, and the SVG then plays the generations that follow.
+
+use crate::calendar::ContributionCalendar;
+use crate::config::HeatmapTheme;
+use crate::sanitize::html_escape;
+use chrono::{Datelike, Duration, NaiveDate};
+
+const WEEKS: usize = 53;
+const DAYS: usize = 7;
+const CELL: usize = 10;
+const GAP: usize = 2;
+/// Generations played before the animation starts over from the calendar.
+const GENERATIONS: usize = 40;
+const FRAME_SECONDS: f64 = 0.4;
+
+type Grid = [[bool; DAYS]; WEEKS];
+
+/// The heatmap level of every cell of the calendar grid, the last column being the current
+/// week, laid out like GitHub's: one column per week, Sunday on top.
+fn seed(
+    calendar: &ContributionCalendar,
+    today: NaiveDate,
+    theme: &HeatmapTheme,
+) -> Vec<[usize; DAYS]> {
+    let last_sunday = today - Duration::days(today.weekday().num_days_from_sunday() as i64);
+    let first = last_sunday - Duration::weeks(WEEKS as i64 - 1);
+    let max = calendar
+        .range(first, today)
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0);
+    let mut levels = vec![[0; DAYS]; WEEKS];
+    for (date, count) in calendar.range(first, today) {
+        let day = (*date - first).num_days() as usize;
+        levels[day / DAYS][day % DAYS] = theme.level(*count, max);
+    }
+    levels
+}
+
+/// The next generation, with the grid wrapping around at the edges.
+fn step(grid: &Grid) -> Grid {
+    let mut next = [[false; DAYS]; WEEKS];
+    for week in 0..WEEKS {
+        for day in 0..DAYS {
+            let mut neighbours = 0;
+            for dw in [WEEKS - 1, 0, 1] {
+                for dd in [DAYS - 1, 0, 1] {
+                    if (dw, dd) != (0, 0) && grid[(week + dw) % WEEKS][(day + dd) % DAYS] {
+                        neighbours += 1;
+                    }
+                }
+            }
+            next[week][day] = neighbours == 3 || (grid[week][day] && neighbours == 2);
+        }
+    }
+    next
+}
+
+/// An animated SVG of the grid's generations. Seed cells keep their heatmap colour in the first
+/// frame; after that a cell is either alive (the brightest colour) or not. The animation stops
+/// early once the grid dies out or settles.
+pub fn render_life_svg(
+    calendar: &ContributionCalendar,
+    today: NaiveDate,
+    theme: &HeatmapTheme,
+) -> String {
+    let levels = seed(calendar, today, theme);
+    let mut grid: Grid = [[false; DAYS]; WEEKS];
+    for (week, days) in levels.iter().enumerate() {
+        for (day, level) in days.iter().enumerate() {
+            grid[week][day] = *level > 0;
+        }
+    }
+    let mut generations = vec![grid];
+    while generations.len() < GENERATIONS {
+        let next = step(generations.last().unwrap());
+        if generations.contains(&next) {
+            break;
+        }
+        generations.push(next);
+    }
+
+    let color = |level: usize| html_escape(theme.color(level));
+    let (dead, alive) = (color(0), color(theme.glyphs.len()));
+    let width = WEEKS * (CELL + GAP);
+    let height = DAYS * (CELL + GAP);
+    let duration = generations.len() as f64 * FRAME_SECONDS;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        width, height
+    );
+    for week in 0..WEEKS {
+        for day in 0..DAYS {
+            let first = color(levels[week][day]);
+            let rect = format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" rx=\"2\" fill=\"{}\"",
+                week * (CELL + GAP),
+                day * (CELL + GAP),
+                first
+            );
+            if generations[1..].iter().all(|grid| !grid[week][day]) && levels[week][day] == 0 {
+                svg += &rect;
+                svg += "/>\n";
+                continue;
+            }
+            let frames: Vec<&str> = std::iter::once(first.as_str())
+                .chain(generations[1..].iter().map(|grid| {
+                    if grid[week][day] {
+                        alive.as_str()
+                    } else {
+                        dead.as_str()
+                    }
+                }))
+                .collect();
+            svg += &format!(
+                "{}><animate attributeName=\"fill\" values=\"{}\" dur=\"{:.1}s\" calcMode=\"discrete\" repeatCount=\"indefinite\"/></rect>\n",
+                rect,
+                frames.join(";"),
+                duration
+            );
+        }
+    }
+    svg += "</svg>\n";
+    svg
+}
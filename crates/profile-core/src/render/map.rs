@@ -0,0 +1,72 @@
+/// Equirectangular land mask, 5° of longitude per column, from 80°N down to 60°S.
+const WORLD_MAP: [&str; 18] = [
+    "            ######### ##########      ####     ###    ####              ",
+    "   ####################  ########      #################################",
+    "   ##############   ####  ##         ###############################    ",
+    "          ###############         ##############################   #    ",
+    "           ############           ###########  ################ #       ",
+    "            #########             ########################## ###        ",
+    "             #######             ###########################            ",
+    "              #### ##            ###############  ########              ",
+    "                  #              #############     #   ###  #           ",
+    "                    ####         #############          # ###           ",
+    "                    #########         ######           ## ### ####      ",
+    "                    #########         ######             ##   # ##      ",
+    "                     #######          ###### #             #######      ",
+    "                      ######           ####                ########     ",
+    "                      ####              ##                 ##  ###    ##",
+    "                     ###                                         #   ###",
+    "                     ##                                                 ",
+    "                      #                                                 ",
+];
+
+const NORTH_EDGE: f64 = 80.0;
+const SOUTH_EDGE: f64 = -60.0;
+
+const LAND: char = '⣿';
+const SEA: char = '⠀';
+const PIN: char = '◉';
+
+/// Projects a coordinate onto the map grid as (row, column), clamped to the map edges.
+pub fn project(latitude: f64, longitude: f64) -> (usize, usize) {
+    let height = WORLD_MAP.len();
+    let width = WORLD_MAP[0].len();
+
+    let x = (longitude.clamp(-180.0, 180.0) + 180.0) / 360.0;
+    let y = (NORTH_EDGE - latitude.clamp(SOUTH_EDGE, NORTH_EDGE)) / (NORTH_EDGE - SOUTH_EDGE);
+
+    let column = ((x * width as f64) as usize).min(width - 1);
+    let row = ((y * height as f64) as usize).min(height - 1);
+    (row, column)
+}
+
+/// Renders the world map with a pin at the given coordinate.
+pub fn render_world_map(latitude: f64, longitude: f64) -> Vec<String> {
+    let (pin_row, pin_column) = project(latitude, longitude);
+
+    WORLD_MAP
+        .iter()
+        .enumerate()
+        .map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(column, cell)| match cell {
+                    _ if (row, column) == (pin_row, pin_column) => PIN,
+                    '#' => LAND,
+                    _ => SEA,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Formats a coordinate as `48.21°N, 16.37°E`.
+pub fn format_coordinates(latitude: f64, longitude: f64) -> String {
+    format!(
+        "{:.2}°{}, {:.2}°{}",
+        latitude.abs(),
+        if latitude < 0.0 { 'S' } else { 'N' },
+        longitude.abs(),
+        if longitude < 0.0 { 'W' } else { 'E' }
+    )
+}
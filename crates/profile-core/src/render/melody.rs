@@ -0,0 +1,90 @@
+//! The last year of contributions as a short tune: one note per week, higher the busier the
+//! week, and a rest for weeks without any. Written as a mono 16-bit WAV.
+
+use crate::calendar::ContributionCalendar;
+use crate::config::HeatmapTheme;
+use chrono::{Duration, NaiveDate};
+use std::f64::consts::PI;
+
+const WEEKS: i64 = 52;
+const SAMPLE_RATE: u32 = 22_050;
+const NOTE_SECONDS: f64 = 0.16;
+/// Fade in and out over this much of each note, so notes don't click.
+const FADE_SECONDS: f64 = 0.02;
+const VOLUME: f64 = 0.4;
+/// A pentatonic scale from C4 up, in Hz; any two of its notes sound fine next to each other.
+const SCALE: [f64; 10] = [
+    261.63, 293.66, 329.63, 392.00, 440.00, 523.25, 587.33, 659.25, 783.99, 880.00,
+];
+
+/// Contributions per week over the 52 weeks ending `today`, oldest first.
+fn weekly_totals(calendar: &ContributionCalendar, today: NaiveDate) -> Vec<u64> {
+    (0..WEEKS)
+        .rev()
+        .map(|week| {
+            let end = today - Duration::weeks(week);
+            calendar.total(end - Duration::days(6), end)
+        })
+        .collect()
+}
+
+/// The frequency of each week's note, `None` for a rest. Heatmap levels pick the notes, spread
+/// over the scale however many levels the theme has.
+fn notes(weeks: &[u64], theme: &HeatmapTheme) -> Vec<Option<f64>> {
+    let max = weeks.iter().copied().max().unwrap_or(0);
+    let levels = theme.glyphs.len();
+    weeks
+        .iter()
+        .map(|count| match theme.level(*count, max) {
+            0 => None,
+            level => Some(SCALE[(level * SCALE.len()).div_ceil(levels) - 1]),
+        })
+        .collect()
+}
+
+fn wav(samples: &[i16]) -> Vec<u8> {
+    let data_length = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_length as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_length).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_length.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+pub fn render_melody_wav(
+    calendar: &ContributionCalendar,
+    today: NaiveDate,
+    theme: &HeatmapTheme,
+) -> Vec<u8> {
+    let note_samples = (NOTE_SECONDS * SAMPLE_RATE as f64) as usize;
+    let fade_samples = FADE_SECONDS * SAMPLE_RATE as f64;
+    let mut samples = Vec::new();
+    for note in notes(&weekly_totals(calendar, today), theme) {
+        for i in 0..note_samples {
+            let value = match note {
+                Some(frequency) => {
+                    let envelope = (i as f64 / fade_samples)
+                        .min((note_samples - i) as f64 / fade_samples)
+                        .min(1.0);
+                    let time = i as f64 / SAMPLE_RATE as f64;
+                    (2.0 * PI * frequency * time).sin() * envelope * VOLUME
+                }
+                None => 0.0,
+            };
+            samples.push((value * i16::MAX as f64) as i16);
+        }
+    }
+    wav(&samples)
+}
@@ -0,0 +1,166 @@
+#[cfg(feature = "svg")]
+pub mod avatar;
+#[cfg(feature = "svg")]
+pub mod chart;
+pub mod contrast;
+pub mod layout;
+#[cfg(feature = "svg")]
+pub mod life;
+pub mod map;
+#[cfg(feature = "audio")]
+pub mod melody;
+pub mod odometer;
+pub mod qr;
+
+use crate::config::HeatmapTheme;
+
+/// Where rendered images (SVG charts and heatmaps) are written for the README to embed,
+/// relative to the README.
+pub const ASSETS_DIR: &str = "assets";
+
+/// A file written next to the README, such as an image for it to embed; renderers return these
+/// and the caller writes them out.
+pub struct Asset {
+    /// Relative to the README, so it is also the link the README embeds it by.
+    pub path: String,
+    pub contents: Vec<u8>,
+}
+
+pub fn create_ascii_bar(percentage: f64, width: usize) -> String {
+    let filled_width = ((percentage / 100.0) * width as f64).round() as usize;
+    let mut bar = String::new();
+
+    for i in 0..width {
+        let char = match i.cmp(&filled_width) {
+            std::cmp::Ordering::Less => '█',    // Filled portion
+            std::cmp::Ordering::Equal => '▓',   // Transition
+            std::cmp::Ordering::Greater => '░', // Unfilled portion
+        };
+        bar.push(char);
+    }
+
+    format!("[{}]", bar)
+}
+
+/// The channels of a `#rrggbb` colour; malformed ones come out black.
+pub fn hex_rgb(hex: &str) -> (u8, u8, u8) {
+    let channel = |range| u8::from_str_radix(hex.get(range).unwrap_or("0"), 16).unwrap_or(0);
+    (channel(1..3), channel(3..5), channel(5..7))
+}
+
+/// One block character per value, scaled between the series minimum and maximum.
+pub fn create_sparkline(values: &[u64]) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    if max == min {
+        return LEVELS[0].to_string().repeat(values.len());
+    }
+    values
+        .iter()
+        .map(|value| LEVELS[((value - min) * (LEVELS.len() as u64 - 1) / (max - min)) as usize])
+        .collect()
+}
+
+/// Picks the theme's glyph for `value` relative to `max`.
+pub fn heatmap_glyph(value: u64, max: u64, theme: &HeatmapTheme) -> char {
+    match theme.level(value, max) {
+        0 => theme.empty,
+        level => theme.glyphs[level - 1],
+    }
+}
+
+/// Columns `c` takes in a terminal or monospace font: two for emoji and East Asian wide
+/// characters, none for joiners, variation selectors and combining marks.
+fn char_width(c: char) -> usize {
+    match c {
+        '\u{0300}'..='\u{036F}' | '\u{200B}'..='\u{200F}' | '\u{FE00}'..='\u{FE0F}' => 0,
+        '\u{1100}'..='\u{115F}'
+        | '\u{231A}'..='\u{231B}'
+        | '\u{23E9}'..='\u{23EC}'
+        | '\u{23F0}'
+        | '\u{23F3}'
+        | '\u{25FD}'..='\u{25FE}'
+        | '\u{2614}'..='\u{2615}'
+        | '\u{2648}'..='\u{2653}'
+        | '\u{26A1}'
+        | '\u{26AA}'..='\u{26AB}'
+        | '\u{26BD}'..='\u{26BE}'
+        | '\u{26C4}'..='\u{26C5}'
+        | '\u{26D4}'
+        | '\u{26EA}'
+        | '\u{26F2}'..='\u{26F3}'
+        | '\u{26F5}'
+        | '\u{26FA}'
+        | '\u{26FD}'
+        | '\u{2705}'
+        | '\u{270A}'..='\u{270B}'
+        | '\u{2728}'
+        | '\u{274C}'
+        | '\u{2753}'..='\u{2755}'
+        | '\u{2757}'
+        | '\u{2B1B}'..='\u{2B1C}'
+        | '\u{2B50}'
+        | '\u{2B55}'
+        | '\u{2E80}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}'
+        | '\u{1F300}'..='\u{1F64F}'
+        | '\u{1F680}'..='\u{1F6FF}'
+        | '\u{1F900}'..='\u{1F9FF}'
+        | '\u{1FA70}'..='\u{1FAFF}' => 2,
+        _ => 1,
+    }
+}
+
+/// Columns `text` takes in a monospace font, so boxes around emoji still line up.
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// `text` followed by spaces up to `width` columns.
+pub fn pad_end(text: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        text,
+        " ".repeat(width.saturating_sub(display_width(text)))
+    )
+}
+
+pub fn create_ascii_badge(label: &str, value: &str, width: usize) -> String {
+    let total_width = width.max(display_width(label) + display_width(value) + 4);
+    let label_width = display_width(label) + 2;
+    let value_width = total_width - label_width;
+
+    let top_bottom = "─".repeat(total_width);
+    let label_part = format!(" {}", pad_end(label, label_width - 2));
+    let value_part = format!(" {} ", pad_end(value, value_width - 2));
+
+    format!(
+        "╭{0}╮\n│{1}│{2}│\n╰{0}╯",
+        top_bottom, label_part, value_part
+    )
+}
+
+pub fn create_ascii_box(title: &str, lines: &[String]) -> String {
+    let inner_width = lines
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0)
+        .max(display_width(title) + 2);
+
+    let title_part = format!("─ {} ", title);
+    let mut output = format!(
+        "╭{}{}╮\n",
+        title_part,
+        "─".repeat(inner_width + 2 - display_width(&title_part))
+    );
+    for line in lines {
+        output += &format!("│ {} │\n", pad_end(line, inner_width));
+    }
+    output += &format!("╰{}╯", "─".repeat(inner_width + 2));
+    output
+}
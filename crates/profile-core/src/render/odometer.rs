@@ -0,0 +1,126 @@
+//! The stats that changed since the last run as an animated SVG odometer: each digit rolls from
+//! the old value to the new one, small enough to post alongside a "my profile changed" note.
+
+// Without `svg` only the list of changes is built; the animation's constants stay next to it.
+#![cfg_attr(not(feature = "svg"), allow(dead_code))]
+
+use crate::history::{metric_noun, Snapshot};
+
+/// Metrics shown, in this order, when they changed.
+const METRICS: [&str; 6] = [
+    "followers",
+    "total_stars",
+    "total_commits",
+    "total_prs",
+    "total_issues",
+    "repos_owned",
+];
+
+const ROW: usize = 28;
+const DIGIT: usize = 13;
+const LABEL_WIDTH: usize = 120;
+const PADDING: usize = 10;
+/// The old value shows, the digits roll, then the new value holds; fractions of `SECONDS`.
+const KEY_TIMES: &str = "0;0.25;0.5;1";
+const SECONDS: u32 = 4;
+
+const BACKGROUND: &str = "#0d1117";
+const TEXT_COLOR: &str = "#e6edf3";
+const LABEL_COLOR: &str = "#8b949e";
+const UP_COLOR: &str = "#3fb950";
+const DOWN_COLOR: &str = "#f85149";
+
+/// `(metric, before, after)` for every tracked metric that changed and both snapshots know.
+pub fn stat_changes(previous: &Snapshot, current: &Snapshot) -> Vec<(&'static str, u64, u64)> {
+    METRICS
+        .iter()
+        .filter_map(|metric| {
+            let (before, after) = (previous.get(metric)?, current.get(metric)?);
+            (before != after).then_some((*metric, before, after))
+        })
+        .collect()
+}
+
+/// "stars 120 → 125, commits 980 → 1004", for the notification text.
+pub fn describe_changes(changes: &[(&str, u64, u64)]) -> String {
+    changes
+        .iter()
+        .map(|(metric, before, after)| format!("{} {} → {}", metric_noun(metric), before, after))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Position of a digit (or the blank left of a shorter number) on the odometer strip.
+fn strip_index(digit: char) -> usize {
+    digit.to_digit(10).map_or(0, |d| d as usize + 1)
+}
+
+#[cfg(feature = "svg")]
+pub fn render_odometer_svg(changes: &[(&str, u64, u64)]) -> String {
+    let digits = changes
+        .iter()
+        .map(|(_, before, after)| before.max(after).to_string().len())
+        .max()
+        .unwrap_or(1);
+    let width = LABEL_WIDTH + digits * DIGIT + 80 + 2 * PADDING;
+    let height = changes.len() * ROW + 2 * PADDING;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"monospace\" font-size=\"18\">\n  <rect width=\"{0}\" height=\"{1}\" rx=\"6\" fill=\"{2}\"/>\n",
+        width, height, BACKGROUND
+    );
+    for (row, (metric, before, after)) in changes.iter().enumerate() {
+        let top = PADDING + row * ROW;
+        let baseline = top + ROW - 8;
+        svg += &format!(
+            "  <clipPath id=\"row{}\"><rect x=\"0\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>\n",
+            row, top, width, ROW
+        );
+        svg += &format!(
+            "  <text x=\"{}\" y=\"{}\" fill=\"{}\" font-size=\"14\">{}</text>\n",
+            PADDING,
+            baseline,
+            LABEL_COLOR,
+            metric_noun(metric)
+        );
+
+        let (old, new) = (
+            format!("{:>digits$}", before),
+            format!("{:>digits$}", after),
+        );
+        svg += &format!("  <g clip-path=\"url(#row{})\">\n", row);
+        for (column, (old_digit, new_digit)) in old.chars().zip(new.chars()).enumerate() {
+            let from = strip_index(old_digit) * ROW;
+            let to = strip_index(new_digit) * ROW;
+            svg += &format!(
+                "    <g><animateTransform attributeName=\"transform\" type=\"translate\" values=\"0 -{0};0 -{0};0 -{1};0 -{1}\" keyTimes=\"{2}\" dur=\"{3}s\" repeatCount=\"indefinite\"/>\n",
+                from, to, KEY_TIMES, SECONDS
+            );
+            let x = PADDING + LABEL_WIDTH + column * DIGIT;
+            for (index, glyph) in std::iter::once(' ').chain('0'..='9').enumerate() {
+                if glyph != ' ' {
+                    svg += &format!(
+                        "      <text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>\n",
+                        x,
+                        baseline + index * ROW,
+                        TEXT_COLOR,
+                        glyph
+                    );
+                }
+            }
+            svg += "    </g>\n";
+        }
+        svg += "  </g>\n";
+
+        let delta = *after as i64 - *before as i64;
+        svg += &format!(
+            "  <text x=\"{}\" y=\"{}\" fill=\"{}\" font-size=\"14\">{:+}</text>\n",
+            PADDING + LABEL_WIDTH + digits * DIGIT + 12,
+            baseline,
+            if delta > 0 { UP_COLOR } else { DOWN_COLOR },
+            delta
+        );
+    }
+    svg += "</svg>\n";
+    svg
+}
@@ -0,0 +1,39 @@
+use qrcode::{Color, QrCode};
+
+/// Light modules kept around the code so scanners can find its edges.
+const QUIET_ZONE: usize = 2;
+
+/// Encodes `data` as a QR code drawn with half-block characters, two module rows per line.
+///
+/// With `invert`, dark modules are left blank instead, which scans correctly on dark themes.
+pub fn render_qr(data: &str, invert: bool) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let code = QrCode::new(data.as_bytes())?;
+    let width = code.width();
+    let colors = code.to_colors();
+
+    let size = width + 2 * QUIET_ZONE;
+    let is_dark = |x: usize, y: usize| -> bool {
+        let inside = (QUIET_ZONE..QUIET_ZONE + width).contains(&x)
+            && (QUIET_ZONE..QUIET_ZONE + width).contains(&y);
+        let dark = inside && colors[(y - QUIET_ZONE) * width + (x - QUIET_ZONE)] == Color::Dark;
+        dark != invert
+    };
+
+    let lines = (0..size)
+        .step_by(2)
+        .map(|y| {
+            (0..size)
+                .map(
+                    |x| match (is_dark(x, y), y + 1 < size && is_dark(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                )
+                .collect()
+        })
+        .collect();
+
+    Ok(lines)
+}
@@ -0,0 +1,87 @@
+//! Layout changes that depend on the data, so a new account shows a shorter profile instead of
+//! badges and tables full of zeros.
+
+use crate::anomaly::report_anomaly;
+use crate::config::{HeaderBadge, LayoutConfig, LayoutRule};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Metrics a rule can test that come straight from the stats query.
+const STATS_METRICS: [&str; 6] = [
+    "total_stars",
+    "total_commits",
+    "total_prs",
+    "total_issues",
+    "repos_owned",
+    "contributed_to",
+];
+
+/// Everything a rule can test: the stats query, the follower count and the number of rows in
+/// the language list.
+pub fn rule_metrics(
+    stats: &Value,
+    followers: u64,
+    languages: &[(String, f64)],
+) -> BTreeMap<&'static str, f64> {
+    let mut metrics: BTreeMap<&'static str, f64> = STATS_METRICS
+        .into_iter()
+        .map(|key| (key, stats[key].as_f64().unwrap_or(0.0)))
+        .collect();
+    metrics.insert("followers", followers as f64);
+    metrics.insert("languages", languages.len() as f64);
+    metrics
+}
+
+/// The combined effect of the rules that matched.
+#[derive(Debug, Default)]
+pub struct LayoutChanges {
+    pub hidden_badges: Vec<HeaderBadge>,
+    pub hidden_sections: Vec<String>,
+    pub compact_languages: bool,
+}
+
+impl LayoutChanges {
+    /// `layout` with the sections the rules hide hidden as well.
+    pub fn apply(&self, layout: &LayoutConfig) -> LayoutConfig {
+        let mut layout = layout.clone();
+        layout.hidden.extend(self.hidden_sections.iter().cloned());
+        layout
+    }
+}
+
+fn matches(rule: &LayoutRule, value: f64) -> bool {
+    rule.below.is_none_or(|below| value < below)
+        && rule.at_least.is_none_or(|at_least| value >= at_least)
+}
+
+/// Evaluates `rules` against `metrics`. Rules on metrics that don't exist, or without a
+/// threshold, are reported and skipped rather than failing the run.
+pub fn apply_rules(rules: &[LayoutRule], metrics: &BTreeMap<&str, f64>) -> LayoutChanges {
+    let mut changes = LayoutChanges::default();
+    for rule in rules {
+        let Some(&value) = metrics.get(rule.metric.as_str()) else {
+            report_anomaly(format!(
+                "Ignoring the rule on unknown metric `{}`; known metrics are {}",
+                rule.metric,
+                metrics.keys().copied().collect::<Vec<_>>().join(", ")
+            ));
+            continue;
+        };
+        if rule.below.is_none() && rule.at_least.is_none() {
+            report_anomaly(format!(
+                "Ignoring the rule on `{}`: it needs `below` or `at_least`",
+                rule.metric
+            ));
+            continue;
+        }
+        if !matches(rule, value) {
+            continue;
+        }
+        changes.hidden_badges.extend(&rule.hide_badges);
+        changes
+            .hidden_sections
+            .extend(rule.hide_sections.iter().cloned());
+        changes.compact_languages |= rule.compact_languages;
+    }
+    changes
+}
@@ -0,0 +1,62 @@
+//! Picking which repositories get analyses that cost a request (or more) per repository, so
+//! users with hundreds of repositories spend a bounded number of requests on them.
+
+use crate::config::{SampleStrategy, SamplingConfig};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+fn stars(repo: &Value) -> u64 {
+    repo["stargazers_count"].as_u64().unwrap_or(0)
+}
+
+fn name(repo: &Value) -> &str {
+    repo["name"].as_str().unwrap_or("")
+}
+
+/// Most starred first, ties by name so the sample is the same from run to run.
+fn by_stars(repos: &mut [Value]) {
+    repos.sort_by(|a, b| stars(b).cmp(&stars(a)).then_with(|| name(a).cmp(name(b))));
+}
+
+/// At most `config.repos` of `repos` (REST repository objects), chosen by `config.strategy`.
+pub fn sample_repos(mut repos: Vec<Value>, config: &SamplingConfig) -> Vec<Value> {
+    match config.strategy {
+        SampleStrategy::Stars => by_stars(&mut repos),
+        SampleStrategy::Recent => {
+            // RFC 3339 timestamps in UTC sort correctly as strings.
+            repos.sort_by(|a, b| {
+                b["pushed_at"]
+                    .as_str()
+                    .cmp(&a["pushed_at"].as_str())
+                    .then_with(|| name(a).cmp(name(b)))
+            });
+        }
+        SampleStrategy::Stratified => {
+            // The most starred repo of each primary language in turn, so a language used in a
+            // few small repos is still represented next to the one used everywhere.
+            let mut strata: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+            for repo in repos {
+                let language = repo["language"].as_str().unwrap_or("").to_string();
+                strata.entry(language).or_default().push(repo);
+            }
+            let mut strata: Vec<Vec<Value>> = strata.into_values().collect();
+            for stratum in &mut strata {
+                by_stars(stratum);
+                stratum.reverse();
+            }
+            strata.sort_by_key(|stratum| std::cmp::Reverse(stratum.last().map(stars)));
+
+            let mut sample = Vec::new();
+            while sample.len() < config.repos && strata.iter().any(|s| !s.is_empty()) {
+                for stratum in &mut strata {
+                    if sample.len() < config.repos {
+                        sample.extend(stratum.pop());
+                    }
+                }
+            }
+            return sample;
+        }
+    }
+    repos.truncate(config.repos);
+    repos
+}
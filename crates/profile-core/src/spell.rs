@@ -0,0 +1,103 @@
+//! Spell-checks the prose that ends up in the README without anyone proofreading it: the header
+//! and footer quotes, custom widget output and release notes pulled from upstream.
+
+use crate::config::SpellingConfig;
+use crate::remote::read_source;
+use std::collections::HashSet;
+use std::fs;
+
+/// Shorter words are mostly abbreviations and units, which no word list covers well.
+const MIN_WORD_LENGTH: usize = 3;
+
+/// Lowercase words considered correctly spelled.
+pub struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// The system word list at `config.dictionary`, plus the user's `words` and the
+    /// `user_dictionary` file (one word per line, `#` starts a comment).
+    pub fn load(config: &SpellingConfig) -> Result<Self, String> {
+        let system = fs::read_to_string(&config.dictionary)
+            .map_err(|e| format!("{}: {}", config.dictionary, e))?;
+        let user = match &config.user_dictionary {
+            Some(source) => read_source(source, None)?,
+            None => String::new(),
+        };
+        let words = system
+            .lines()
+            .chain(
+                user.lines()
+                    .map(|line| line.split('#').next().unwrap_or("")),
+            )
+            .chain(config.words.iter().map(String::as_str))
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        Ok(Dictionary { words })
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        let word = word
+            .strip_suffix("'s")
+            .or_else(|| word.strip_suffix('\''))
+            .unwrap_or(&word);
+        self.words.contains(word)
+    }
+}
+
+/// Whether `word` looks like a name or identifier rather than prose: acronyms, camelCase,
+/// anything with a digit or non-ASCII letters.
+fn is_checkable(word: &str) -> bool {
+    word.chars().count() >= MIN_WORD_LENGTH
+        && word.chars().all(|c| c.is_ascii_alphabetic() || c == '\'')
+        && !word.chars().skip(1).any(|c| c.is_ascii_uppercase())
+}
+
+/// The words of `text` outside HTML tags, URLs and inline code.
+fn words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut in_tag = false;
+    let mut in_code = false;
+    for token in text.split_whitespace() {
+        if token.contains("://") || token.starts_with("www.") {
+            continue;
+        }
+        let mut start = None;
+        for (index, c) in token.char_indices() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                '`' => in_code = !in_code,
+                _ => {}
+            }
+            let part_of_word = !in_tag && !in_code && (c.is_alphanumeric() || c == '\'');
+            match (part_of_word, start) {
+                (true, None) => start = Some(index),
+                (false, Some(begin)) => {
+                    words.push(&token[begin..index]);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(begin) = start {
+            words.push(&token[begin..]);
+        }
+    }
+    words
+}
+
+/// The distinct words of `text` missing from `dictionary`, in order of first appearance.
+pub fn misspellings(text: &str, dictionary: &Dictionary) -> Vec<String> {
+    let mut misspelled: Vec<String> = Vec::new();
+    for word in words(text) {
+        let word = word.trim_matches('\'');
+        if is_checkable(word) && !dictionary.contains(word) && !misspelled.iter().any(|w| w == word)
+        {
+            misspelled.push(word.to_string());
+        }
+    }
+    misspelled
+}
@@ -0,0 +1,81 @@
+use crate::anomaly::report_anomaly;
+use crate::document::{Block, CalloutKind};
+use crate::sanitize::html_escape;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Manually maintained availability, stored in `status.toml` and edited via `status set`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Status {
+    pub state: StatusState,
+    pub message: Option<String>,
+    /// Last day the status is shown; it disappears from the README afterwards.
+    pub until: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusState {
+    Busy,
+    OpenToWork,
+    OnVacation,
+}
+
+impl StatusState {
+    pub fn parse(state: &str) -> Option<Self> {
+        match state {
+            "busy" => Some(StatusState::Busy),
+            "open-to-work" => Some(StatusState::OpenToWork),
+            "on-vacation" => Some(StatusState::OnVacation),
+            _ => None,
+        }
+    }
+}
+
+pub fn load_status(path: &str) -> Option<Status> {
+    let contents = fs::read_to_string(path).ok()?;
+    match toml::from_str(&contents) {
+        Ok(status) => Some(status),
+        Err(e) => {
+            report_anomaly(format!("Ignoring unreadable {}: {}", path, e));
+            None
+        }
+    }
+}
+
+pub fn save_status(path: &str, status: &Status) {
+    let contents = toml::to_string(status).expect("Failed to serialize status");
+    fs::write(path, contents).expect("Failed to write status file");
+}
+
+pub fn clear_status(path: &str) {
+    if fs::metadata(path).is_ok() {
+        fs::remove_file(path).expect("Failed to remove status file");
+    }
+}
+
+/// Renders the status as a GitHub alert, or nothing once it has expired.
+pub fn format_status(status: &Status, today: NaiveDate) -> Option<Block> {
+    if status.until.is_some_and(|until| until < today) {
+        return None;
+    }
+
+    let (kind, headline) = match status.state {
+        StatusState::Busy => (CalloutKind::Important, "🔴 Busy"),
+        StatusState::OpenToWork => (CalloutKind::Tip, "🟢 Open to work"),
+        StatusState::OnVacation => (CalloutKind::Note, "🌴 On vacation"),
+    };
+
+    let mut line = format!("<b>{}</b>", headline);
+    if let Some(message) = &status.message {
+        line += &format!(" - {}", html_escape(message));
+    }
+    if let Some(until) = status.until {
+        line += &format!(" (until {})", until.format("%Y-%m-%d"));
+    }
+    Some(Block::Callout {
+        kind,
+        blocks: vec![Block::text(line)],
+    })
+}
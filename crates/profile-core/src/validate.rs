@@ -0,0 +1,89 @@
+//! Sanity checks on fetched data before it is rendered. GitHub now and then answers with
+//! partial or inconsistent data, which would otherwise show up as zeros, NaN bars or events
+//! dated next year; each problem is reported as an anomaly, so `--strict` refuses to publish it.
+
+use crate::anomaly::report_anomaly;
+use crate::calendar::ContributionCalendar;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde_json::Value;
+
+/// Nothing on GitHub predates its launch.
+fn github_launch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2008, 2, 8).unwrap()
+}
+
+/// How far a percentage total may drift from 100 through rounding before it counts.
+const PERCENT_TOLERANCE: f64 = 0.5;
+
+/// `fields` of `object` that are missing or not non-negative integers.
+pub fn check_counts(what: &str, object: &Value, fields: &[&str]) -> Vec<String> {
+    fields
+        .iter()
+        .filter(|field| object[**field].as_u64().is_none())
+        .map(|field| match &object[*field] {
+            Value::Null => format!("{} has no `{}`", what, field),
+            value => format!("{} has `{}` = {}, not a count", what, field, value),
+        })
+        .collect()
+}
+
+/// Percentages outside 0 to 100, or a non-empty list that doesn't add up to 100.
+pub fn check_percentages(what: &str, rows: &[(String, f64)]) -> Vec<String> {
+    let mut problems: Vec<String> = rows
+        .iter()
+        .filter(|(_, percentage)| !(0.0..=100.0).contains(percentage))
+        .map(|(name, percentage)| format!("{}: {} is at {}%", what, name, percentage))
+        .collect();
+    let total: f64 = rows.iter().map(|(_, percentage)| percentage).sum();
+    if !rows.is_empty() && (total - 100.0).abs() > PERCENT_TOLERANCE {
+        problems.push(format!("{} add up to {:.1}%, not 100%", what, total));
+    }
+    problems
+}
+
+/// Events without a readable date, or dated in the future or before GitHub existed.
+pub fn check_activity_dates(activities: &[Value], now: DateTime<Utc>) -> Vec<String> {
+    activities
+        .iter()
+        .filter_map(|activity| {
+            let created_at = activity["created_at"].as_str().unwrap_or_default();
+            // A few minutes of clock skew between GitHub and the runner are normal.
+            let plausible = DateTime::parse_from_rfc3339(created_at).is_ok_and(|date| {
+                date <= now + Duration::hours(1) && date.date_naive() >= github_launch()
+            });
+            (!plausible).then(|| {
+                format!(
+                    "Activity event {} is dated {:?}",
+                    activity["id"].as_str().unwrap_or("without an id"),
+                    created_at
+                )
+            })
+        })
+        .collect()
+}
+
+/// Calendar days after `today` or before GitHub existed.
+pub fn check_calendar(calendar: &ContributionCalendar, today: NaiveDate) -> Vec<String> {
+    let outside: Vec<&NaiveDate> = calendar
+        .days
+        .keys()
+        .filter(|date| **date > today + Duration::days(1) || **date < github_launch())
+        .collect();
+    match outside.as_slice() {
+        [] => Vec::new(),
+        [first, ..] => vec![format!(
+            "Contribution calendar has {} days outside {}..={}, first {}",
+            outside.len(),
+            github_launch(),
+            today,
+            first
+        )],
+    }
+}
+
+/// Reports every problem as an anomaly.
+pub fn report_invalid(problems: Vec<String>) {
+    for problem in problems {
+        report_anomaly(format!("Invalid data: {}", problem));
+    }
+}
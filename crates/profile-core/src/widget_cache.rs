@@ -0,0 +1,112 @@
+use crate::cache::{hash_key, write_atomically, CACHE_DIR};
+use crate::document::Section;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Deserialize)]
+struct RenderedSection {
+    input_hash: String,
+    output: Value,
+}
+
+/// Rendered sections from the previous run, keyed by section name and reused while the data
+/// behind them hashes the same.
+#[derive(Default, Serialize, Deserialize)]
+pub struct WidgetCache {
+    #[serde(skip)]
+    username: String,
+    /// Hash of the config and version that rendered the sections; a mismatch discards them all.
+    fingerprint: String,
+    sections: BTreeMap<String, RenderedSection>,
+    #[serde(skip)]
+    changed: BTreeSet<String>,
+}
+
+impl WidgetCache {
+    /// Returns the cached output of `name` if `input` is unchanged, otherwise renders and stores
+    /// it.
+    pub fn section<T: Serialize + ?Sized, O: Serialize + DeserializeOwned>(
+        &mut self,
+        name: &str,
+        input: &T,
+        render: impl FnOnce() -> O,
+    ) -> O {
+        let serialized = serde_json::to_string(input).expect("Failed to serialize widget input");
+        let input_hash = format!("{:016x}", hash_key(&serialized));
+
+        if let Some(output) = self
+            .sections
+            .get(name)
+            .filter(|section| section.input_hash == input_hash)
+            .and_then(|section| serde_json::from_value(section.output.clone()).ok())
+        {
+            return output;
+        }
+
+        let output = render();
+        self.changed.insert(name.to_string());
+        self.sections.insert(
+            name.to_string(),
+            RenderedSection {
+                input_hash,
+                output: serde_json::to_value(&output).expect("Failed to serialize widget output"),
+            },
+        );
+        output
+    }
+
+    /// Sections whose input differed from the previous run (or that are new).
+    pub fn changed(&self) -> &BTreeSet<String> {
+        &self.changed
+    }
+}
+
+fn widget_cache_path(username: &str) -> PathBuf {
+    Path::new(CACHE_DIR).join(format!("widgets-{}.json", username.to_lowercase()))
+}
+
+/// Loads `username`'s cached sections, dropping them if `config` (the profile config as text) has
+/// changed since they were rendered, as options like the output mode or theme shape every section.
+pub fn load_widget_cache(username: &str, config: &str) -> WidgetCache {
+    let fingerprint = format!("{}-{:016x}", env!("CARGO_PKG_VERSION"), hash_key(config));
+    let mut cache = fs::read_to_string(widget_cache_path(username))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<WidgetCache>(&contents).ok())
+        .filter(|cache| cache.fingerprint == fingerprint)
+        .unwrap_or(WidgetCache {
+            fingerprint,
+            ..WidgetCache::default()
+        });
+    cache.username = username.to_string();
+    cache
+}
+
+pub fn save_widget_cache(cache: &WidgetCache) {
+    let json = serde_json::to_string(cache).expect("Failed to serialize widget cache");
+    // Like the request cache, losing this only means re-rendering everything next run.
+    if fs::create_dir_all(CACHE_DIR).is_ok() {
+        let _ = write_atomically(&widget_cache_path(&cache.username), &json);
+    }
+}
+
+fn sections_path(username: &str) -> PathBuf {
+    Path::new(CACHE_DIR).join(format!("sections-{}.json", username.to_lowercase()))
+}
+
+/// Keeps every section of the last run, hidden ones included, for previewing layout changes
+/// without fetching anything.
+pub fn save_sections(username: &str, sections: &[Section]) {
+    let json = serde_json::to_string(sections).expect("Failed to serialize sections");
+    if fs::create_dir_all(CACHE_DIR).is_ok() {
+        let _ = write_atomically(&sections_path(username), &json);
+    }
+}
+
+pub fn load_sections(username: &str) -> Option<Vec<Section>> {
+    let contents = fs::read_to_string(sections_path(username)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
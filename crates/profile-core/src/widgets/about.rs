@@ -0,0 +1,81 @@
+use crate::config::AboutConfig;
+use crate::render::create_ascii_box;
+use crate::sanitize::fence_safe;
+
+/// ISO 639-1 code -> (language name, ISO 3166 country whose flag represents it).
+const LANGUAGES: [(&str, &str, &str); 20] = [
+    ("ar", "Arabic", "SA"),
+    ("cs", "Czech", "CZ"),
+    ("de", "German", "DE"),
+    ("en", "English", "GB"),
+    ("es", "Spanish", "ES"),
+    ("fr", "French", "FR"),
+    ("hi", "Hindi", "IN"),
+    ("hu", "Hungarian", "HU"),
+    ("it", "Italian", "IT"),
+    ("ja", "Japanese", "JP"),
+    ("ko", "Korean", "KR"),
+    ("nl", "Dutch", "NL"),
+    ("pl", "Polish", "PL"),
+    ("pt", "Portuguese", "PT"),
+    ("ro", "Romanian", "RO"),
+    ("ru", "Russian", "RU"),
+    ("sv", "Swedish", "SE"),
+    ("tr", "Turkish", "TR"),
+    ("uk", "Ukrainian", "UA"),
+    ("zh", "Chinese", "CN"),
+];
+
+/// Builds a flag emoji from a two-letter country code using regional indicator symbols.
+fn flag_emoji(country: &str) -> Option<String> {
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    country
+        .to_ascii_uppercase()
+        .chars()
+        .map(|c| char::from_u32(0x1F1E6 + (c as u32 - 'A' as u32)))
+        .collect()
+}
+
+/// Formats a spoken language given as `de`, `de-AT` (regional flag) or a free-form name.
+fn format_spoken_language(language: &str) -> String {
+    let (code, region) = match language.split_once('-') {
+        Some((code, region)) => (code, Some(region)),
+        None => (language, None),
+    };
+
+    match LANGUAGES
+        .iter()
+        .find(|(known, _, _)| known.eq_ignore_ascii_case(code))
+    {
+        Some((_, name, country)) => match flag_emoji(region.unwrap_or(country)) {
+            Some(flag) => format!("{} {}", flag, name),
+            None => name.to_string(),
+        },
+        None => language.to_string(),
+    }
+}
+
+pub fn format_about(about: &AboutConfig) -> String {
+    let mut lines = Vec::new();
+    let mut push = |label: &str, value: &Option<String>| {
+        if let Some(value) = value {
+            lines.push(format!("{:<10} {}", label, fence_safe(value)));
+        }
+    };
+    push("Pronouns", &about.pronouns);
+    push("Location", &about.location);
+    push("Role", &about.role);
+
+    if !about.languages.is_empty() {
+        let languages: Vec<String> = about
+            .languages
+            .iter()
+            .map(|language| fence_safe(&format_spoken_language(language)))
+            .collect();
+        lines.push(format!("{:<10} {}", "Speaks", languages.join(" · ")));
+    }
+
+    create_ascii_box("About", &lines)
+}
@@ -0,0 +1,67 @@
+use crate::render::create_ascii_badge;
+use crate::render::layout::wrap;
+use crate::sanitize::fence_safe;
+
+const ALT_PREFIX: &str = "alt=\"Achievement: ";
+const TIER_CLASS: &str = "achievement-tier-label";
+/// Columns a row of achievement badges may take before wrapping.
+const ROW_WIDTH: usize = 96;
+
+pub struct Achievement {
+    pub name: String,
+    pub tier: Option<String>,
+}
+
+/// Pulls achievement names from the `alt` texts of their badge images, and the `x2`-style tier
+/// label that follows each one. Anything unexpected is skipped rather than treated as an error,
+/// since the markup is not a stable interface.
+pub fn parse_achievements(html: &str) -> Vec<Achievement> {
+    let mut achievements: Vec<Achievement> = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(ALT_PREFIX) {
+        rest = &rest[start + ALT_PREFIX.len()..];
+        let Some(end) = rest.find('"') else {
+            break;
+        };
+        let name = fence_safe(rest[..end].trim());
+
+        // Only look for a tier label before the next achievement starts.
+        let block = &rest[..rest.find(ALT_PREFIX).unwrap_or(rest.len())];
+        let tier = block.find(TIER_CLASS).and_then(|pos| {
+            let label = &block[pos..];
+            let text_start = label.find('>')? + 1;
+            let text_end = text_start + label[text_start..].find('<')?;
+            let text = label[text_start..text_end].trim();
+            (!text.is_empty()).then(|| fence_safe(text))
+        });
+
+        // The page lists each achievement more than once (card and dialog).
+        if !name.is_empty() && !achievements.iter().any(|a| a.name == name) {
+            achievements.push(Achievement { name, tier });
+        }
+    }
+
+    achievements
+}
+
+pub fn format_achievements(achievements: &[Achievement]) -> String {
+    let badges: Vec<Vec<String>> = achievements
+        .iter()
+        .map(|achievement| {
+            create_ascii_badge(
+                &achievement.name,
+                achievement.tier.as_deref().unwrap_or("✓"),
+                0,
+            )
+            .lines()
+            .map(String::from)
+            .collect()
+        })
+        .collect();
+
+    wrap(&badges, 1, ROW_WIDTH)
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
@@ -0,0 +1,64 @@
+use crate::config::CelebrationsConfig;
+use crate::document::{Block, CalloutKind};
+use crate::history::{metric_noun, History};
+use crate::sanitize::html_escape;
+use chrono::{Duration, NaiveDate};
+
+pub struct Celebration {
+    pub metric: String,
+    pub threshold: u64,
+    pub crossed_on: NaiveDate,
+    pub message: Option<String>,
+}
+
+impl Celebration {
+    pub fn headline(&self) -> String {
+        format!("{} {}!", self.threshold, metric_noun(&self.metric))
+    }
+}
+
+/// Milestones crossed within the last `config.days` days, most recent first.
+pub fn get_celebrations(
+    config: &CelebrationsConfig,
+    history: &History,
+    today: NaiveDate,
+) -> Vec<Celebration> {
+    let mut celebrations: Vec<Celebration> = config
+        .milestones
+        .iter()
+        .filter_map(|milestone| {
+            let crossed_on = history.crossed_on(&milestone.metric, milestone.threshold)?;
+            if today - crossed_on >= Duration::days(config.days) {
+                return None;
+            }
+            Some(Celebration {
+                metric: milestone.metric.clone(),
+                threshold: milestone.threshold,
+                crossed_on,
+                message: milestone.message.clone(),
+            })
+        })
+        .collect();
+
+    celebrations.sort_by_key(|celebration| std::cmp::Reverse(celebration.crossed_on));
+    celebrations
+}
+
+pub fn format_celebrations(celebrations: &[Celebration]) -> Block {
+    let lines = celebrations
+        .iter()
+        .map(|celebration| {
+            Block::text(format!(
+                "🎉 <b>{}</b> Crossed on {}. {}",
+                html_escape(&celebration.headline()),
+                celebration.crossed_on.format("%Y-%m-%d"),
+                html_escape(celebration.message.as_deref().unwrap_or("Thank you!"))
+            ))
+        })
+        .collect();
+
+    Block::Callout {
+        kind: CalloutKind::Tip,
+        blocks: lines,
+    }
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, FixedOffset, Offset};
+
+/// Derives a display name from an IANA zone, e.g. `America/New_York` -> `New York`.
+pub fn city_from_timezone(timezone: &str) -> String {
+    timezone
+        .rsplit('/')
+        .next()
+        .unwrap_or(timezone)
+        .replace('_', " ")
+}
+
+fn format_utc_offset(now: &DateTime<FixedOffset>) -> String {
+    let seconds = now.offset().fix().local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+}
+
+/// Lines for the header's badge column.
+pub fn format_clock(now: &DateTime<FixedOffset>, city: &str) -> Vec<String> {
+    vec![
+        format!(
+            "🕒 It's currently {} in {} for me",
+            now.format("%H:%M"),
+            city
+        ),
+        format!("   {} · {}", format_utc_offset(now), now.format("%A")),
+    ]
+}
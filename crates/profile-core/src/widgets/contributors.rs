@@ -0,0 +1,78 @@
+use crate::config::OutputMode;
+use crate::document::{Block, Image};
+use crate::render::create_ascii_box;
+use crate::sanitize::{fence_safe, html_escape, is_http_url};
+use serde::{Deserialize, Serialize};
+
+/// Widest a line of logins may get before wrapping inside the ASCII box.
+const ROW_WIDTH: usize = 60;
+
+#[derive(Serialize, Deserialize)]
+pub struct Contributor {
+    pub login: String,
+    pub avatar_url: String,
+    pub contributions: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Contributors {
+    pub repo: String,
+    pub contributors: Vec<Contributor>,
+}
+
+fn format_contributors_ascii(contributors: &Contributors) -> Block {
+    let mut lines: Vec<String> = Vec::new();
+    for contributor in &contributors.contributors {
+        let entry = format!(
+            "@{} ({})",
+            fence_safe(&contributor.login),
+            contributor.contributions
+        );
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + entry.chars().count() + 3 <= ROW_WIDTH => {
+                *line += " · ";
+                *line += &entry;
+            }
+            _ => lines.push(entry),
+        }
+    }
+
+    let title = format!(
+        "Thanks to the contributors of {}",
+        fence_safe(&contributors.repo)
+    );
+    Block::Art(create_ascii_box(&title, &lines))
+}
+
+fn format_contributors_html(contributors: &Contributors) -> Block {
+    let avatars = contributors
+        .contributors
+        .iter()
+        .filter(|contributor| is_http_url(&contributor.avatar_url))
+        .map(|contributor| Image {
+            src: format!("{}&s=64", contributor.avatar_url),
+            alt: format!("@{}", contributor.login),
+            title: Some(format!(
+                "@{} ({} contributions)",
+                contributor.login, contributor.contributions
+            )),
+            link: Some(format!("https://github.com/{}", contributor.login)),
+            size: Some(32),
+        })
+        .collect();
+
+    Block::Images {
+        caption: Some(format!(
+            "Thanks to the contributors of <a href=\"https://github.com/{0}\">{0}</a>",
+            html_escape(&contributors.repo)
+        )),
+        images: avatars,
+    }
+}
+
+pub fn format_contributors(contributors: &Contributors, mode: &OutputMode) -> Block {
+    match mode {
+        OutputMode::Ascii => format_contributors_ascii(contributors),
+        OutputMode::Html => format_contributors_html(contributors),
+    }
+}
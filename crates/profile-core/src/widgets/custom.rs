@@ -0,0 +1,148 @@
+use crate::config::CustomWidgetConfig;
+use crate::sanitize::fence_safe;
+use serde_json::Value;
+
+const MAX_QUERY_LENGTH: usize = 4000;
+const MAX_VALUE_LENGTH: usize = 200;
+/// Bounds on a template and on what it renders to, so a template from a shared config can't
+/// blow up the README by repeating a placeholder thousands of times.
+const MAX_TEMPLATE_LENGTH: usize = 2000;
+const MAX_OUTPUT_LENGTH: usize = 8000;
+
+/// Rejects anything but read-only queries (and the fragments they use) with balanced braces.
+/// Every top-level definition is checked, so a mutation can't follow an innocent first query.
+pub fn validate_query(query: &str) -> Result<(), String> {
+    let trimmed = query.trim();
+    if trimmed.len() > MAX_QUERY_LENGTH {
+        return Err(format!("query is longer than {} bytes", MAX_QUERY_LENGTH));
+    }
+
+    let mut depth: i64 = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    // Where the top-level definition being read starts.
+    let mut definition = 0;
+    for (i, c) in trimmed.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => {
+                if depth == 0 {
+                    let keyword = trimmed[definition..i]
+                        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                        .find(|word| !word.is_empty());
+                    if !matches!(keyword, None | Some("query") | Some("fragment")) {
+                        return Err("only `query` operations are allowed".to_string());
+                    }
+                }
+                depth += 1;
+            }
+            '}' => {
+                depth -= 1;
+                if depth < 0 {
+                    return Err("unbalanced braces".to_string());
+                }
+                if depth == 0 {
+                    definition = i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || in_string {
+        return Err("unbalanced braces or quotes".to_string());
+    }
+    if !trimmed[definition..].trim().is_empty() {
+        return Err("only `query` operations are allowed".to_string());
+    }
+    Ok(())
+}
+
+/// Looks up a dotted path such as `user.repositories.nodes.0.name`.
+///
+/// A `*` segment maps the rest of the path over every element of an array.
+fn extract<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+    let mut current = vec![value];
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        current = current
+            .into_iter()
+            .flat_map(|value| -> Vec<&Value> {
+                match (segment, value) {
+                    ("*", Value::Array(items)) => items.iter().collect(),
+                    (_, Value::Array(items)) => segment
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| items.get(index))
+                        .into_iter()
+                        .collect(),
+                    (_, Value::Object(map)) => map.get(segment).into_iter().collect(),
+                    _ => Vec::new(),
+                }
+            })
+            .collect();
+    }
+    current
+}
+
+/// Makes an upstream value safe to place inside a code fence, and bounds its length.
+fn sanitize_value(value: &str) -> String {
+    let cleaned = fence_safe(value);
+    if cleaned.chars().count() > MAX_VALUE_LENGTH {
+        cleaned
+            .chars()
+            .take(MAX_VALUE_LENGTH - 1)
+            .chain(['…'])
+            .collect()
+    } else {
+        cleaned
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Fills the widget's template from the `data` of its query response.
+pub fn render_custom_widget(
+    widget: &CustomWidgetConfig,
+    data: &Value,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if widget.template.len() > MAX_TEMPLATE_LENGTH {
+        return Err(format!("template is longer than {} bytes", MAX_TEMPLATE_LENGTH).into());
+    }
+    let mut output = widget.template.clone();
+    for (name, path) in &widget.fields {
+        let values: Vec<String> = extract(data, path)
+            .into_iter()
+            .map(value_to_string)
+            .collect();
+        if values.is_empty() {
+            return Err(format!("path `{}` for field `{}` matched nothing", path, name).into());
+        }
+        let placeholder = format!("{{{}}}", name);
+        let value = sanitize_value(&values.join(", "));
+        // Checked before replacing, so an oversized result is never built.
+        let length = output.len() + output.matches(&placeholder).count() * value.len();
+        if length > MAX_OUTPUT_LENGTH {
+            return Err(format!(
+                "field `{}` makes the output longer than {} bytes",
+                name, MAX_OUTPUT_LENGTH
+            )
+            .into());
+        }
+        output = output.replace(&placeholder, &value);
+    }
+    Ok(output)
+}
@@ -0,0 +1,23 @@
+use crate::history::WeeklyDigest;
+use chrono::{Datelike, Duration, NaiveDate};
+
+/// Monday of the last fully completed week before `today`.
+pub fn last_week_start(today: NaiveDate) -> NaiveDate {
+    today - Duration::days(today.weekday().num_days_from_monday() as i64 + 7)
+}
+
+pub fn format_weekly_digest(digest: &WeeklyDigest) -> String {
+    let week_end = digest.week_start + Duration::days(6);
+    let plural =
+        |count: u64, word: &str| format!("{} {}{}", count, word, if count == 1 { "" } else { "s" });
+
+    format!(
+        "Last week ({} - {}): {} across {}, {} merged, {:+} followers\n",
+        digest.week_start.format("%b %-d"),
+        week_end.format("%b %-d"),
+        plural(digest.commits, "commit"),
+        plural(digest.repos as u64, "repo"),
+        plural(digest.prs_merged, "PR"),
+        digest.follower_delta
+    )
+}
@@ -0,0 +1,47 @@
+use crate::sanitize::fence_safe;
+use serde::{Deserialize, Serialize};
+
+/// Widest a thread title may get before it is cut short.
+const TITLE_WIDTH: usize = 56;
+
+#[derive(Serialize, Deserialize)]
+pub struct AnsweredThread {
+    pub repo: String,
+    pub title: String,
+    pub answered_on: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DiscussionStats {
+    /// Replies the user wrote in discussions.
+    pub replies: u64,
+    /// Of those, the ones marked as the answer.
+    pub accepted: u64,
+    pub recent: Vec<AnsweredThread>,
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    match text.chars().count() > width {
+        true => text.chars().take(width - 1).collect::<String>() + "…",
+        false => text.to_string(),
+    }
+}
+
+pub fn format_discussion_stats(stats: &DiscussionStats) -> String {
+    let mut output = format!(
+        "Answers marked helpful: {} (of {} discussion replies)\n",
+        stats.accepted, stats.replies
+    );
+    if !stats.recent.is_empty() {
+        output += "\nRecently answered:\n";
+    }
+    for thread in &stats.recent {
+        output += &format!(
+            "  {} {}: {}\n",
+            thread.answered_on,
+            fence_safe(&thread.repo),
+            fence_safe(&truncate(&thread.title, TITLE_WIDTH))
+        );
+    }
+    output
+}
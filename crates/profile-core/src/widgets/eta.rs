@@ -0,0 +1,62 @@
+use crate::config::EtaConfig;
+use crate::forecast::{forecast, next_milestone, Confidence, Forecast};
+use crate::history::{metric_noun, History};
+use crate::sanitize::fence_safe;
+use chrono::{Duration, NaiveDate};
+
+/// One line per metric: when it reaches its target at the pace of the last `config.days`.
+pub fn format_eta(config: &EtaConfig, history: &History, today: NaiveDate) -> String {
+    let from = today - Duration::days(config.days);
+    let mut output = String::new();
+    for metric in &config.metrics {
+        let series: Vec<(NaiveDate, u64)> = history
+            .snapshots
+            .iter()
+            .filter(|snapshot| snapshot.date >= from)
+            .filter_map(|snapshot| Some((snapshot.date, snapshot.get(metric)?)))
+            .collect();
+        let current = series.last().map_or(0, |(_, value)| *value);
+        let target = config
+            .targets
+            .get(metric)
+            .copied()
+            .filter(|target| *target > current)
+            .unwrap_or_else(|| next_milestone(current));
+        let noun = fence_safe(metric_noun(metric));
+
+        output += &match forecast(&series, target) {
+            Forecast::Eta {
+                date,
+                confidence: Confidence::Steady,
+            } => format!(
+                "At this rate: {} {} by ~{}\n",
+                target,
+                noun,
+                date.format("%B %Y")
+            ),
+            Forecast::Eta {
+                date,
+                confidence: Confidence::Rough,
+            } => format!(
+                "Roughly: {} {} around {} (the pace varies)\n",
+                target,
+                noun,
+                date.format("%B %Y")
+            ),
+            Forecast::Distant => format!(
+                "{} {}: more than five years away at this rate\n",
+                target, noun
+            ),
+            Forecast::NoTrend => format!(
+                "{} {}: no steady growth over the last {} days\n",
+                target, noun, config.days
+            ),
+            Forecast::NotEnoughHistory => format!(
+                "{} {}: not enough history for a forecast yet\n",
+                target, noun
+            ),
+            Forecast::Reached => String::new(),
+        };
+    }
+    output
+}
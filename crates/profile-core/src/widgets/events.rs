@@ -0,0 +1,45 @@
+use crate::render::create_ascii_bar;
+use crate::sanitize::fence_safe;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Pull requests counted towards an event running today, against its goal.
+#[derive(Serialize, Deserialize)]
+pub struct EventProgress {
+    pub name: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub pull_requests: u64,
+    pub goal: u64,
+}
+
+/// A progress bar per running event, with the days it has left.
+pub fn format_event_progress(events: &[EventProgress], today: NaiveDate) -> String {
+    let width = events
+        .iter()
+        .map(|event| event.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    events
+        .iter()
+        .map(|event| {
+            let percentage = match event.goal {
+                0 => 100.0,
+                goal => (event.pull_requests as f64 / goal as f64 * 100.0).min(100.0),
+            };
+            let days_left = (event.to - today).num_days();
+            let status = match event.pull_requests >= event.goal {
+                true => "goal reached 🎉".to_string(),
+                false => format!("{} days left", days_left),
+            };
+            format!(
+                "{:<width$} {} {}/{} PRs · {}\n",
+                fence_safe(&event.name),
+                create_ascii_bar(percentage, 20),
+                event.pull_requests,
+                event.goal,
+                status
+            )
+        })
+        .collect()
+}
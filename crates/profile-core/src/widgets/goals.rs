@@ -0,0 +1,126 @@
+use crate::config::{GoalConfig, GoalPeriod};
+use crate::history::{History, Snapshot};
+use crate::render::create_ascii_bar;
+use chrono::{Datelike, Duration, NaiveDate};
+
+pub struct GoalProgress {
+    pub label: String,
+    pub current: u64,
+    pub target: u64,
+    pub deadline: Option<NaiveDate>,
+}
+
+/// First day of the period containing `today`, and the last day of it.
+fn period_bounds(period: &GoalPeriod, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match period {
+        GoalPeriod::Week => {
+            let start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            (start, start + Duration::days(6))
+        }
+        GoalPeriod::Month => {
+            let start = today.with_day(1).unwrap();
+            let next = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+            };
+            (start, next.unwrap() - Duration::days(1))
+        }
+        GoalPeriod::Year => (
+            NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(today.year(), 12, 31).unwrap(),
+        ),
+    }
+}
+
+pub fn get_goal_progress(
+    goals: &[GoalConfig],
+    history: &History,
+    today: NaiveDate,
+) -> Vec<GoalProgress> {
+    let Some(latest) = history.snapshots.last() else {
+        return Vec::new();
+    };
+
+    goals
+        .iter()
+        .map(|goal| {
+            let total = latest.metric(&goal.metric);
+            let (current, deadline) = match &goal.period {
+                None => (total, goal.deadline),
+                Some(period) => {
+                    // Period goals count growth since the last snapshot before the period began,
+                    // falling back to the earliest snapshot inside it.
+                    let (start, end) = period_bounds(period, today);
+                    let known = |snapshot: &&Snapshot| snapshot.get(&goal.metric).is_some();
+                    let baseline = history
+                        .snapshots
+                        .iter()
+                        .rev()
+                        .filter(known)
+                        .find(|s| s.date < start)
+                        .or_else(|| {
+                            history
+                                .snapshots
+                                .iter()
+                                .filter(known)
+                                .find(|s| s.date >= start)
+                        })
+                        .map(|snapshot| snapshot.metric(&goal.metric))
+                        .unwrap_or(total);
+                    (total.saturating_sub(baseline), goal.deadline.or(Some(end)))
+                }
+            };
+
+            GoalProgress {
+                label: goal.label.clone(),
+                current,
+                target: goal.target,
+                deadline,
+            }
+        })
+        .collect()
+}
+
+fn format_remaining(progress: &GoalProgress, today: NaiveDate) -> String {
+    if progress.current >= progress.target {
+        return "done ✓".to_string();
+    }
+    match progress.deadline {
+        Some(deadline) if deadline < today => "overdue".to_string(),
+        Some(deadline) => match (deadline - today).num_days() {
+            0 => "last day".to_string(),
+            1 => "1 day left".to_string(),
+            days => format!("{} days left", days),
+        },
+        None => String::new(),
+    }
+}
+
+pub fn format_goals(goals: &[GoalProgress], today: NaiveDate) -> String {
+    let label_width = goals
+        .iter()
+        .map(|goal| goal.label.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    goals
+        .iter()
+        .map(|goal| {
+            let percentage = if goal.target == 0 {
+                100.0
+            } else {
+                (goal.current as f64 / goal.target as f64 * 100.0).min(100.0)
+            };
+            format!(
+                "{:<label_width$} {} {:>5.1}%  {:>12}  {}\n",
+                goal.label,
+                create_ascii_bar(percentage, 20),
+                percentage,
+                format!("{}/{}", goal.current, goal.target),
+                format_remaining(goal, today),
+                label_width = label_width
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,70 @@
+use crate::config::{GrowthConfig, OutputMode};
+use crate::document::Block;
+#[cfg(feature = "svg")]
+use crate::document::Image;
+use crate::history::{metric_noun, History};
+#[cfg(feature = "svg")]
+use crate::render::chart::render_line_chart_svg;
+#[cfg(feature = "svg")]
+use crate::render::ASSETS_DIR;
+use crate::render::{create_sparkline, Asset};
+use chrono::{Duration, NaiveDate};
+
+fn get_series(history: &History, metric: &str, from: NaiveDate) -> Vec<(NaiveDate, u64)> {
+    history
+        .snapshots
+        .iter()
+        .filter(|snapshot| snapshot.date >= from)
+        .filter_map(|snapshot| Some((snapshot.date, snapshot.get(metric)?)))
+        .collect()
+}
+
+/// Renders one sparkline (ASCII mode) or SVG chart (HTML mode) per configured metric.
+///
+/// In HTML mode the charts are returned as assets under `assets/` and embedded as images.
+/// Builds without `svg` show the sparklines in either mode.
+pub fn format_growth(
+    config: &GrowthConfig,
+    history: &History,
+    today: NaiveDate,
+    mode: &OutputMode,
+) -> (Block, Vec<Asset>) {
+    let from = today - Duration::days(config.days);
+
+    match mode {
+        #[cfg(feature = "svg")]
+        OutputMode::Html => {
+            let mut images = Vec::new();
+            let mut assets = Vec::new();
+            for metric in &config.metrics {
+                let path = format!("{}/growth-{}.svg", ASSETS_DIR, metric);
+                let title = format!("{} (last {} days)", metric_noun(metric), config.days);
+                images.push(Image::new(&path, &title));
+                assets.push(Asset {
+                    contents: render_line_chart_svg(&title, &get_series(history, metric, from))
+                        .into_bytes(),
+                    path,
+                });
+            }
+            let block = Block::Images {
+                caption: None,
+                images,
+            };
+            (block, assets)
+        }
+        _ => {
+            let mut output = String::new();
+            for metric in &config.metrics {
+                let series = get_series(history, metric, from);
+                let values: Vec<u64> = series.iter().map(|(_, value)| *value).collect();
+                output += &format!(
+                    "{:<14} {} {}\n",
+                    metric_noun(metric),
+                    create_sparkline(&values),
+                    values.last().copied().unwrap_or(0)
+                );
+            }
+            (Block::Art(output), Vec::new())
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::config::HeaderLayout;
+use crate::render::layout::{beside, center, stack, width, wrap};
+
+/// Columns between the art and the badge column.
+const BADGE_GAP: usize = 4;
+/// Lines the badge column starts below the top of the art, so it sits beside the figure's body.
+const BADGE_OFFSET: usize = 3;
+/// Columns a badge row may take before wrapping.
+const ROW_WIDTH: usize = 96;
+/// Blank lines between stacked badges, and between the art and a badge row below it.
+const BLOCK_GAP: usize = 1;
+
+/// The art and badges arranged as `layout` says. Each badge is a block of lines.
+pub fn compose_header(layout: HeaderLayout, art: &[String], badges: &[Vec<String>]) -> String {
+    let lines = match layout {
+        HeaderLayout::ArtLeft => beside(art, &stack(badges, BLOCK_GAP), BADGE_GAP, BADGE_OFFSET),
+        HeaderLayout::ArtRight => beside(&stack(badges, BLOCK_GAP), art, BADGE_GAP, 0),
+        HeaderLayout::ArtTop => {
+            let badge_row = wrap(badges, BADGE_GAP, ROW_WIDTH);
+            let total_width = width(art).max(width(&badge_row));
+            stack(
+                &[center(art, total_width), center(&badge_row, total_width)],
+                BLOCK_GAP,
+            )
+        }
+        HeaderLayout::BadgesOnly => wrap(badges, BADGE_GAP, ROW_WIDTH),
+    };
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
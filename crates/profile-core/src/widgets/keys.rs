@@ -0,0 +1,62 @@
+use crate::config::KeysConfig;
+use crate::sanitize::fence_safe;
+
+pub struct GpgKey {
+    /// The 16-digit long key ID GitHub lists, the last 64 bits of the fingerprint. GitHub
+    /// doesn't list the fingerprint itself; `https://github.com/<user>.gpg` has the full key.
+    pub key_id: String,
+    pub emails: Vec<String>,
+}
+
+pub struct SshKey {
+    pub key_type: String,
+    pub fingerprint: String,
+}
+
+pub struct Keys {
+    pub gpg: Vec<GpgKey>,
+    pub ssh: Vec<SshKey>,
+}
+
+/// `hex` in blocks of four digits, the way `gpg` prints key IDs and fingerprints.
+pub fn hex_groups(hex: &str) -> Vec<String> {
+    hex.chars()
+        .collect::<Vec<char>>()
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+pub fn format_keys(keys: &Keys, config: &KeysConfig, username: &str) -> String {
+    let mut output = String::new();
+
+    if config.summary {
+        output += &format!(
+            "{} GPG key(s), {} SSH key(s)\n",
+            keys.gpg.len(),
+            keys.ssh.len()
+        );
+    } else {
+        for key in &keys.gpg {
+            output += &format!(
+                "GPG  {:<10} {:<19} {}\n",
+                "key ID",
+                fence_safe(&hex_groups(&key.key_id).join(" ")),
+                fence_safe(&key.emails.join(", "))
+            );
+        }
+        for key in &keys.ssh {
+            output += &format!(
+                "SSH  {:<10} {}\n",
+                fence_safe(&key.key_type),
+                key.fingerprint
+            );
+        }
+    }
+
+    output += &format!(
+        "Verify: https://github.com/{0}.gpg · https://github.com/{0}.keys\n",
+        username
+    );
+    output
+}
@@ -0,0 +1,61 @@
+use crate::config::MentorshipEntry;
+use crate::document::{Align, Block, Column};
+
+const GITHUB: &str = "https://github.com/";
+
+/// `owner/name` and, for a pull request or issue link, its number.
+pub fn github_target(link: &str) -> Option<(&str, Option<&str>)> {
+    let path = link.strip_prefix(GITHUB)?.trim_end_matches('/');
+    let mut parts = path.splitn(4, '/');
+    let (owner, name) = (parts.next()?, parts.next()?);
+    let repo = &path[..owner.len() + 1 + name.len()];
+    match (parts.next(), parts.next()) {
+        (None, _) => Some((repo, None)),
+        (Some("pull" | "issues"), Some(number)) if number.parse::<u64>().is_ok() => {
+            Some((repo, Some(number)))
+        }
+        _ => None,
+    }
+}
+
+/// `owner/name#12` for GitHub links, the link itself otherwise.
+pub fn short_link(link: &str) -> String {
+    match github_target(link) {
+        Some((repo, Some(number))) => format!("{}#{}", repo, number),
+        Some((repo, None)) => repo.to_string(),
+        None => link.to_string(),
+    }
+}
+
+/// One row per program, newest year first, in the same table layout as the stats.
+pub fn format_mentorship(entries: &[MentorshipEntry]) -> Block {
+    let mut rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| {
+            vec![
+                entry.year.to_string(),
+                entry.program.clone(),
+                entry.role.clone(),
+                entry.project.clone(),
+                entry.link.as_deref().map(short_link).unwrap_or_default(),
+            ]
+        })
+        .collect();
+    rows.sort_by(|a, b| b[0].cmp(&a[0]));
+
+    let width = |column: usize, heading: &str| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .chain([heading.chars().count()])
+            .max()
+            .unwrap_or(0)
+    };
+    let columns = vec![
+        Column::new("Year", width(0, "Year"), Align::Center),
+        Column::new("Program", width(1, "Program"), Align::Left),
+        Column::new("Role", width(2, "Role"), Align::Left),
+        Column::new("Project", width(3, "Project"), Align::Left),
+        Column::new("Link", width(4, "Link"), Align::Left),
+    ];
+    Block::Table { columns, rows }
+}
@@ -0,0 +1,26 @@
+pub mod about;
+pub mod achievements;
+pub mod celebrations;
+pub mod clock;
+pub mod contributors;
+pub mod custom;
+pub mod digest;
+pub mod discussions;
+pub mod eta;
+pub mod events;
+pub mod goals;
+pub mod growth;
+pub mod header;
+pub mod keys;
+pub mod mentorship;
+pub mod pr_sizes;
+pub mod proofs;
+pub mod releases;
+pub mod responsiveness;
+pub mod review_balance;
+pub mod spotlight;
+pub mod stargazers;
+pub mod talks;
+pub mod timeline;
+pub mod triage;
+pub mod year_comparison;
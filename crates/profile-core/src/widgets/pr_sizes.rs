@@ -0,0 +1,72 @@
+use crate::render::create_ascii_bar;
+use serde::{Deserialize, Serialize};
+
+/// Size classes by lines changed (additions plus deletions): label and exclusive upper bound.
+const SIZE_CLASSES: [(&str, Option<u64>); 4] = [
+    ("XS", Some(10)),
+    ("S", Some(100)),
+    ("M", Some(500)),
+    ("L", None),
+];
+
+/// Lines changed by each of the user's most recent pull requests, newest first.
+#[derive(Serialize, Deserialize)]
+pub struct PrSizes {
+    pub sizes: Vec<u64>,
+}
+
+impl PrSizes {
+    /// How many pull requests fall in each of [`SIZE_CLASSES`].
+    fn histogram(&self) -> [u64; SIZE_CLASSES.len()] {
+        let mut counts = [0; SIZE_CLASSES.len()];
+        for size in &self.sizes {
+            let class = SIZE_CLASSES
+                .iter()
+                .position(|(_, below)| below.is_none_or(|below| *size < below))
+                .unwrap_or(SIZE_CLASSES.len() - 1);
+            counts[class] += 1;
+        }
+        counts
+    }
+
+    fn median(&self) -> Option<u64> {
+        let mut sizes = self.sizes.clone();
+        sizes.sort_unstable();
+        let middle = sizes.len() / 2;
+        match sizes.len() {
+            0 => None,
+            len if len % 2 == 0 => Some((sizes[middle - 1] + sizes[middle]) / 2),
+            _ => Some(sizes[middle]),
+        }
+    }
+}
+
+pub fn format_pr_sizes(pr_sizes: &PrSizes) -> String {
+    let histogram = pr_sizes.histogram();
+    let max = histogram.iter().copied().max().unwrap_or(0).max(1);
+
+    let mut lower = 0;
+    let mut output = String::new();
+    for ((label, below), count) in SIZE_CLASSES.iter().zip(histogram) {
+        let range = match below {
+            Some(below) => format!("{}-{}", lower, below - 1),
+            None => format!("{}+", lower),
+        };
+        output += &format!(
+            "{:<2} {:>7} {} {}\n",
+            label,
+            range,
+            create_ascii_bar(count as f64 / max as f64 * 100.0, 20),
+            count
+        );
+        lower = below.unwrap_or(lower);
+    }
+    if let Some(median) = pr_sizes.median() {
+        output += &format!(
+            "\nMedian: {} lines changed over the last {} PRs\n",
+            median,
+            pr_sizes.sizes.len()
+        );
+    }
+    output
+}
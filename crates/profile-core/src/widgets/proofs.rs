@@ -0,0 +1,81 @@
+use crate::config::ProofsConfig;
+use crate::sanitize::fence_safe;
+use crate::widgets::keys::hex_groups;
+
+/// One identity claim and, if it can be checked, where its proof lives.
+pub struct Proof {
+    pub claim: String,
+    pub url: Option<String>,
+}
+
+/// A v4 (40 hex digits) or v5 (64) OpenPGP fingerprint, uppercased and grouped in fours with
+/// a wider gap in the middle, the way `gpg --fingerprint` prints it.
+fn format_fingerprint(fingerprint: &str) -> Result<String, String> {
+    let hex: String = fingerprint
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    if !matches!(hex.len(), 40 | 64) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "`{}` is not an OpenPGP fingerprint (40 or 64 hex digits)",
+            fingerprint
+        ));
+    }
+    let groups = hex_groups(&hex);
+    let (first, second) = groups.split_at(groups.len() / 2);
+    Ok(format!("{}  {}", first.join(" "), second.join(" ")))
+}
+
+/// The claims in `config`, GitHub first. Fails on a malformed fingerprint rather than
+/// publishing a wrong one.
+pub fn proof_claims(config: &ProofsConfig, username: &str) -> Result<Vec<Proof>, String> {
+    let mut proofs = vec![Proof {
+        claim: format!("I am {} on GitHub.", username),
+        url: None,
+    }];
+    if let Some(keybase) = &config.keybase {
+        proofs.push(Proof {
+            claim: format!("I am {} on Keybase.", keybase),
+            url: Some(format!("https://keybase.io/{}", keybase)),
+        });
+    }
+    if let Some(fingerprint) = &config.fingerprint {
+        proofs.push(Proof {
+            claim: format!(
+                "I have a public key with fingerprint {}.",
+                format_fingerprint(fingerprint)?
+            ),
+            url: None,
+        });
+    }
+    for domain in &config.domains {
+        proofs.push(Proof {
+            claim: format!("I control {}.", domain),
+            url: Some(format!("https://{}/.well-known/keybase.txt", domain)),
+        });
+    }
+    for account in &config.accounts {
+        proofs.push(Proof {
+            claim: format!("I am {} on {}.", account.username, account.service),
+            url: account.proof.clone(),
+        });
+    }
+    Ok(proofs)
+}
+
+/// The claims as a Keybase-style statement. Proofs whose URL is in `unresolved` are marked.
+pub fn format_proofs(proofs: &[Proof], unresolved: &[String]) -> String {
+    let mut output = "I hereby claim:\n\n".to_string();
+    for proof in proofs {
+        output += &format!("  * {}\n", fence_safe(&proof.claim));
+        if let Some(url) = &proof.url {
+            let mark = match unresolved.contains(url) {
+                true => " ✗ unresolved",
+                false => "",
+            };
+            output += &format!("    {}{}\n", fence_safe(url), mark);
+        }
+    }
+    output
+}
@@ -0,0 +1,51 @@
+use crate::sanitize::fence_safe;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Release {
+    pub repo: String,
+    pub tag: String,
+    pub name: String,
+    pub published_at: String,
+    pub excerpt: Vec<String>,
+    pub truncated: bool,
+}
+
+pub fn strip_html(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+pub fn format_release(release: &Release) -> String {
+    let mut title = format!("{} {}", release.repo, fence_safe(&release.tag));
+    if !release.name.is_empty() && release.name != release.tag {
+        title += &format!(" - {}", fence_safe(&strip_html(&release.name)));
+    }
+
+    let mut output = format!("{:<50} {}\n", title, release.published_at);
+    output += &"-".repeat(60);
+    output += "\n";
+    for line in &release.excerpt {
+        output += &format!("{}\n", fence_safe(line));
+    }
+    if release.truncated {
+        output += "…\n";
+    }
+    output
+}
@@ -0,0 +1,89 @@
+use crate::percent::normalize_percentages;
+use crate::render::{create_ascii_bar, create_ascii_box};
+use crate::sanitize::fence_safe;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+
+pub struct Responsiveness {
+    pub repo: String,
+    pub median_first_response: Option<Duration>,
+    pub responded: usize,
+    pub sampled: usize,
+    pub open_ages: Vec<(&'static str, usize)>,
+}
+
+pub fn parse_time(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Time from `issue` being opened to the first of `events` in its `timeline` by someone other
+/// than the author or a bot.
+pub fn first_reaction_delay(issue: &Value, timeline: &Value, events: &[&str]) -> Option<Duration> {
+    let author = issue["user"]["login"].as_str()?;
+    let opened = parse_time(&issue["created_at"])?;
+
+    timeline
+        .as_array()?
+        .iter()
+        .filter(|event| events.contains(&event["event"].as_str().unwrap_or("")))
+        .filter_map(|event| {
+            // Comments carry `user`, every other timeline event carries `actor`.
+            let responder = event["actor"]["login"]
+                .as_str()
+                .or_else(|| event["user"]["login"].as_str())?;
+            if responder == author || responder.ends_with("[bot]") {
+                return None;
+            }
+            parse_time(&event["created_at"])
+        })
+        .min()
+        .map(|responded_at| responded_at - opened)
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.num_hours() < 1 {
+        format!("{}m", duration.num_minutes().max(0))
+    } else if duration.num_days() < 1 {
+        format!("{}h {}m", duration.num_hours(), duration.num_minutes() % 60)
+    } else {
+        format!("{}d {}h", duration.num_days(), duration.num_hours() % 24)
+    }
+}
+
+pub fn format_responsiveness(stats: &Responsiveness) -> String {
+    let median = stats
+        .median_first_response
+        .map(format_duration)
+        .unwrap_or_else(|| "n/a".to_string());
+    let total_open: usize = stats.open_ages.iter().map(|(_, count)| count).sum();
+
+    let mut lines = vec![
+        format!("{:<18} {}", "Repository", fence_safe(&stats.repo)),
+        format!(
+            "{:<18} {} ({} of {} issues answered)",
+            "Median response", median, stats.responded, stats.sampled
+        ),
+        format!("{:<18} {}", "Open issues", total_open),
+    ];
+    let counts: Vec<f64> = stats
+        .open_ages
+        .iter()
+        .map(|(_, count)| *count as f64)
+        .collect();
+    for ((label, count), percentage) in stats
+        .open_ages
+        .iter()
+        .zip(normalize_percentages(&counts, 0))
+    {
+        lines.push(format!(
+            "  {:<16} {} {}",
+            label,
+            create_ascii_bar(percentage, 10),
+            count
+        ));
+    }
+
+    create_ascii_box("Maintainer responsiveness", &lines)
+}
@@ -0,0 +1,43 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// How far back reviews are counted: about a quarter.
+pub const REVIEW_WINDOW_DAYS: i64 = 90;
+
+/// Review comments the user left on others' pull requests against those others left on theirs,
+/// since `since`.
+#[derive(Serialize, Deserialize)]
+pub struct ReviewBalance {
+    pub since: NaiveDate,
+    pub given_reviews: u64,
+    pub given_comments: u64,
+    pub received_reviews: u64,
+    pub received_comments: u64,
+}
+
+/// "Gives 3.0× more review comments than received", with the counts behind it.
+pub fn format_review_balance(balance: &ReviewBalance) -> String {
+    let (given, received) = (balance.given_comments, balance.received_comments);
+    let verdict = match (given, received) {
+        (0, 0) => "No review comments given or received".to_string(),
+        (_, 0) => "Gives review comments without receiving any".to_string(),
+        (0, _) => "Receives review comments without giving any".to_string(),
+        _ if given >= received => format!(
+            "Gives {:.1}× more review comments than received",
+            given as f64 / received as f64
+        ),
+        _ => format!(
+            "Receives {:.1}× more review comments than given",
+            received as f64 / given as f64
+        ),
+    };
+    format!(
+        "{} since {}\nGiven:    {} comments in {} reviews\nReceived: {} comments in {} reviews\n",
+        verdict,
+        balance.since.format("%b %-d"),
+        given,
+        balance.given_reviews,
+        received,
+        balance.received_reviews
+    )
+}
@@ -0,0 +1,73 @@
+use crate::document::Block;
+use crate::sanitize::html_escape;
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Where the rotation stands, kept in the history file between runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpotlightRotation {
+    /// Monday of the week `current` is featured.
+    pub week_start: Option<NaiveDate>,
+    pub current: Option<String>,
+    /// Repositories featured since the rotation last started over, oldest first.
+    pub featured: Vec<String>,
+}
+
+impl SpotlightRotation {
+    /// The repository to feature in the week starting `week_start`: the current one if it is
+    /// still that week and still a candidate, else the first of `candidates` (in rotation
+    /// order) not featured yet, starting over once every candidate had its turn.
+    pub fn advance(&mut self, week_start: NaiveDate, candidates: &[String]) -> Option<String> {
+        let current = self
+            .current
+            .clone()
+            .filter(|current| candidates.contains(current));
+        if self.week_start == Some(week_start) && current.is_some() {
+            return current;
+        }
+        let next = match candidates
+            .iter()
+            .find(|candidate| !self.featured.contains(candidate))
+        {
+            Some(next) => next.clone(),
+            None => {
+                self.featured.clear();
+                candidates.first()?.clone()
+            }
+        };
+        self.featured.push(next.clone());
+        self.week_start = Some(week_start);
+        self.current = Some(next.clone());
+        Some(next)
+    }
+}
+
+/// Monday of the week `today` is in.
+pub fn week_start(today: NaiveDate) -> NaiveDate {
+    today - Duration::days(today.weekday().num_days_from_monday() as i64)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Spotlight {
+    pub repo: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    pub stars: u64,
+}
+
+pub fn format_spotlight(spotlight: &Spotlight) -> Block {
+    let mut html = format!(
+        "<b><a href=\"{}\">{}</a></b>",
+        html_escape(&spotlight.url),
+        html_escape(&spotlight.repo)
+    );
+    if let Some(language) = &spotlight.language {
+        html += &format!(" · {}", html_escape(language));
+    }
+    html += &format!(" · ★ {}", spotlight.stars);
+    if let Some(description) = &spotlight.description {
+        html += &format!("<br>{}", html_escape(description));
+    }
+    Block::text(html)
+}
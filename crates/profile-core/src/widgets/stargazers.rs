@@ -0,0 +1,21 @@
+pub struct StargazerFacts {
+    pub sampled: usize,
+    pub countries: usize,
+    /// UTC hour in which the sampled stars most often arrived.
+    pub busiest_hour: Option<u32>,
+}
+
+pub fn format_stargazer_facts(facts: &StargazerFacts) -> String {
+    let mut output = format!(
+        "⭐ People from {} countries starred my repos (of the last {} stargazers)\n",
+        facts.countries, facts.sampled
+    );
+    if let Some(hour) = facts.busiest_hour {
+        output += &format!(
+            "🕒 Most stars arrive between {:02}:00 and {:02}:00 UTC\n",
+            hour,
+            (hour + 1) % 24
+        );
+    }
+    output
+}
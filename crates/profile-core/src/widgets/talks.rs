@@ -0,0 +1,278 @@
+//! Conference talks and publications, kept in a YAML list or a BibTeX file next to the config.
+
+use crate::anomaly::report_anomaly;
+use crate::config::TalksConfig;
+use crate::document::Block;
+use crate::remote::read_source;
+use crate::sanitize::html_escape;
+use chrono::{Datelike, NaiveDate};
+use serde::Deserialize;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Fields naming where a BibTeX entry appeared, most specific first.
+const VENUE_FIELDS: [&str; 6] = [
+    "booktitle",
+    "journal",
+    "howpublished",
+    "school",
+    "organization",
+    "publisher",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TalkKind {
+    Talk,
+    Publication,
+}
+
+impl TalkKind {
+    fn icon(self) -> &'static str {
+        match self {
+            TalkKind::Talk => "🎤",
+            TalkKind::Publication => "📄",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Talk {
+    pub kind: TalkKind,
+    pub title: String,
+    pub year: i32,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    /// Conference, journal or meetup.
+    pub venue: Option<String>,
+    pub link: Option<String>,
+}
+
+/// One entry of `talks.yaml`. Either `date` or `year` is required.
+#[derive(Deserialize)]
+struct YamlTalk {
+    title: String,
+    date: Option<NaiveDate>,
+    year: Option<i32>,
+    #[serde(alias = "venue")]
+    event: Option<String>,
+    #[serde(alias = "url")]
+    link: Option<String>,
+    kind: Option<TalkKind>,
+}
+
+fn parse_yaml(contents: &str) -> Result<Vec<Talk>, String> {
+    let entries: Vec<YamlTalk> = serde_yaml::from_str(contents).map_err(|e| e.to_string())?;
+    entries
+        .into_iter()
+        .map(|entry| {
+            let year = match (entry.date, entry.year) {
+                (Some(date), _) => date.year(),
+                (None, Some(year)) => year,
+                (None, None) => return Err(format!("`{}` has neither date nor year", entry.title)),
+            };
+            Ok(Talk {
+                kind: entry.kind.unwrap_or(TalkKind::Talk),
+                title: entry.title,
+                year,
+                month: entry.date.map(|date| date.month()),
+                day: entry.date.map(|date| date.day()),
+                venue: entry.event,
+                link: entry.link,
+            })
+        })
+        .collect()
+}
+
+/// Splits `text` after the group opened by its first character, `{` or `(`, returning the
+/// group's inside and what follows it.
+fn split_group(text: &str) -> Option<(&str, &str)> {
+    let open = text.chars().next()?;
+    let close = match open {
+        '{' => '}',
+        '(' => ')',
+        _ => return None,
+    };
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&text[1..index], &text[index + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Reads one field value (`{...}`, `"..."` or a bare word, joined with `#`) off the front of
+/// `text`, returning it and the rest after the separating comma.
+fn take_value(text: &str) -> (String, &str) {
+    let mut value = String::new();
+    let mut rest = text.trim_start();
+    loop {
+        let (part, after) = match rest.chars().next() {
+            Some('{') => split_group(rest).unwrap_or((rest, "")),
+            Some('"') => {
+                let mut depth = 0;
+                let end = rest[1..]
+                    .char_indices()
+                    .find(|&(_, c)| {
+                        match c {
+                            '{' => depth += 1,
+                            '}' => depth -= 1,
+                            _ => {}
+                        }
+                        c == '"' && depth == 0
+                    })
+                    .map_or(rest.len(), |(index, _)| index + 1);
+                (&rest[1..end], rest.get(end + 1..).unwrap_or(""))
+            }
+            _ => {
+                let end = rest.find([',', '#']).unwrap_or(rest.len());
+                (rest[..end].trim(), &rest[end..])
+            }
+        };
+        value += part;
+        rest = after.trim_start();
+        match rest.strip_prefix('#') {
+            Some(more) => rest = more.trim_start(),
+            None => break,
+        }
+    }
+    (value, rest.strip_prefix(',').unwrap_or(rest))
+}
+
+/// Plain text from a BibTeX value: grouping braces, common escapes and runs of whitespace gone.
+fn clean_value(value: &str) -> String {
+    value
+        .replace("\\&", "&")
+        .replace("\\%", "%")
+        .replace("\\_", "_")
+        .replace("---", "—")
+        .replace("--", "–")
+        .replace(['{', '}'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `5`, `may` or `May` to 5.
+fn parse_month(month: &str) -> Option<u32> {
+    month
+        .parse()
+        .ok()
+        .filter(|month| (1..=12).contains(month))
+        .or_else(|| {
+            let prefix = month.get(..3)?;
+            MONTHS
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(prefix))
+                .map(|index| index as u32 + 1)
+        })
+}
+
+/// Entries of a BibTeX file as publications. `@string`, `@preamble` and `@comment` are ignored,
+/// and entries without a title or year are reported and skipped.
+fn parse_bibtex(contents: &str) -> Result<Vec<Talk>, String> {
+    let mut talks = Vec::new();
+    let mut rest = contents;
+    while let Some(at) = rest.find('@') {
+        rest = &rest[at + 1..];
+        let open = rest.find(['{', '(']).ok_or("unterminated BibTeX entry")?;
+        let entry_type = rest[..open].trim().to_lowercase();
+        let (body, after) = split_group(&rest[open..])
+            .ok_or_else(|| format!("unbalanced braces in a @{} entry", entry_type))?;
+        rest = after;
+        if matches!(entry_type.as_str(), "string" | "preamble" | "comment") {
+            continue;
+        }
+
+        let (key, mut fields_text) = body.split_once(',').unwrap_or((body, ""));
+        let mut fields = Vec::new();
+        while let Some((name, after_name)) = fields_text.split_once('=') {
+            let (value, after_value) = take_value(after_name);
+            fields.push((name.trim().to_lowercase(), clean_value(&value)));
+            fields_text = after_value;
+        }
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, value)| field == name && !value.is_empty())
+                .map(|(_, value)| value.clone())
+        };
+
+        let (Some(title), Some(year)) =
+            (field("title"), field("year").and_then(|y| y.parse().ok()))
+        else {
+            report_anomaly(format!(
+                "Skipping BibTeX entry `{}`: it needs a title and a year",
+                key.trim()
+            ));
+            continue;
+        };
+        talks.push(Talk {
+            kind: TalkKind::Publication,
+            title,
+            year,
+            month: field("month").and_then(|month| parse_month(&month)),
+            day: None,
+            venue: VENUE_FIELDS.iter().find_map(|name| field(name)),
+            link: field("url")
+                .or_else(|| field("doi").map(|doi| format!("https://doi.org/{}", doi))),
+        });
+    }
+    Ok(talks)
+}
+
+/// Reads `config.source`, as BibTeX when it ends in `.bib` and as a YAML list otherwise.
+pub fn load_talks(config: &TalksConfig) -> Result<Vec<Talk>, String> {
+    let contents = read_source(&config.source, None)?;
+    let talks = match config.source.to_lowercase().ends_with(".bib") {
+        true => parse_bibtex(&contents),
+        false => parse_yaml(&contents),
+    };
+    talks.map_err(|e| format!("{}: {}", config.source, e))
+}
+
+fn format_talk(talk: &Talk) -> String {
+    let title = html_escape(&talk.title);
+    let mut html = format!("{} ", talk.kind.icon());
+    html += &match &talk.link {
+        Some(link) => format!("<a href=\"{}\">{}</a>", html_escape(link), title),
+        None => format!("<b>{}</b>", title),
+    };
+    if let Some(venue) = &talk.venue {
+        html += &format!(" · {}", html_escape(venue));
+    }
+    let month = talk
+        .month
+        .and_then(|month| MONTHS.get(month.checked_sub(1)? as usize));
+    match (month, talk.day) {
+        (Some(month), Some(day)) => html += &format!(" · {} {}", month, day),
+        (Some(month), None) => html += &format!(" · {}", month),
+        _ => {}
+    }
+    html
+}
+
+/// The newest `limit` talks (all when unset) grouped under their year, newest first.
+pub fn format_talks(talks: &mut [Talk], limit: Option<usize>) -> Vec<Block> {
+    talks.sort_by_key(|talk| std::cmp::Reverse((talk.year, talk.month, talk.day)));
+    let shown = &talks[..limit.unwrap_or(talks.len()).min(talks.len())];
+
+    let mut blocks = Vec::new();
+    for (index, talk) in shown.iter().enumerate() {
+        if index == 0 || shown[index - 1].year != talk.year {
+            blocks.push(Block::text(format!("<b>{}</b>", talk.year)));
+        }
+        blocks.push(Block::text(format_talk(talk)));
+    }
+    blocks
+}
@@ -0,0 +1,18 @@
+use crate::render::create_ascii_bar;
+use std::collections::BTreeMap;
+
+pub fn format_repos_per_year(per_year: &BTreeMap<i32, u64>) -> String {
+    let max = per_year.values().copied().max().unwrap_or(0).max(1);
+
+    per_year
+        .iter()
+        .map(|(year, count)| {
+            format!(
+                "{} {} {}\n",
+                year,
+                create_ascii_bar(*count as f64 / max as f64 * 100.0, 20),
+                count
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,39 @@
+use crate::render::{create_ascii_bar, create_ascii_box};
+use crate::sanitize::fence_safe;
+use serde::{Deserialize, Serialize};
+
+/// How many of one repository's recent issues were triaged in time.
+#[derive(Serialize, Deserialize)]
+pub struct TriageVelocity {
+    pub repo: String,
+    pub triaged: usize,
+    pub issues: usize,
+}
+
+/// A gauge per repository of the share of issues triaged within `hours`.
+pub fn format_triage_velocity(velocities: &[TriageVelocity], hours: i64) -> String {
+    let width = velocities
+        .iter()
+        .map(|velocity| velocity.repo.chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<String> = velocities
+        .iter()
+        .map(|velocity| {
+            let repo = fence_safe(&velocity.repo);
+            if velocity.issues == 0 {
+                return format!("{:<width$} no new issues", repo);
+            }
+            let percentage = velocity.triaged as f64 / velocity.issues as f64 * 100.0;
+            format!(
+                "{:<width$} {} {:>3.0}% ({} of {})",
+                repo,
+                create_ascii_bar(percentage, 20),
+                percentage,
+                velocity.triaged,
+                velocity.issues
+            )
+        })
+        .collect();
+    create_ascii_box(&format!("Issues triaged within {}h", hours), &lines)
+}
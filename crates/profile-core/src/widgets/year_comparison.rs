@@ -0,0 +1,215 @@
+// Without `svg` the heatmap is only drawn as text; the SVG layout constants stay next to it.
+#![cfg_attr(not(feature = "svg"), allow(dead_code, unused_imports))]
+
+use crate::calendar::ContributionCalendar;
+use crate::config::{on_vacation, HeatmapTheme, Vacation};
+use crate::render::heatmap_glyph;
+use crate::sanitize::html_escape;
+use chrono::{Datelike, NaiveDate};
+
+const WEEKS: usize = 53;
+const CELL: usize = 10;
+const GAP: usize = 2;
+const LABEL_WIDTH: usize = 40;
+
+/// Marks a quiet week spent on vacation in the text heatmap.
+const VACATION_GLYPH: char = '~';
+
+/// Weekly totals of both years on a shared scale, plus the text summary below them.
+struct YearComparison {
+    this_year: i32,
+    this_weeks: Vec<u64>,
+    last_weeks: Vec<u64>,
+    this_vacation: Vec<bool>,
+    last_vacation: Vec<bool>,
+    current_week: usize,
+    max: u64,
+    this_total: u64,
+    last_total: u64,
+    last_year_total: u64,
+    last_same_day: NaiveDate,
+}
+
+/// Sums each year's contributions into week columns, Jan 1 starting week 0.
+fn weekly_totals(calendar: &ContributionCalendar, from: NaiveDate, to: NaiveDate) -> Vec<u64> {
+    let mut weeks = vec![0; WEEKS];
+    for (date, count) in calendar.range(from, to) {
+        weeks[(date.ordinal0() / 7) as usize] += count;
+    }
+    weeks
+}
+
+/// Which week columns of `year` were mostly (four days or more) spent on vacation.
+fn vacation_weeks(vacations: &[Vacation], year: i32) -> Vec<bool> {
+    let mut days = vec![0; WEEKS];
+    let mut date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    while date.year() == year {
+        if on_vacation(vacations, date) {
+            days[(date.ordinal0() / 7) as usize] += 1;
+        }
+        date = date.succ_opt().unwrap();
+    }
+    days.into_iter().map(|days| days >= 4).collect()
+}
+
+fn compare_years(
+    calendar: &ContributionCalendar,
+    today: NaiveDate,
+    vacations: &[Vacation],
+) -> YearComparison {
+    let this_start = NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap();
+    let last_start = NaiveDate::from_ymd_opt(today.year() - 1, 1, 1).unwrap();
+    let last_end = NaiveDate::from_ymd_opt(today.year() - 1, 12, 31).unwrap();
+    // Feb 29 has no counterpart last year; compare against Feb 28 instead.
+    let last_same_day = today
+        .with_year(today.year() - 1)
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(today.year() - 1, 2, 28).unwrap());
+
+    let this_weeks = weekly_totals(calendar, this_start, today);
+    let last_weeks = weekly_totals(calendar, last_start, last_end);
+    // A shared scale keeps the two rows comparable.
+    let max = this_weeks
+        .iter()
+        .chain(&last_weeks)
+        .copied()
+        .max()
+        .unwrap_or(0);
+
+    YearComparison {
+        this_year: today.year(),
+        this_weeks,
+        last_weeks,
+        this_vacation: vacation_weeks(vacations, today.year()),
+        last_vacation: vacation_weeks(vacations, today.year() - 1),
+        current_week: (today.ordinal0() / 7) as usize,
+        max,
+        this_total: calendar.total(this_start, today),
+        last_total: calendar.total(last_start, last_same_day),
+        last_year_total: calendar.total(last_start, last_end),
+        last_same_day,
+    }
+}
+
+fn format_summary(comparison: &YearComparison) -> String {
+    let mut output = format!(
+        "{} contributions so far this year vs {} by {} last year",
+        comparison.this_total,
+        comparison.last_total,
+        comparison.last_same_day.format("%b %-d")
+    );
+    if comparison.last_total > 0 {
+        let change = (comparison.this_total as f64 / comparison.last_total as f64 - 1.0) * 100.0;
+        output += &format!(" ({:+.0}%)", change);
+    }
+    output
+}
+
+/// Two heatmap rows, this year up to today above the same span of last year.
+pub fn format_year_comparison(
+    calendar: &ContributionCalendar,
+    today: NaiveDate,
+    theme: &HeatmapTheme,
+    vacations: &[Vacation],
+) -> String {
+    let comparison = compare_years(calendar, today, vacations);
+
+    let row = |weeks: &[u64], vacation: &[bool], until_week: usize| -> String {
+        weeks
+            .iter()
+            .enumerate()
+            .map(|(week, count)| match (week <= until_week, vacation[week]) {
+                (false, _) => ' ',
+                (true, true) if *count == 0 => VACATION_GLYPH,
+                (true, _) => heatmap_glyph(*count, comparison.max, theme),
+            })
+            .collect()
+    };
+
+    let mut output = format!(
+        "{} {} {:>6}\n",
+        comparison.this_year,
+        row(
+            &comparison.this_weeks,
+            &comparison.this_vacation,
+            comparison.current_week
+        ),
+        comparison.this_total
+    );
+    output += &format!(
+        "{} {} {:>6}\n",
+        comparison.this_year - 1,
+        row(&comparison.last_weeks, &comparison.last_vacation, WEEKS),
+        comparison.last_year_total
+    );
+    output += &format!("\n{}\n", format_summary(&comparison));
+    let shown_vacation = comparison.this_vacation[..=comparison.current_week]
+        .iter()
+        .chain(&comparison.last_vacation)
+        .any(|vacation| *vacation);
+    if shown_vacation {
+        output += &format!("{} quiet week on vacation\n", VACATION_GLYPH);
+    }
+    output
+}
+
+/// The same two rows as an SVG of coloured cells, with the summary as a caption.
+#[cfg(feature = "svg")]
+pub fn render_year_comparison_svg(
+    calendar: &ContributionCalendar,
+    today: NaiveDate,
+    theme: &HeatmapTheme,
+    vacations: &[Vacation],
+) -> String {
+    let comparison = compare_years(calendar, today, vacations);
+    let width = LABEL_WIDTH + WEEKS * (CELL + GAP);
+    let height = 2 * (CELL + GAP) + 24;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"monospace\" font-size=\"10\">\n",
+        width, height
+    );
+    let rows = [
+        (
+            comparison.this_year,
+            &comparison.this_weeks,
+            &comparison.this_vacation,
+            comparison.current_week,
+        ),
+        (
+            comparison.this_year - 1,
+            &comparison.last_weeks,
+            &comparison.last_vacation,
+            WEEKS,
+        ),
+    ];
+    for (row, (year, weeks, vacation, until_week)) in rows.iter().enumerate() {
+        let y = row * (CELL + GAP);
+        svg += &format!(
+            "  <text x=\"0\" y=\"{}\" fill=\"#8b949e\">{}</text>\n",
+            y + CELL - 1,
+            year
+        );
+        for (week, count) in weeks.iter().enumerate().take(until_week + 1) {
+            // Vacation weeks get a dashed outline, so a quiet one reads as time off.
+            let (outline, note) = match vacation[week] {
+                true => (" stroke=\"#8b949e\" stroke-dasharray=\"2\"", " (vacation)"),
+                false => ("", ""),
+            };
+            svg += &format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" rx=\"2\" fill=\"{}\"{}><title>{} contributions{}</title></rect>\n",
+                LABEL_WIDTH + week * (CELL + GAP),
+                y,
+                html_escape(theme.color(theme.level(*count, comparison.max))),
+                outline,
+                count,
+                note
+            );
+        }
+    }
+    svg += &format!(
+        "  <text x=\"0\" y=\"{}\" fill=\"#8b949e\">{}</text>\n</svg>\n",
+        height - 6,
+        format_summary(&comparison)
+    );
+    svg
+}
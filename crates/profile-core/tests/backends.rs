@@ -0,0 +1,86 @@
+//! Renders the shared fixture documents through every backend and compares the output with the
+//! snapshots in `tests/golden`, so a change to the document model can't silently break one
+//! backend. After an intended change, rewrite the snapshots with `UPDATE_GOLDEN=1 cargo test`.
+
+use profile_core::backend::ansi::{self, ColorDepth};
+#[cfg(feature = "svg")]
+use profile_core::backend::svg;
+use profile_core::backend::{html, markdown};
+use profile_core::document::Document;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const FIXTURES: [&str; 2] = ["profile", "minimal"];
+
+fn tests_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+fn load_fixture(name: &str) -> Document {
+    let path = tests_dir().join("fixtures").join(format!("{}.json", name));
+    let json = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("invalid {}: {}", path.display(), e))
+}
+
+fn assert_golden(file_name: &str, actual: &str) {
+    let path = tests_dir().join("golden").join(file_name);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}; create it with UPDATE_GOLDEN=1",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "{} no longer matches the output; rerun with UPDATE_GOLDEN=1 if the change is intended\n\
+         --- expected\n{}\n--- actual\n{}",
+        path.display(),
+        expected,
+        actual
+    );
+}
+
+/// Checks every fixture through one backend; `extension` names its snapshot files.
+fn check_backend(extension: &str, render: fn(&Document) -> String) {
+    for name in FIXTURES {
+        let document = load_fixture(name);
+        assert_golden(&format!("{}.{}", name, extension), &render(&document));
+    }
+}
+
+#[test]
+fn markdown_matches_golden() {
+    check_backend("md", markdown::render);
+}
+
+#[test]
+fn html_matches_golden() {
+    check_backend("html", html::render);
+}
+
+#[test]
+#[cfg(feature = "svg")]
+fn svg_matches_golden() {
+    check_backend("svg", svg::render);
+}
+
+#[test]
+fn ansi_matches_golden() {
+    check_backend("ansi", |document| {
+        ansi::render(document, ColorDepth::TrueColor)
+    });
+}
+
+#[test]
+fn ansi_16_matches_golden() {
+    check_backend("ansi16", |document| {
+        ansi::render(document, ColorDepth::Palette16)
+    });
+}
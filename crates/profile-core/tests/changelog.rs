@@ -0,0 +1,123 @@
+//! The changelog entries derived from two snapshots, and `commit_message`, whose subject must
+//! stay within `MAX_SUBJECT` however much changed.
+
+use chrono::NaiveDate;
+use profile_core::changelog::{append_changelog, commit_message, get_notable_changes, MAX_SUBJECT};
+use profile_core::config::ChangelogConfig;
+use profile_core::history::Snapshot;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+fn snapshot() -> Snapshot {
+    Snapshot::new(
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        &Value::Null,
+        0,
+        &[],
+    )
+}
+
+fn day(day: u32, stars: u64, followers: u64, languages: &[&str]) -> Snapshot {
+    let languages: Vec<(String, f64)> = languages
+        .iter()
+        .map(|lang| (lang.to_string(), 10.0))
+        .collect();
+    Snapshot::new(
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+        &json!({ "total_stars": stars }),
+        followers,
+        &languages,
+    )
+}
+
+/// The defaults, as an empty `[changelog]` table reads.
+fn config(path: PathBuf) -> ChangelogConfig {
+    let mut config: ChangelogConfig = toml::from_str("").unwrap();
+    config.path = path.to_string_lossy().into_owned();
+    config
+}
+
+/// A fresh directory for one test, so tests running in parallel don't share files.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("profile-changelog-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn only_the_highest_milestone_crossed_is_noted() {
+    let config = config(PathBuf::new());
+    let changes = get_notable_changes(&config, &day(1, 90, 9, &[]), &day(2, 260, 10, &[]));
+    assert_eq!(changes, ["crossed 10 followers", "crossed 250 stars"]);
+
+    let changes = get_notable_changes(&config, &day(1, 100, 10, &[]), &day(2, 120, 12, &[]));
+    assert!(changes.is_empty(), "{:?}", changes);
+}
+
+#[test]
+fn configured_milestones_and_metrics_replace_the_defaults() {
+    let mut config = config(PathBuf::new());
+    config.milestones = vec![200, 150];
+    config.metrics = vec!["total_stars".to_string()];
+    let changes = get_notable_changes(&config, &day(1, 90, 9, &[]), &day(2, 260, 10, &[]));
+    assert_eq!(changes, ["crossed 200 stars"]);
+}
+
+#[test]
+fn language_changes_are_noted_unless_backfilled() {
+    let config = config(PathBuf::new());
+    let changes = get_notable_changes(
+        &config,
+        &day(1, 0, 0, &["Rust", "Go"]),
+        &day(2, 0, 0, &["Rust", "Zig"]),
+    );
+    assert_eq!(
+        changes,
+        ["picked up Zig", "Go dropped out of the top languages"]
+    );
+
+    let changes = get_notable_changes(&config, &day(1, 0, 0, &[]), &day(2, 0, 0, &["Zig"]));
+    assert!(changes.is_empty(), "{:?}", changes);
+}
+
+#[test]
+fn reruns_on_the_same_day_add_nothing() {
+    let dir = scratch_dir("rerun");
+    let config = config(dir.join("CHANGELOG.md"));
+    let (previous, current) = (day(1, 90, 0, &[]), day(2, 110, 0, &[]));
+
+    append_changelog(&config, &previous, &current).unwrap();
+    let first = fs::read_to_string(&config.path).unwrap();
+    assert!(
+        first.ends_with("- 2024-01-02: crossed 100 stars\n"),
+        "{}",
+        first
+    );
+
+    append_changelog(&config, &previous, &current).unwrap();
+    assert_eq!(fs::read_to_string(&config.path).unwrap(), first);
+}
+
+#[test]
+fn long_subjects_are_cut_within_the_limit() {
+    // Section names of every length, so some cut lands right at the limit.
+    for len in 1..=30 {
+        let sections: BTreeSet<String> = (0..40)
+            .map(|i| format!("{:02}{}", i, "x".repeat(len)))
+            .collect();
+        let message = commit_message(None, &snapshot(), 0, &sections);
+        assert!(message.chars().count() <= MAX_SUBJECT, "{}", message);
+        assert!(message.ends_with(", …"), "{}", message);
+    }
+}
+
+#[test]
+fn subjects_that_fit_are_not_cut() {
+    let sections = BTreeSet::from(["stats".to_string(), "activity".to_string()]);
+    let message = commit_message(None, &snapshot(), 0, &sections);
+    assert_eq!(message, "🔄 Update README: updated activity, stats");
+}
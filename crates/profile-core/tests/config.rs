@@ -0,0 +1,131 @@
+//! `load_config` rejecting values that would otherwise fail in the middle of a run, and the
+//! schema editors check config files against.
+
+use profile_core::config::{config_schema, find_config, load_config, ConfigSource, CONFIG_ENV};
+use profile_core::remote::{install_fetcher, sha256_hex};
+use std::fs;
+use std::sync::Once;
+
+const REMOTE_CONFIG: &str = "username = \"octocat\"\n";
+
+/// Serves [`REMOTE_CONFIG`] for every remote source instead of going online.
+fn install_test_fetcher() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| install_fetcher(|_| Ok(REMOTE_CONFIG.to_string())).unwrap());
+}
+
+fn remote(path: &str, sha256: Option<String>) -> ConfigSource {
+    ConfigSource {
+        path: path.to_string(),
+        explicit: true,
+        sha256,
+    }
+}
+
+fn load(name: &str, contents: &str) -> Result<profile_core::config::Config, String> {
+    let dir = std::env::temp_dir().join(format!("profile-config-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("profile.toml");
+    fs::write(&path, contents).unwrap();
+    load_config(&find_config(path.to_str()), None)
+}
+
+#[test]
+fn unknown_timezone_is_an_error_naming_it() {
+    let error = load(
+        "bad-timezone",
+        "username = \"octocat\"\ntimezone = \"Europe/Berln\"\n",
+    )
+    .unwrap_err();
+    assert!(error.contains("Europe/Berln"), "{}", error);
+}
+
+#[test]
+fn known_timezone_loads() {
+    let config = load(
+        "timezone",
+        "username = \"octocat\"\ntimezone = \"Europe/Vienna\"\n",
+    )
+    .unwrap();
+    assert!(config.timezone().is_some());
+}
+
+#[test]
+fn short_heatmap_palette_is_an_error() {
+    let error = load(
+        "short-palette",
+        "[theme.heatmap]\ncolors = [\"#000000\", \"#111111\"]\n",
+    )
+    .unwrap_err();
+    assert!(error.contains("heatmap theme needs 5 colours"), "{}", error);
+}
+
+#[test]
+fn missing_config_named_by_flag_is_an_error() {
+    let error = load_config(&find_config(Some("no-such-dir/profle.toml")), None).unwrap_err();
+    assert_eq!(error, "config file no-such-dir/profle.toml not found");
+}
+
+#[test]
+fn missing_config_named_by_environment_is_an_error() {
+    std::env::set_var(CONFIG_ENV, "no-such-dir/from-env.toml");
+    let source = find_config(None);
+    std::env::remove_var(CONFIG_ENV);
+    assert!(source.explicit);
+    let error = load_config(&source, None).unwrap_err();
+    assert_eq!(error, "config file no-such-dir/from-env.toml not found");
+}
+
+#[test]
+fn missing_implicit_config_loads_the_defaults() {
+    let source = ConfigSource {
+        path: "no-such-dir/profile.toml".to_string(),
+        explicit: false,
+        sha256: None,
+    };
+    assert!(load_config(&source, None).is_ok());
+}
+
+#[test]
+fn remote_config_named_by_flag_is_fetched() {
+    install_test_fetcher();
+    for path in [
+        "https://example.com/profile.toml",
+        "github:octocat/dotfiles/profile.toml@main",
+    ] {
+        let config = load_config(&remote(path, None), None).unwrap();
+        assert_eq!(config.username, "octocat", "{}", path);
+    }
+}
+
+#[test]
+fn remote_config_is_checked_against_its_pin() {
+    install_test_fetcher();
+    let path = "https://example.com/profile.toml";
+    assert!(load_config(&remote(path, Some(sha256_hex(REMOTE_CONFIG))), None).is_ok());
+    let error = load_config(&remote(path, Some("00".repeat(32))), None).unwrap_err();
+    assert!(error.contains("pinned checksum"), "{}", error);
+}
+
+#[test]
+fn plain_http_config_needs_a_pin() {
+    install_test_fetcher();
+    let path = "http://example.com/profile.toml";
+    let error = load_config(&remote(path, None), None).unwrap_err();
+    assert!(error.contains("plain HTTP"), "{}", error);
+    assert!(load_config(&remote(path, Some(sha256_hex(REMOTE_CONFIG))), None).is_ok());
+}
+
+#[test]
+fn schema_declares_the_keys_taken_out_before_parsing() {
+    let schema: serde_json::Value = serde_json::from_str(&config_schema()).unwrap();
+    for key in [
+        "extends",
+        "extends_sha256",
+        "profile",
+        "metrics",
+        "username",
+    ] {
+        assert!(schema["properties"].get(key).is_some(), "{}", key);
+    }
+}
@@ -0,0 +1,45 @@
+//! Which GraphQL documents a custom widget may send.
+
+use profile_core::widgets::custom::validate_query;
+
+#[test]
+fn plain_queries_are_allowed() {
+    assert!(validate_query("{ viewer { login } }").is_ok());
+    assert!(
+        validate_query("query Stars { viewer { starredRepositories { totalCount } } }").is_ok()
+    );
+    assert!(validate_query(
+        "fragment Repo on Repository { name } query { viewer { repositories(first: 5) { nodes { ...Repo } } } }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn mutations_and_subscriptions_are_rejected_anywhere() {
+    for query in [
+        "mutation { addStar(input: {starrableId: \"x\"}) { clientMutationId } }",
+        "query { viewer { login } } mutation { addStar(input: {starrableId: \"x\"}) { clientMutationId } }",
+        "subscription { event { id } }",
+        "{ viewer { login } } subscription { event { id } }",
+        "{ viewer { login } } mutation",
+    ] {
+        assert!(validate_query(query).is_err(), "{}", query);
+    }
+}
+
+#[test]
+fn escaped_quotes_stay_inside_strings() {
+    let query =
+        r#"{ search(query: "say \"}\" here", type: REPOSITORY, first: 1) { repositoryCount } }"#;
+    assert!(validate_query(query).is_ok());
+    let hidden =
+        r#"{ search(query: "\"", type: REPOSITORY, first: 1) { repositoryCount } } mutation { x }"#;
+    assert!(validate_query(hidden).is_err());
+}
+
+#[test]
+fn unbalanced_documents_are_rejected() {
+    assert!(validate_query("{ viewer { login }").is_err());
+    assert!(validate_query("{ viewer { login } } }").is_err());
+    assert!(validate_query("{ search(query: \"open) { x } }").is_err());
+}
@@ -0,0 +1,32 @@
+//! `load_history`, which must not turn a damaged history file into an empty one.
+
+use profile_core::history::load_history;
+use std::fs;
+use std::path::PathBuf;
+
+/// A fresh directory for one test, so tests running in parallel don't share files.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("profile-history-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn missing_file_is_an_empty_history() {
+    let dir = scratch_dir("missing");
+    let history = load_history(&dir.join("history.json")).unwrap();
+    assert!(history.snapshots.is_empty());
+}
+
+#[test]
+fn malformed_file_is_an_error_and_left_alone() {
+    let dir = scratch_dir("malformed");
+    let path = dir.join("history.json");
+    let contents = r#"{"snapshots": [{"date": "2024-01-01", "metrics": {"#;
+    fs::write(&path, contents).unwrap();
+
+    let error = load_history(&path).unwrap_err();
+    assert!(error.contains("not a valid history file"), "{}", error);
+    assert_eq!(fs::read_to_string(&path).unwrap(), contents);
+}
@@ -0,0 +1,35 @@
+//! `project`, which places the location pin on the world map grid.
+
+use profile_core::render::map::{project, render_world_map};
+
+/// Rows and columns of the map.
+fn size() -> (usize, usize) {
+    let map = render_world_map(0.0, 0.0);
+    (map.len(), map[0].chars().count())
+}
+
+#[test]
+fn corners_map_to_the_grid_corners() {
+    let (height, width) = size();
+    assert_eq!(project(80.0, -180.0), (0, 0));
+    assert_eq!(project(80.0, 180.0), (0, width - 1));
+    assert_eq!(project(-60.0, -180.0), (height - 1, 0));
+    assert_eq!(project(-60.0, 180.0), (height - 1, width - 1));
+}
+
+#[test]
+fn equator_and_prime_meridian() {
+    let (height, width) = size();
+    // 80° of the map's 140° lie north of the equator.
+    assert_eq!(project(0.0, 0.0), (80 * height / 140, width / 2));
+    assert_eq!(project(0.0, -180.0).0, project(0.0, 179.0).0);
+    assert_eq!(project(79.0, 0.0).1, project(-59.0, 0.0).1);
+}
+
+#[test]
+fn out_of_range_coordinates_are_clamped() {
+    let (height, width) = size();
+    assert_eq!(project(90.0, -200.0), (0, 0));
+    assert_eq!(project(-90.0, 200.0), (height - 1, width - 1));
+    assert_eq!(project(-85.0, 0.0), project(-60.0, 0.0));
+}
@@ -0,0 +1,52 @@
+//! `normalize_percentages`, whose rows have to add up to exactly 100 whatever the rounding.
+
+use profile_core::percent::normalize_percentages;
+
+/// The sum in whole units of the last decimal, so float error can't hide a missing unit.
+fn units(percentages: &[f64], decimals: u32) -> u64 {
+    let scale = 10u64.pow(decimals) as f64;
+    percentages
+        .iter()
+        .map(|percentage| (percentage * scale).round() as u64)
+        .sum()
+}
+
+#[test]
+fn adds_up_to_100_without_decimals() {
+    let shares = [3.0, 7.0, 11.0, 13.0, 17.0];
+    let percentages = normalize_percentages(&shares, 0);
+    assert_eq!(units(&percentages, 0), 100);
+    assert_eq!(percentages, [6.0, 14.0, 22.0, 25.0, 33.0]);
+}
+
+#[test]
+fn adds_up_to_100_with_one_decimal() {
+    let shares = [3.0, 7.0, 11.0, 13.0, 17.0];
+    let percentages = normalize_percentages(&shares, 1);
+    assert_eq!(units(&percentages, 1), 1000);
+    assert_eq!(percentages, [5.9, 13.7, 21.6, 25.5, 33.3]);
+}
+
+#[test]
+fn earlier_values_win_ties() {
+    assert_eq!(
+        normalize_percentages(&[1.0, 1.0, 1.0], 0),
+        [34.0, 33.0, 33.0]
+    );
+    assert_eq!(
+        normalize_percentages(&[1.0, 1.0, 1.0], 1),
+        [33.4, 33.3, 33.3]
+    );
+}
+
+#[test]
+fn all_zero_input_stays_zero() {
+    assert_eq!(normalize_percentages(&[0.0, 0.0], 1), [0.0, 0.0]);
+    assert!(normalize_percentages(&[], 1).is_empty());
+}
+
+#[test]
+fn a_single_entry_gets_everything() {
+    assert_eq!(normalize_percentages(&[42.0], 0), [100.0]);
+    assert_eq!(normalize_percentages(&[0.5], 1), [100.0]);
+}
@@ -0,0 +1,51 @@
+//! Page-size estimates and the `rateLimit` field added to outgoing GraphQL queries.
+
+use profile_core::query_budget::{estimate_nodes, fit_node_limit, with_rate_limit, MAX_NODES};
+
+#[test]
+fn nested_connections_multiply() {
+    let query = "{ user(login: \"a\") { repositories(first: 100) { nodes { issues(first: 50) { totalCount } } } } }";
+    assert_eq!(estimate_nodes(query), 100 + 100 * 50);
+}
+
+#[test]
+fn oversized_queries_are_cut_to_fit() {
+    let query = "{ a(first: 100) { b(first: 100) { c(first: 100) { id } } } }";
+    let (fitted, note) = fit_node_limit(query);
+    assert!(estimate_nodes(&fitted) <= MAX_NODES);
+    assert!(note.is_some());
+}
+
+#[test]
+fn rate_limit_goes_into_the_operation() {
+    let (query, added) = with_rate_limit("query { viewer { login } }");
+    assert!(added);
+    assert_eq!(
+        query,
+        "query { rateLimit { cost remaining } viewer { login } }"
+    );
+}
+
+#[test]
+fn rate_limit_skips_fragments_defined_first() {
+    let query = "fragment Repo on Repository { name } query { viewer { repositories(first: 5) { nodes { ...Repo } } } }";
+    let (query, added) = with_rate_limit(query);
+    assert!(added);
+    assert_eq!(
+        query,
+        "fragment Repo on Repository { name } query { rateLimit { cost remaining } viewer { repositories(first: 5) { nodes { ...Repo } } } }"
+    );
+}
+
+#[test]
+fn rate_limit_already_asked_for_is_kept() {
+    let query = "{ rateLimit { cost } viewer { login } }";
+    assert_eq!(with_rate_limit(query), (query.to_string(), false));
+}
+
+#[test]
+fn non_ascii_comments_are_skipped_over() {
+    let query = "query { viewer { login } } # café";
+    assert_eq!(fit_node_limit(query), (query.to_string(), None));
+    assert_eq!(estimate_nodes("# übersicht\n{ a(first: 10) { id } }"), 10);
+}
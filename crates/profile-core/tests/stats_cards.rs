@@ -0,0 +1,32 @@
+//! Changes shown on the stats cards, which compare fetched numbers even when one is overridden.
+
+use chrono::NaiveDate;
+use profile_core::document::Block;
+use profile_core::history::Snapshot;
+use profile_core::profile::format_stats_cards;
+use serde_json::json;
+
+fn cards(stats: serde_json::Value) -> String {
+    let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let previous = Snapshot::new(date, &json!({ "total_stars": 40 }), 0, &[]);
+    match format_stats_cards(&stats, Some(&previous), 200) {
+        Block::Art(art) => art,
+        _ => panic!("stats cards are art"),
+    }
+}
+
+#[test]
+fn delta_follows_the_fetched_number() {
+    assert!(cards(json!({ "total_stars": 45 })).contains("▲ 5"));
+}
+
+#[test]
+fn overridden_card_compares_what_was_fetched() {
+    let art = cards(json!({
+        "total_stars": 500,
+        "overrides": { "total_stars": { "fetched": 42, "note": null } },
+    }));
+    assert!(art.contains("500*"));
+    assert!(art.contains("▲ 2"));
+    assert!(!art.contains("▲ 460"));
+}
@@ -0,0 +1,1055 @@
+//! Builds the profile from the fetched core data: the header, every configured widget and
+//! the footer, laid out by the rules. The widgets fetch their own data as they go; loading the
+//! config and history, the checks and writing the output are left to the caller.
+
+use crate::calendar::get_contribution_calendar;
+use crate::scrape::Scraper;
+use crate::widgets::achievements::get_achievements;
+use crate::widgets::contributors::get_contributors;
+use crate::widgets::custom::get_custom_widget;
+use crate::widgets::digest::get_weekly_digest;
+use crate::widgets::discussions::get_discussion_stats;
+use crate::widgets::events::{event_window, get_event_progress};
+use crate::widgets::keys::get_keys;
+use crate::widgets::mentorship::find_missing_links;
+use crate::widgets::pr_sizes::get_pr_sizes;
+use crate::widgets::proofs::unresolved_proofs;
+use crate::widgets::releases::get_latest_release;
+use crate::widgets::responsiveness::get_responsiveness;
+use crate::widgets::review_balance::get_review_balance;
+use crate::widgets::spotlight::get_spotlight;
+use crate::widgets::stargazers::get_stargazer_facts;
+use crate::widgets::timeline::get_repos_per_year;
+use crate::widgets::triage::get_triage_velocity;
+use chrono::prelude::*;
+use profile_core::anomaly::report_anomaly;
+use profile_core::art::{crop_art, is_braille, mirror_art, scale_art};
+use profile_core::builder::ProfileBuilder;
+use profile_core::cache::track_data_age;
+#[cfg(feature = "svg")]
+use profile_core::config::OutputMode;
+use profile_core::config::{CelebrationsConfig, Config, HeaderBadge, LinksConfig, StatsStyle};
+#[cfg(feature = "svg")]
+use profile_core::document::Image;
+use profile_core::document::{Block, Section};
+use profile_core::history::History;
+use profile_core::overrides::{format_overridden, OVERRIDES_KEY};
+use profile_core::profile::{
+    format_activity, format_github_stats, format_languages, format_languages_compact,
+    format_stats_cards,
+};
+use profile_core::remote::read_source;
+#[cfg(feature = "svg")]
+use profile_core::render::avatar::{render_avatar_frame, render_favicon};
+use profile_core::render::create_ascii_badge;
+#[cfg(feature = "svg")]
+use profile_core::render::life::render_life_svg;
+use profile_core::render::map::{format_coordinates, render_world_map};
+#[cfg(feature = "audio")]
+use profile_core::render::melody::render_melody_wav;
+#[cfg(feature = "svg")]
+use profile_core::render::odometer::render_odometer_svg;
+use profile_core::render::odometer::stat_changes;
+use profile_core::render::qr::render_qr;
+#[cfg(any(feature = "svg", feature = "audio"))]
+use profile_core::render::{Asset, ASSETS_DIR};
+use profile_core::rules::{apply_rules, rule_metrics, LayoutChanges};
+use profile_core::sanitize::fence_safe;
+use profile_core::widget_cache::WidgetCache;
+use profile_core::widgets::about::format_about;
+use profile_core::widgets::achievements::format_achievements;
+use profile_core::widgets::celebrations::{format_celebrations, get_celebrations, Celebration};
+use profile_core::widgets::clock::{city_from_timezone, format_clock};
+use profile_core::widgets::contributors::format_contributors;
+use profile_core::widgets::digest::{format_weekly_digest, last_week_start};
+use profile_core::widgets::discussions::format_discussion_stats;
+use profile_core::widgets::eta::format_eta;
+use profile_core::widgets::events::format_event_progress;
+use profile_core::widgets::goals::{format_goals, get_goal_progress};
+use profile_core::widgets::growth::format_growth;
+use profile_core::widgets::header::compose_header;
+use profile_core::widgets::keys::format_keys;
+use profile_core::widgets::mentorship::format_mentorship;
+use profile_core::widgets::pr_sizes::format_pr_sizes;
+use profile_core::widgets::proofs::{format_proofs, proof_claims};
+use profile_core::widgets::releases::format_release;
+use profile_core::widgets::responsiveness::format_responsiveness;
+use profile_core::widgets::review_balance::format_review_balance;
+use profile_core::widgets::spotlight::format_spotlight;
+use profile_core::widgets::stargazers::format_stargazer_facts;
+use profile_core::widgets::talks::{format_talks, load_talks};
+use profile_core::widgets::timeline::format_repos_per_year;
+use profile_core::widgets::triage::format_triage_velocity;
+use profile_core::widgets::year_comparison::format_year_comparison;
+#[cfg(feature = "svg")]
+use profile_core::widgets::year_comparison::render_year_comparison_svg;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// The stats diff animation, under [`ASSETS_DIR`].
+#[cfg(feature = "svg")]
+pub const DIFF_ANIMATION_FILE: &str = "stats-diff.svg";
+
+/// Width of the stats table, which the QR code is right-aligned to.
+const QR_CORNER_WIDTH: usize = 96;
+
+/// Runs the named fetch steps of a run and remembers how old the data each one used is, which
+/// dates the sections built from it.
+pub trait Steps {
+    fn step<T>(&self, name: &str, fetch: impl FnOnce() -> T) -> T;
+
+    /// When the oldest data behind `steps` was fetched, ignoring steps that haven't run.
+    fn fetched_at(&self, steps: &[&str]) -> Option<DateTime<Utc>>;
+}
+
+/// [`Steps`] that reports nothing, for callers without a terminal to report to.
+#[derive(Default)]
+pub struct QuietSteps {
+    fetched: RefCell<BTreeMap<String, DateTime<Utc>>>,
+}
+
+impl Steps for QuietSteps {
+    fn step<T>(&self, name: &str, fetch: impl FnOnce() -> T) -> T {
+        let (result, fetched_at) = track_data_age(fetch);
+        self.fetched
+            .borrow_mut()
+            .insert(name.to_string(), fetched_at);
+        result
+    }
+
+    fn fetched_at(&self, steps: &[&str]) -> Option<DateTime<Utc>> {
+        let fetched = self.fetched.borrow();
+        steps
+            .iter()
+            .filter_map(|step| fetched.get(*step))
+            .min()
+            .copied()
+    }
+}
+
+/// What the core sections render from, as the [`crate::fetch`] functions return it. Languages
+/// and stats that couldn't be fetched hold the error, shown in place of their section.
+pub struct CoreData {
+    pub activities: Vec<Value>,
+    pub languages: Result<Vec<(String, f64)>, String>,
+    /// With the overrides already applied, see [`crate::overrides`].
+    pub stats: Result<Value, String>,
+    pub followers: u64,
+}
+
+/// The profile before it is built, and what the caller needs afterwards for its checks and
+/// notifications.
+pub struct Assembled<'a> {
+    pub profile: ProfileBuilder,
+    /// Prose nobody proofreads before it is published, by where it appears, for the spell-check.
+    pub prose: Vec<(String, String)>,
+    /// Shown in the profile; the caller announces them once the README is written.
+    pub celebrations: Option<(&'a CelebrationsConfig, Vec<Celebration>)>,
+    /// The stats that changed since the previous snapshot, as `(metric, before, after)`.
+    pub stat_diff: Vec<(&'static str, u64, u64)>,
+}
+
+/// Reports a widget that failed and, unless `layout.omit_failed` is set, keeps its slot with a
+/// placeholder, so the sections around it stay where they were and the owner sees it too.
+fn skip_widget(
+    profile: ProfileBuilder,
+    config: &Config,
+    title: &str,
+    message: String,
+) -> ProfileBuilder {
+    report_anomaly(message);
+    match config.layout.omit_failed {
+        true => profile,
+        false => profile.with_section(Section::unavailable(title)),
+    }
+}
+
+/// [`skip_widget`] for a widget shown inside another section: the placeholder goes at the end
+/// of that section's `blocks`.
+fn skip_part(blocks: &mut Vec<Block>, config: &Config, message: String) {
+    report_anomaly(message);
+    if !config.layout.omit_failed {
+        blocks.push(Block::unavailable());
+    }
+}
+
+/// One generation run: the config to build, the token to fetch with and when it happens.
+pub struct Run<'a, S> {
+    pub config: &'a Config,
+    /// Empty for an unauthenticated preview.
+    pub token: &'a str,
+    /// In the profile's timezone.
+    pub now: DateTime<FixedOffset>,
+    pub steps: &'a S,
+}
+
+/// Builds the profile for `run`. `status` goes right under the header. Updates the weekly
+/// digest and the spotlight rotation in `history`, which must already hold today's snapshot,
+/// and renders through `widgets` so unchanged sections are reused.
+pub fn assemble<'a, S: Steps>(
+    run: &Run<'a, S>,
+    data: &CoreData,
+    status: Option<Block>,
+    history: &mut History,
+    widgets: &mut WidgetCache,
+) -> Assembled<'a> {
+    let Run {
+        config,
+        token,
+        now,
+        steps,
+    } = *run;
+    let username = config.username.as_str();
+    let today = now.date_naive();
+    let CoreData {
+        activities,
+        languages: top_languages,
+        stats: github_stats,
+        followers: github_followers,
+    } = data;
+    let github_followers = *github_followers;
+    // Rules only see fetched numbers; a failed fetch isn't a count of zero.
+    let layout_changes = match (github_stats, top_languages) {
+        (Ok(stats), Ok(languages)) => apply_rules(
+            &config.rules,
+            &rule_metrics(stats, github_followers, languages),
+        ),
+        _ => LayoutChanges::default(),
+    };
+
+    if config.weekly_digest {
+        let week_start = last_week_start(today);
+        if history.digest.as_ref().map(|digest| digest.week_start) != Some(week_start) {
+            match steps.step("Weekly digest", || {
+                get_weekly_digest(username, token, history, week_start)
+            }) {
+                Ok(digest) => history.digest = Some(digest),
+                Err(e) => report_anomaly(format!("Failed to compute weekly digest: {}", e)),
+            }
+        }
+    }
+
+    // Step 4: Generate ASCII art header and badges
+    let figure = r#"
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⣠⣤⣄⡀⠀⠀⠀⣀⣠⣀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⢀⣄⣀⣀⣀⠀⠀⠀⠀⠀⠀⣀⣠⣾⠏⠉⠙⢿⣶⡾⠟⠛⠉⠻⣷⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⢰⣿⠋⠉⠙⠛⠿⣶⣶⠿⠿⠟⢻⣿⠃⠀⢠⣴⣤⣿⣧⣄⡀⣀⣀⣿⡆⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⣿⡏⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢸⣏⠀⠀⢻⣧⡿⠋⠉⠉⢿⣟⠉⠙⠻⣧⠀⠀⠀⠀
+⠀⠀⠀⠀⢻⣧⣀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⢿⣦⣤⣤⣿⣷⡀⠀⢀⣾⣿⡧⠀⢀⣿⠀⠀⠀⠀
+⠀⠀⠀⠀⢘⣿⠏⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠉⠛⠿⣿⡛⠉⠁⣠⣿⡇⠀⠀⠀⠀
+⠀⠀⠀⠀⣾⡏⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⠛⠿⠟⠋⠘⣿⠀⠀⠀⠀
+⠀⠀⠀⢠⣿⠄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠠⠀⠀⠀⠀⣤⡶⣿⡷⠶⠶⠆
+⠀⣀⣠⣼⣿⣤⣤⠀⠀⠀⣠⣦⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⣾⣿⡄⠀⠀⠀⣀⣿⣇⡀⠀⠀
+⠈⠉⠉⣴⠟⠻⣷⡄⠀⢰⣿⡿⠃⠀⠀⠀⠀⣴⣷⣤⠀⠀⠀⠀⠙⠻⠗⠀⠀⠀⢩⣿⠉⠉⠉⠀
+⢀⣤⣶⣿⡄⠀⠸⣷⣀⣀⡀⠀⠀⠀⠀⠀⠀⠿⠶⠟⠀⠀⠀⠀⠀⠀⠀⠀⠀⣻⣿⣷⣤⣀⠀⠀
+⢺⡇⠀⠈⠑⠀⠀⠉⠉⠙⠻⣷⡄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⣀⣤⣾⠟⠁⠀⠈⠉⠀⠀
+⠈⠻⢷⣦⡀⠀⣠⡶⠾⠆⠀⠘⣿⣤⣤⣤⣤⣤⣤⣤⣤⣤⣴⣶⢶⣿⡿⣭⡀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⢹⣇⠀⢿⣧⣠⣾⠇⢠⣿⠃⠉⢿⣍⣉⣉⣩⡟⠁⠸⣧⣼⡟⣁⣼⠇⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠈⢿⣦⣄⣉⣉⣠⣴⣿⣏⠀⠀⠀⠈⠉⠉⠁⠀⠀⠀⣹⡟⠛⠋⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠈⠙⠛⠛⠛⠉⠀⠹⣷⠦⣤⣀⣀⣀⣀⣤⡴⣺⠟⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⠳⢤⣈⡽⢿⣅⣤⠾⠃⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀
+    "#;
+    let figure = match &config.header.art {
+        Some(source) => {
+            read_source(source, config.header.art_sha256.as_deref()).unwrap_or_else(|e| {
+                report_anomaly(format!("Using the built-in header art: {}", e));
+                figure.to_string()
+            })
+        }
+        None => figure.to_string(),
+    };
+    let mut art: Vec<String> = figure
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    art = crop_art(&art, &config.header.art_crop);
+    if config.header.art_mirror || config.header.art_width.is_some() {
+        if is_braille(&art) {
+            if config.header.art_mirror {
+                art = mirror_art(&art);
+            }
+            if let Some(columns) = config.header.art_width {
+                art = scale_art(&art, columns);
+            }
+        } else {
+            report_anomaly(
+                "header.art_mirror and header.art_width need braille art; ignoring them",
+            );
+        }
+    }
+
+    // The streak badge, the calendar widgets and the extras share one calendar request.
+    let calendar = if config.year_comparison
+        || config.header.badges.contains(&HeaderBadge::Streak)
+        || config.life
+        || config.extras.avatar_frame
+        || config.extras.melody
+    {
+        let from = NaiveDate::from_ymd_opt(today.year() - 1, 1, 1).unwrap();
+        steps
+            .step("Contribution calendar", || {
+                get_contribution_calendar(username, token, from, today)
+            })
+            .map_err(|e| report_anomaly(format!("Skipping contribution calendar: {}", e)))
+            .ok()
+    } else {
+        None
+    };
+
+    let mut badges = Vec::new();
+    for badge in &config.header.badges {
+        if layout_changes.hidden_badges.contains(badge) {
+            continue;
+        }
+        let (label, value, overridden) = match badge {
+            HeaderBadge::Followers => (
+                "Followers",
+                github_followers,
+                github_stats
+                    .as_ref()
+                    .is_ok_and(|stats| stats[OVERRIDES_KEY].get("followers").is_some()),
+            ),
+            HeaderBadge::Stars => match github_stats {
+                Ok(stats) => (
+                    "Stars",
+                    stats["total_stars"].as_u64().unwrap_or(0),
+                    stats[OVERRIDES_KEY].get("total_stars").is_some(),
+                ),
+                Err(_) => continue,
+            },
+            HeaderBadge::Streak => match &calendar {
+                Some(calendar) => (
+                    "Streak",
+                    calendar.current_streak(today, &config.vacations),
+                    false,
+                ),
+                None => continue,
+            },
+        };
+        let value = config
+            .theme
+            .badges
+            .get(badge)
+            .map_or_else(|| value.to_string(), |theme| theme.format_value(value));
+        // Notes are too long for a badge; the `*` still marks the number as set by hand.
+        let value = format_overridden(&value, None, overridden);
+        badges.push(
+            create_ascii_badge(label, &value, 20)
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        );
+    }
+    if let Some(clock_config) = &config.clock {
+        let city = clock_config
+            .city
+            .clone()
+            .unwrap_or_else(|| city_from_timezone(config.timezone.as_deref().unwrap_or("Local")));
+        badges.push(format_clock(&now, &city));
+    }
+    let art = compose_header(config.header.layout, &art, &badges);
+
+    // Prose nobody proofreads before it is published, by where it appears, for the spell-check.
+    let mut prose: Vec<(String, String)> = Vec::new();
+    let tagline = "We are <b>those</b>, the <b>Different<b/>. Technological rats, swimming in the <i>ocean of information</i>.";
+    let credits = "This README is <b>auto-generated</b> with Rust and Actions - Credits to the original creater <a href=\"https://github.com/vxfemboy/vxfemboy/\">@vxfemboy</a>";
+    prose.push(("the header".to_string(), tagline.to_string()));
+    prose.push(("the footer".to_string(), credits.to_string()));
+
+    let mut header = config
+        .style
+        .header
+        .apply(vec![Block::Art(art), Block::centered(tagline)]);
+    header.push(Block::Rule);
+    let mut profile = ProfileBuilder::new(username)
+        .with_header(header)
+        .with_art_color(config.header.art_color.clone());
+
+    if let Some(status) = status {
+        profile = profile.with_section(Section::untitled(vec![status]));
+    }
+
+    // Announced by the caller, once the README with them is written.
+    let celebrations = config.celebrations.as_ref().map(|celebrations_config| {
+        (
+            celebrations_config,
+            get_celebrations(celebrations_config, history, today),
+        )
+    });
+    if let Some((_, celebrations)) = celebrations
+        .as_ref()
+        .filter(|(_, celebrations)| !celebrations.is_empty())
+    {
+        profile = profile.with_section(Section::untitled(vec![format_celebrations(celebrations)]));
+    }
+
+    if let Some(about) = &config.about {
+        profile = profile.with_section(Section::fenced("About", &format_about(about)));
+    }
+
+    if let Some(location) = &config.location {
+        let label = location
+            .label
+            .as_deref()
+            .or_else(|| config.about.as_ref()?.location.as_deref());
+        let coordinates = format_coordinates(location.latitude, location.longitude);
+
+        let mut map = render_world_map(location.latitude, location.longitude).join("\n");
+        map += "\n";
+        map += &match label {
+            Some(label) => format!("◉ {} ({})\n", fence_safe(label), coordinates),
+            None => format!("◉ {}\n", coordinates),
+        };
+        profile = profile.with_section(Section::fenced("Location", &map));
+    }
+
+    // Dates `section` by the oldest data the given fetch steps used, in the profile's timezone,
+    // and marks it stale if that is older than the freshness policy allows.
+    let dated = |section: Section, names: &[&str]| {
+        let Some(fetched_at) = steps.fetched_at(names) else {
+            return section;
+        };
+        let fetched_at = fetched_at.with_timezone(&now.timezone());
+        let title = section.title.clone().unwrap_or_default();
+        let stale = config
+            .freshness
+            .max_age(&title)
+            .is_some_and(|max_age| now - fetched_at > max_age);
+        if stale {
+            report_anomaly(format!(
+                "{} shows stale data from {}",
+                title,
+                fetched_at.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        Section {
+            stale,
+            ..section.with_as_of(Some(fetched_at.date_naive()))
+        }
+    };
+
+    match top_languages {
+        Ok(top_languages) => {
+            profile = profile.with_section(dated(
+                Section::new(
+                    "Languages",
+                    vec![match layout_changes.compact_languages {
+                        true => widgets.section("languages_compact", &top_languages, || {
+                            format_languages_compact(top_languages)
+                        }),
+                        false => widgets.section("languages", &top_languages, || {
+                            format_languages(top_languages)
+                        }),
+                    }],
+                ),
+                &["Languages"],
+            ));
+        }
+        Err(e) => {
+            profile = skip_widget(
+                profile,
+                config,
+                "Languages",
+                format!("Skipping languages: {}", e),
+            );
+        }
+    }
+
+    match github_stats {
+        Ok(github_stats) => {
+            let mut stats = vec![match config.stats.style {
+                StatsStyle::Table => {
+                    widgets.section("stats", &github_stats, || format_github_stats(github_stats))
+                }
+                StatsStyle::Cards => {
+                    let previous = history.previous(today);
+                    widgets.section("stats_cards", &(&github_stats, previous), || {
+                        format_stats_cards(github_stats, previous, config.stats.max_width)
+                    })
+                }
+            }];
+            if let Some(stargazers_config) = &config.stargazers {
+                match steps.step("Stargazers", || {
+                    get_stargazer_facts(stargazers_config, username, token)
+                }) {
+                    Ok(facts) => stats.push(Block::Art(format_stargazer_facts(&facts))),
+                    Err(e) => skip_part(
+                        &mut stats,
+                        config,
+                        format!("Skipping stargazer facts: {}", e),
+                    ),
+                }
+            }
+            profile = profile.with_section(dated(
+                Section::new("Stats", stats),
+                &["Stats", "Stargazers"],
+            ));
+        }
+        Err(e) => {
+            profile = skip_widget(profile, config, "Stats", format!("Skipping stats: {}", e));
+        }
+    }
+
+    if let Some(calendar) = calendar.as_ref().filter(|_| config.year_comparison) {
+        let theme = &config.theme.heatmap;
+        let title = "This year vs last year";
+        match config.mode {
+            #[cfg(feature = "svg")]
+            OutputMode::Html => {
+                let path = format!("{}/year-comparison.svg", ASSETS_DIR);
+                profile = profile.with_section(dated(
+                    Section::new(
+                        title,
+                        vec![Block::Images {
+                            caption: None,
+                            images: vec![Image::new(&path, title)],
+                        }],
+                    ),
+                    &["Contribution calendar"],
+                ));
+                profile = profile.with_asset(Asset {
+                    contents: widgets
+                        .section("year_comparison", &(calendar, today), || {
+                            render_year_comparison_svg(calendar, today, theme, &config.vacations)
+                        })
+                        .into_bytes(),
+                    path,
+                });
+            }
+            _ => {
+                let heatmap: String =
+                    widgets.section("year_comparison", &(calendar, today), || {
+                        format_year_comparison(calendar, today, theme, &config.vacations)
+                    });
+                profile = profile.with_section(dated(
+                    Section::fenced(title, &heatmap),
+                    &["Contribution calendar"],
+                ));
+            }
+        }
+    }
+
+    #[cfg(feature = "svg")]
+    if let Some(calendar) = calendar.as_ref().filter(|_| config.life) {
+        let title = "Game of Life";
+        let path = format!("{}/life.svg", ASSETS_DIR);
+        profile = profile.with_section(dated(
+            Section::new(
+                title,
+                vec![Block::Images {
+                    caption: None,
+                    images: vec![Image::new(
+                        &path,
+                        "Conway's Game of Life seeded with my contributions",
+                    )],
+                }],
+            ),
+            &["Contribution calendar"],
+        ));
+        profile = profile.with_asset(Asset {
+            contents: widgets
+                .section("life", &(calendar, today), || {
+                    render_life_svg(calendar, today, &config.theme.heatmap)
+                })
+                .into_bytes(),
+            path,
+        });
+    }
+    #[cfg(not(feature = "svg"))]
+    if config.life {
+        report_anomaly("Skipping the Game of Life: this build has no `svg` feature");
+    }
+
+    if config.repo_timeline {
+        match steps.step("Repository timeline", || {
+            get_repos_per_year(username, token)
+        }) {
+            Ok(per_year) => {
+                let timeline: String = widgets.section("repo_timeline", &per_year, || {
+                    format_repos_per_year(&per_year)
+                });
+                profile = profile.with_section(dated(
+                    Section::fenced("Repositories per year", &timeline),
+                    &["Repository timeline"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Repositories per year",
+                    format!("Skipping repository timeline: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(pr_sizes_config) = &config.pr_sizes {
+        match steps.step("Pull request sizes", || {
+            get_pr_sizes(pr_sizes_config, username, token)
+        }) {
+            Ok(pr_sizes) => {
+                let histogram: String =
+                    widgets.section("pr_sizes", &pr_sizes, || format_pr_sizes(&pr_sizes));
+                profile = profile.with_section(dated(
+                    Section::fenced("Pull request sizes", &histogram),
+                    &["Pull request sizes"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Pull request sizes",
+                    format!("Skipping pull request sizes: {}", e),
+                );
+            }
+        }
+    }
+
+    if config.review_balance {
+        match steps.step("Review balance", || {
+            get_review_balance(username, token, today)
+        }) {
+            Ok(balance) => {
+                let summary: String = widgets.section("review_balance", &balance, || {
+                    format_review_balance(&balance)
+                });
+                profile = profile.with_section(dated(
+                    Section::fenced("Code review", &summary),
+                    &["Review balance"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Code review",
+                    format!("Skipping review balance: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(growth_config) = &config.growth {
+        let (growth, growth_assets) = format_growth(growth_config, history, today, &config.mode);
+        profile = profile.with_section(Section::new("Growth", vec![growth]));
+        for asset in growth_assets {
+            profile = profile.with_asset(asset);
+        }
+    }
+
+    if !config.goals.is_empty() {
+        let goals = format_goals(&get_goal_progress(&config.goals, history, today), today);
+        profile = profile.with_section(Section::fenced("Goals", &goals));
+    }
+
+    if let Some(eta_config) = &config.eta {
+        let eta = format_eta(eta_config, history, today);
+        if !eta.is_empty() {
+            profile = profile.with_section(Section::fenced("Forecast", &eta));
+        }
+    }
+
+    // Events outside their window are neither fetched nor shown.
+    let mut events = Vec::new();
+    let mut failed_events = Vec::new();
+    for event in &config.events {
+        match event_window(event, today) {
+            Ok(Some(window)) => match steps.step(&event.name, || {
+                get_event_progress(event, username, token, window)
+            }) {
+                Ok(standing) => events.push(standing),
+                Err(e) => failed_events.push(format!("Skipping {}: {}", event.name, e)),
+            },
+            Ok(None) => {}
+            Err(e) => failed_events.push(format!("Skipping {}: {}", event.name, e)),
+        }
+    }
+    if !events.is_empty() {
+        let bars: String =
+            widgets.section("events", &events, || format_event_progress(&events, today));
+        let names: Vec<&str> = events.iter().map(|event| event.name.as_str()).collect();
+        let mut blocks = vec![Block::Art(bars)];
+        if !failed_events.is_empty() {
+            skip_part(&mut blocks, config, failed_events.join("; "));
+        }
+        profile = profile.with_section(dated(Section::new("Events", blocks), &names));
+    } else if !failed_events.is_empty() {
+        profile = skip_widget(profile, config, "Events", failed_events.join("; "));
+    }
+
+    if let Some(mentorship_config) = &config.mentorship {
+        let mut entries = mentorship_config.entries.clone();
+        if mentorship_config.validate {
+            let missing = steps.step("Mentorship links", || find_missing_links(&entries, token));
+            for (index, reason) in missing {
+                report_anomaly(format!("Unlinking mentorship entry: {}", reason));
+                entries[index].link = None;
+            }
+        }
+        if !entries.is_empty() {
+            profile = profile.with_section(Section::new(
+                "Mentorship",
+                vec![format_mentorship(&entries)],
+            ));
+        }
+    }
+
+    if let Some(talks_config) = &config.talks {
+        match load_talks(talks_config) {
+            Ok(mut talks) if !talks.is_empty() => {
+                let talks = format_talks(&mut talks, talks_config.limit);
+                profile = profile.with_section(Section::new("Talks & publications", talks));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Talks & publications",
+                    format!("Skipping talks: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(responsiveness_config) = &config.responsiveness {
+        match steps.step("Responsiveness", || {
+            get_responsiveness(responsiveness_config, token)
+        }) {
+            Ok(responsiveness) => {
+                profile = profile.with_section(dated(
+                    Section::fenced(
+                        "Maintainer responsiveness",
+                        &format_responsiveness(&responsiveness),
+                    ),
+                    &["Responsiveness"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Maintainer responsiveness",
+                    format!("Skipping maintainer responsiveness: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(spotlight_config) = &config.spotlight {
+        match steps.step("Spotlight", || {
+            get_spotlight(spotlight_config, username, token, history, today)
+        }) {
+            Ok(spotlight) => {
+                let card =
+                    widgets.section("spotlight", &spotlight, || format_spotlight(&spotlight));
+                profile = profile
+                    .with_section(dated(Section::new("Spotlight", vec![card]), &["Spotlight"]));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Spotlight",
+                    format!("Skipping the spotlight: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(triage_config) = &config.triage {
+        match steps.step("Triage velocity", || {
+            get_triage_velocity(triage_config, token)
+        }) {
+            Ok(velocities) => {
+                let gauges: String = widgets.section("triage", &velocities, || {
+                    format_triage_velocity(&velocities, triage_config.hours)
+                });
+                profile = profile.with_section(dated(
+                    Section::fenced("Issue triage", &gauges),
+                    &["Triage velocity"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Issue triage",
+                    format!("Skipping triage velocity: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(discussions_config) = &config.discussions {
+        match steps.step("Discussions", || {
+            get_discussion_stats(discussions_config, username, token)
+        }) {
+            Ok(stats) => {
+                let summary: String =
+                    widgets.section("discussions", &stats, || format_discussion_stats(&stats));
+                profile = profile.with_section(dated(
+                    Section::fenced("Community support", &summary),
+                    &["Discussions"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Community support",
+                    format!("Skipping discussion stats: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(releases_config) = &config.releases {
+        match steps.step("Latest release", || {
+            get_latest_release(releases_config, username, token)
+        }) {
+            Ok(release) => {
+                let excerpt: String =
+                    widgets.section("releases", &release, || format_release(&release));
+                prose.push(("Latest release".to_string(), excerpt.clone()));
+                profile = profile.with_section(dated(
+                    Section::fenced("Latest release", &excerpt),
+                    &["Latest release"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Latest release",
+                    format!("Skipping latest release: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(contributors_config) = &config.contributors {
+        match steps.step("Contributors", || {
+            get_contributors(contributors_config, username, token)
+        }) {
+            Ok(contributors) => {
+                let thanks = widgets.section("contributors", &contributors, || {
+                    format_contributors(&contributors, &config.mode)
+                });
+                profile = profile.with_section(dated(
+                    Section::new("Contributors", vec![thanks]),
+                    &["Contributors"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Contributors",
+                    format!("Skipping contributors: {}", e),
+                );
+            }
+        }
+    }
+
+    for widget in &config.custom {
+        match steps.step(&widget.title, || get_custom_widget(widget, username, token)) {
+            Ok(text) => {
+                prose.push((widget.title.clone(), text.clone()));
+                profile = profile.with_section(dated(
+                    Section::fenced(&widget.title, &text),
+                    &[widget.title.as_str()],
+                ));
+            }
+            Err(e) => {
+                let message = format!("Skipping custom widget \"{}\": {}", widget.title, e);
+                profile = skip_widget(profile, config, &widget.title, message);
+            }
+        }
+    }
+
+    let digest = history.digest.as_ref().filter(|_| config.weekly_digest);
+    let recent = &activities[..activities.len().min(5)];
+    let mut activity: String = widgets.section("activity", &(digest, recent), || {
+        let mut section = String::new();
+        if let Some(digest) = digest {
+            section += &format_weekly_digest(digest);
+        }
+        section += &"-".repeat(60);
+        section += "\n";
+        for activity in recent {
+            section += &format_activity(activity);
+            section += "\n";
+        }
+        section += &"-".repeat(60);
+        section += "\n\n";
+        section
+    });
+    activity += &format!("Last updated: {}\n", now.format("%Y-%m-%d %H:%M:%S"));
+    profile = profile.with_section(dated(Section::fenced("Activity", &activity), &["Activity"]));
+
+    let mut scraper = Scraper::new(&config.scraping);
+
+    if config.achievements {
+        match steps.step("Achievements", || get_achievements(&mut scraper, username)) {
+            Ok(achievements) if !achievements.is_empty() => {
+                profile = profile.with_section(dated(
+                    Section::fenced("Achievements", &format_achievements(&achievements)),
+                    &["Achievements"],
+                ));
+            }
+            Ok(_) => report_anomaly("No achievements found on the profile page"),
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Achievements",
+                    format!("Skipping achievements: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(keys_config) = &config.keys {
+        match steps.step("Keys", || get_keys(username, token)) {
+            Ok(keys) => {
+                profile = profile.with_section(dated(
+                    Section::fenced("Keys", &format_keys(&keys, keys_config, username)),
+                    &["Keys"],
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(profile, config, "Keys", format!("Skipping keys: {}", e))
+            }
+        }
+    }
+
+    if let Some(proofs_config) = &config.proofs {
+        match proof_claims(proofs_config, username) {
+            Ok(proofs) => {
+                let mut unresolved = Vec::new();
+                if proofs_config.verify {
+                    let default_links = LinksConfig::default();
+                    let links_config = config.links.as_ref().unwrap_or(&default_links);
+                    let dead = steps.step("Proofs", || unresolved_proofs(&proofs, links_config));
+                    for (url, reason) in dead {
+                        report_anomaly(format!("Proof {} does not resolve: {}", url, reason));
+                        unresolved.push(url);
+                    }
+                }
+                profile = profile.with_section(Section::fenced(
+                    "Identity",
+                    &format_proofs(&proofs, &unresolved),
+                ));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "Identity",
+                    format!("Skipping identity proofs: {}", e),
+                );
+            }
+        }
+    }
+
+    if let Some(qr_config) = &config.qr {
+        match render_qr(&qr_config.url, qr_config.invert) {
+            Ok(qr_lines) => {
+                // Right-align under the stats table so the code sits in the bottom corner.
+                let caption = format!("↳ {}", fence_safe(&qr_config.url));
+                let mut qr = String::new();
+                for line in qr_lines.iter().chain(std::iter::once(&caption)) {
+                    qr += &format!("{:>width$}\n", line, width = QR_CORNER_WIDTH);
+                }
+                profile = profile.with_section(Section::untitled(vec![Block::Art(qr)]));
+            }
+            Err(e) => {
+                profile = skip_widget(
+                    profile,
+                    config,
+                    "QR code",
+                    format!("Skipping QR code: {}", e),
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "svg")]
+    if let Some(calendar) = calendar.as_ref().filter(|_| config.extras.avatar_frame) {
+        profile = profile.with_asset(Asset {
+            path: format!("{}/avatar-frame.svg", ASSETS_DIR),
+            contents: render_avatar_frame(
+                calendar.current_streak(today, &config.vacations),
+                &config.theme.heatmap,
+            )
+            .into_bytes(),
+        });
+    }
+    #[cfg(feature = "svg")]
+    if config.extras.favicon {
+        profile = profile.with_asset(Asset {
+            path: format!("{}/favicon.svg", ASSETS_DIR),
+            contents: render_favicon(github_followers, &config.theme.heatmap).into_bytes(),
+        });
+    }
+    #[cfg(not(feature = "svg"))]
+    if config.extras.avatar_frame || config.extras.favicon {
+        report_anomaly("Skipping the avatar frame and favicon: this build has no `svg` feature");
+    }
+    let stat_diff = history
+        .previous(today)
+        .zip(
+            history
+                .snapshots
+                .iter()
+                .find(|snapshot| snapshot.date == today),
+        )
+        .map(|(previous, current)| stat_changes(previous, current))
+        .unwrap_or_default();
+    #[cfg(feature = "svg")]
+    if config.diff_animation.is_some() && !stat_diff.is_empty() {
+        profile = profile.with_asset(Asset {
+            path: format!("{}/{}", ASSETS_DIR, DIFF_ANIMATION_FILE),
+            contents: render_odometer_svg(&stat_diff).into_bytes(),
+        });
+    }
+
+    #[cfg(feature = "audio")]
+    if let Some(calendar) = calendar.as_ref().filter(|_| config.extras.melody) {
+        profile = profile.with_asset(Asset {
+            path: format!("{}/contributions.wav", ASSETS_DIR),
+            contents: render_melody_wav(calendar, today, &config.theme.heatmap),
+        });
+    }
+    #[cfg(not(feature = "audio"))]
+    if config.extras.melody {
+        report_anomaly("Skipping the contribution melody: this build has no `audio` feature");
+    }
+
+    // Once for the whole README: marked numbers can be in the header badges as well as the stats.
+    let mut footer = Vec::new();
+    if github_stats
+        .as_ref()
+        .is_ok_and(|stats| stats.get(OVERRIDES_KEY).is_some())
+    {
+        footer.push(Block::text(
+            "<sub>* Set by hand in the config rather than counted from the public API.</sub>",
+        ));
+    }
+    footer.push(Block::centered(credits));
+    Assembled {
+        profile: profile
+            .with_footer(config.style.footer.apply(footer))
+            .with_layout(&layout_changes.apply(&config.layout))
+            .with_style(&config.style),
+        prose,
+        celebrations,
+        stat_diff,
+    }
+}
@@ -0,0 +1,95 @@
+//! Fills the stats history with one snapshot per past year, so growth charts, goals and
+//! milestones have something to work with on the first run instead of starting empty.
+
+use crate::github::graphql_cached;
+use crate::http::Client;
+use crate::widgets::timeline::get_repos_per_year;
+use chrono::{Datelike, Duration, NaiveDate};
+use profile_core::history::{History, Snapshot};
+use std::collections::BTreeMap;
+
+/// Contributions in a finished year don't change, so they are fetched once.
+const MAX_AGE_DAYS: i64 = 365;
+
+/// The metrics of a snapshot on `date`: contribution counts over the year up to `date`, as a live
+/// run counts them, and the repositories created by then. Followers, stars and contributed-to
+/// repositories have no history in the API and are left out rather than guessed.
+fn get_snapshot(
+    client: &Client,
+    username: &str,
+    token: &str,
+    date: NaiveDate,
+    repos_owned: u64,
+) -> Result<Snapshot, Box<dyn std::error::Error>> {
+    let query = format!(
+        r#"
+        query {{
+          user(login: "{}") {{
+            contributionsCollection(from: "{}T00:00:00Z", to: "{}T23:59:59Z") {{
+              totalCommitContributions
+              totalPullRequestContributions
+              totalIssueContributions
+              restrictedContributionsCount
+            }}
+          }}
+        }}
+        "#,
+        username,
+        date - Duration::days(364),
+        date
+    );
+    let data = graphql_cached(client, &query, token, Duration::days(MAX_AGE_DAYS))?;
+    let contributions = &data["user"]["contributionsCollection"];
+    if contributions.is_null() {
+        return Err(format!("no contributions for {} in {}", username, date.year()).into());
+    }
+    let count = |field: &str| contributions[field].as_u64().unwrap_or(0);
+
+    let metrics = BTreeMap::from([
+        (
+            "total_commits".to_string(),
+            count("totalCommitContributions") + count("restrictedContributionsCount"),
+        ),
+        (
+            "total_prs".to_string(),
+            count("totalPullRequestContributions"),
+        ),
+        ("total_issues".to_string(), count("totalIssueContributions")),
+        ("repos_owned".to_string(), repos_owned),
+    ]);
+    Ok(Snapshot {
+        date,
+        metrics,
+        languages: Vec::new(),
+    })
+}
+
+/// Adds a snapshot on December 31 of every year from `since` up to last year, skipping days
+/// that already have one, and returns how many were added. `repos_owned` counts today's
+/// repositories by creation date, so deleted repositories are missing from it.
+pub fn backfill_history(
+    history: &mut History,
+    username: &str,
+    token: &str,
+    since: i32,
+    today: NaiveDate,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let repos_per_year = get_repos_per_year(username, token)?;
+    let mut added = 0;
+
+    for year in since..today.year() {
+        let date = NaiveDate::from_ymd_opt(year, 12, 31).ok_or("year out of range")?;
+        if history
+            .snapshots
+            .iter()
+            .any(|snapshot| snapshot.date == date)
+        {
+            continue;
+        }
+        let repos_owned = repos_per_year.range(..=year).map(|(_, count)| count).sum();
+        history.record(get_snapshot(&client, username, token, date, repos_owned)?);
+        added += 1;
+    }
+    Ok(added)
+}
@@ -0,0 +1,70 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::Duration;
+use chrono::NaiveDate;
+use profile_core::calendar::ContributionCalendar;
+use profile_core::validate::{check_calendar, report_invalid};
+
+/// Fetches the calendar for `from..=to`, one request per year since the API caps each
+/// contributionsCollection at a year.
+pub fn get_contribution_calendar(
+    username: &str,
+    token: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<ContributionCalendar, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let mut calendar = ContributionCalendar::default();
+    let mut start = from;
+    let mut invalid_days = 0;
+
+    while start <= to {
+        let end = (start + Duration::days(364)).min(to);
+        let query = format!(
+            r#"
+            query {{
+              user(login: "{}") {{
+                contributionsCollection(from: "{}T00:00:00Z", to: "{}T23:59:59Z") {{
+                  contributionCalendar {{
+                    weeks {{ contributionDays {{ date contributionCount }} }}
+                  }}
+                }}
+              }}
+            }}
+            "#,
+            username, start, end
+        );
+        let data = graphql_cached(&client, &query, token, Duration::hours(6))?;
+
+        for week in data["user"]["contributionsCollection"]["contributionCalendar"]["weeks"]
+            .as_array()
+            .ok_or("unexpected response for contribution calendar")?
+        {
+            for day in week["contributionDays"].as_array().into_iter().flatten() {
+                match (
+                    day["date"]
+                        .as_str()
+                        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()),
+                    day["contributionCount"].as_u64(),
+                ) {
+                    (Some(date), Some(count)) => {
+                        calendar.days.insert(date, count);
+                    }
+                    _ => invalid_days += 1,
+                }
+            }
+        }
+
+        start = end + Duration::days(1);
+    }
+
+    let mut problems = check_calendar(&calendar, to);
+    if invalid_days > 0 {
+        problems.push(format!(
+            "Contribution calendar has {} days without a valid date and count",
+            invalid_days
+        ));
+    }
+    report_invalid(problems);
+    Ok(calendar)
+}
@@ -0,0 +1,82 @@
+//! Recorded API responses on disk ("cassettes"), one JSON file per distinct request, so a run
+//! that rendered oddly for someone can be replayed exactly from their recording.
+
+use crate::http::{redact_url, HttpResponse, StatusCode};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use profile_core::cache::hash_key;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+/// Writes `exchanges` to `dir`, the responses to a repeated request together in one file in
+/// the order they came. Bodies are kept as text where they are UTF-8, to stay readable.
+pub fn save_cassettes(dir: &Path, exchanges: &[(String, HttpResponse)]) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let mut keys: Vec<&str> = exchanges.iter().map(|(key, _)| key.as_str()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let responses: Vec<Value> = exchanges
+            .iter()
+            .filter(|(other, _)| other == key)
+            .map(|(_, response)| {
+                let mut entry = json!({
+                    "url": redact_url(&response.url),
+                    "status": response.status.as_u16(),
+                    "headers": response.headers,
+                });
+                match std::str::from_utf8(&response.body) {
+                    Ok(text) => entry["body"] = json!(text),
+                    Err(_) => entry["body_base64"] = json!(STANDARD.encode(&response.body)),
+                }
+                entry
+            })
+            .collect();
+        let cassette = json!({ "request": key, "responses": responses });
+        let path = dir.join(format!("{:016x}.json", hash_key(key)));
+        let contents = serde_json::to_string_pretty(&cassette).map_err(|e| e.to_string())?;
+        fs::write(&path, contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+fn parse_response(entry: &Value) -> Option<HttpResponse> {
+    let body = match (entry["body"].as_str(), entry["body_base64"].as_str()) {
+        (Some(text), _) => text.as_bytes().to_vec(),
+        (None, Some(encoded)) => STANDARD.decode(encoded).ok()?,
+        (None, None) => Vec::new(),
+    };
+    Some(HttpResponse {
+        url: entry["url"].as_str()?.to_string(),
+        status: StatusCode::from_u16(u16::try_from(entry["status"].as_u64()?).ok()?).ok()?,
+        // Absent from cassettes recorded before headers were kept.
+        headers: serde_json::from_value(entry["headers"].clone()).unwrap_or_default(),
+        body,
+    })
+}
+
+/// Reads back every cassette in `dir` as `(request key, response)` exchanges.
+pub fn load_cassettes(dir: &Path) -> Result<Vec<(String, HttpResponse)>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let mut exchanges = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let cassette: Value = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| serde_json::from_str(&contents).map_err(|e| e.to_string()))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        let key = cassette["request"]
+            .as_str()
+            .ok_or_else(|| format!("{}: not a cassette", path.display()))?;
+        for response in cassette["responses"].as_array().into_iter().flatten() {
+            let response = parse_response(response)
+                .ok_or_else(|| format!("{}: malformed response", path.display()))?;
+            exchanges.push((key.to_string(), response));
+        }
+    }
+    Ok(exchanges)
+}
@@ -0,0 +1,356 @@
+//! The data the core sections render from: recent activity, languages, the stats and the
+//! follower count. The optional widgets fetch their own data next to their renderers.
+
+use crate::github::{count_request, get_json_cached, graphql_cached};
+use crate::http::{send, Client, HttpResponse};
+use crate::polite::is_rate_limited;
+use chrono::{Duration, Utc};
+use profile_core::anomaly::report_anomaly;
+use profile_core::cancel::{check_cancelled, is_cancelled};
+use profile_core::config::{LanguagesConfig, SamplingConfig};
+use profile_core::percent::normalize_percentages;
+use profile_core::sampling::sample_repos;
+use profile_core::validate::{
+    check_activity_dates, check_counts, check_percentages, report_invalid,
+};
+use profile_core::OTHER_LANGUAGE;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// The latest public events, as the events API returns them, checked for implausible dates.
+pub fn get_github_activity(
+    username: &str,
+    token: &str,
+) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let url = format!("https://api.github.com/users/{}/events/public", username);
+    let client = Client::new();
+
+    check_cancelled()?;
+    count_request();
+    send(
+        client
+            .get(&url)
+            .header("Authorization", format!("token {}", token))
+            .header("User-Agent", "Rust GitHub Action"),
+    )?
+    .json::<Vec<Value>>()
+    .map_err(|e| e.into())
+    .inspect(|activities| report_invalid(check_activity_dates(activities, Utc::now())))
+}
+
+/// Pages of 100 repositories listed before sampling them for languages.
+const MAX_REPO_PAGES: u32 = 10;
+
+/// Every language's share of the bytes in a sample of the user's repositories, sorted by share
+/// (then name), small ones folded into [`OTHER_LANGUAGE`] and rounded to one decimal.
+pub fn get_all_languages(
+    username: &str,
+    token: &str,
+    languages_config: &LanguagesConfig,
+    sampling: &SamplingConfig,
+) -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
+    fetch_languages(username, token, languages_config, sampling).map_err(|e| {
+        match token.is_empty() && is_rate_limited(e.as_ref()) {
+            true => "unauthenticated rate limit reached, languages unavailable".into(),
+            false => e,
+        }
+    })
+}
+
+fn fetch_languages(
+    username: &str,
+    token: &str,
+    languages_config: &LanguagesConfig,
+    sampling: &SamplingConfig,
+) -> Result<Vec<(String, f64)>, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    // Listing is cheap (a request per 100 repos); the languages request per repo is what the
+    // sample bounds.
+    let mut repos = Vec::new();
+    for page in 1..=MAX_REPO_PAGES {
+        let url = format!(
+            "https://api.github.com/users/{}/repos?per_page=100&page={}",
+            username, page
+        );
+        let Value::Array(batch) = get_json_cached(&client, &url, token, Duration::hours(6))? else {
+            return Err("unexpected response for repositories".into());
+        };
+        let last = batch.len() < 100;
+        repos.extend(batch);
+        if last {
+            break;
+        }
+        if page == MAX_REPO_PAGES {
+            report_anomaly(format!(
+                "Repository list truncated at {}; later repos are never sampled for languages",
+                repos.len()
+            ));
+        }
+    }
+    let repos = sample_repos(repos, sampling);
+
+    let mut languages: BTreeMap<String, u64> = BTreeMap::new();
+
+    for repo in repos {
+        if is_cancelled() {
+            break;
+        }
+        if let Some(lang_url) = repo["languages_url"].as_str() {
+            let repo_langs = get_json_cached(&client, lang_url, token, Duration::hours(6))?;
+
+            if let Some(obj) = repo_langs.as_object() {
+                let names: Vec<&str> = obj.keys().map(String::as_str).collect();
+                report_invalid(check_counts(lang_url, &repo_langs, &names));
+                for (lang, bytes) in obj {
+                    *languages.entry(lang.clone()).or_insert(0) += bytes.as_u64().unwrap_or(0);
+                }
+            }
+        }
+    }
+
+    Ok(language_shares(&languages, languages_config))
+}
+
+/// Each language's share of `bytes`, the byte counts summed over the sampled repositories,
+/// after the aliases and the "Other" grouping, biggest first and rounded to add up to 100.0.
+pub fn language_shares(
+    bytes: &BTreeMap<String, u64>,
+    languages_config: &LanguagesConfig,
+) -> Vec<(String, f64)> {
+    let mut languages: BTreeMap<&str, u64> = BTreeMap::new();
+    for (lang, count) in bytes {
+        *languages.entry(languages_config.resolve(lang)).or_insert(0) += count;
+    }
+
+    let total_bytes: u64 = languages.values().sum();
+    let mut language_percentages: Vec<(String, f64)> = languages
+        .into_iter()
+        .map(|(lang, count)| {
+            (
+                lang.to_string(),
+                (count as f64 / total_bytes as f64) * 100.0,
+            )
+        })
+        .collect();
+
+    // Equal shares are ordered alphabetically so reruns on the same data produce the same README.
+    language_percentages.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let grouped = group_small_languages(language_percentages, languages_config);
+    // Checked before rounding, which would otherwise make any total come out at 100.
+    report_invalid(check_percentages("Language percentages", &grouped));
+
+    // Shown with one decimal, so round in a way that still adds up to 100.0%.
+    let shares: Vec<f64> = grouped.iter().map(|(_, percentage)| *percentage).collect();
+    grouped
+        .into_iter()
+        .zip(normalize_percentages(&shares, 1))
+        .map(|((lang, _), percentage)| (lang, percentage))
+        .collect()
+}
+
+/// Folds languages below `min_percentage`, and any beyond `max_rows`, into a trailing
+/// "Other" row so the displayed bars still add up to 100%.
+pub fn group_small_languages(
+    sorted: Vec<(String, f64)>,
+    languages_config: &LanguagesConfig,
+) -> Vec<(String, f64)> {
+    let max_rows = languages_config.max_rows.max(1);
+    let fits = |rows: usize| {
+        sorted.len() <= rows
+            && sorted
+                .iter()
+                .all(|(_, percentage)| *percentage >= languages_config.min_percentage)
+    };
+    if fits(max_rows) {
+        return sorted;
+    }
+
+    let (mut shown, other): (Vec<_>, Vec<_>) =
+        sorted
+            .into_iter()
+            .enumerate()
+            .partition(|(i, (_, percentage))| {
+                *i < max_rows - 1 && *percentage >= languages_config.min_percentage
+            });
+    let other_percentage: f64 = other.iter().map(|(_, (_, percentage))| percentage).sum();
+    shown.push((0, (OTHER_LANGUAGE.to_string(), other_percentage)));
+    shown.into_iter().map(|(_, language)| language).collect()
+}
+
+/// The stats table's counts from one GraphQL query: contributions, stars, owned and
+/// contributed-to repositories.
+pub fn get_github_stats(username: &str, token: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let client = Client::new();
+
+    let query = format!(
+        r#"
+        query {{
+          user(login: "{}") {{
+            name
+            contributionsCollection {{
+              totalCommitContributions
+              totalPullRequestContributions
+              totalIssueContributions
+              restrictedContributionsCount
+            }}
+            repositories(first: 100, ownerAffiliations: OWNER, isFork: false) {{
+              totalCount
+              nodes {{
+                stargazerCount
+              }}
+            }}
+            repositoriesContributedTo(first: 1, contributionTypes: [COMMIT, ISSUE, PULL_REQUEST, REPOSITORY]) {{
+              totalCount
+            }}
+          }}
+        }}
+        "#,
+        username
+    );
+    let data = graphql_cached(&client, &query, token, Duration::hours(1))?;
+
+    let user = &data["user"];
+    if user.is_null() {
+        return Err(format!("no GitHub user {}", username).into());
+    }
+    let contributions = &user["contributionsCollection"];
+    let repositories = &user["repositories"];
+    let fetched_repos = repositories["nodes"]
+        .as_array()
+        .map_or(0, |nodes| nodes.len());
+    if repositories["totalCount"].as_u64().unwrap_or(0) > fetched_repos as u64 {
+        report_anomaly(format!(
+            "Star count only covers the first {} of {} repositories",
+            fetched_repos, repositories["totalCount"]
+        ));
+    }
+
+    let mut problems = check_counts(
+        "contributionsCollection",
+        contributions,
+        &[
+            "totalCommitContributions",
+            "restrictedContributionsCount",
+            "totalPullRequestContributions",
+            "totalIssueContributions",
+        ],
+    );
+    problems.extend(check_counts("repositories", repositories, &["totalCount"]));
+    problems.extend(check_counts(
+        "repositoriesContributedTo",
+        &user["repositoriesContributedTo"],
+        &["totalCount"],
+    ));
+    let unstarred = repositories["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|repo| repo["stargazerCount"].as_u64().is_none())
+        .count();
+    if unstarred > 0 {
+        problems.push(format!(
+            "{} repositories have no valid stargazerCount",
+            unstarred
+        ));
+    }
+    report_invalid(problems);
+
+    let total_stars: u64 = repositories["nodes"]
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .map(|repo| repo["stargazerCount"].as_u64().unwrap_or(0))
+        .sum();
+
+    Ok(json!({
+        "total_commits": contributions["totalCommitContributions"].as_u64().unwrap_or(0) +
+                         contributions["restrictedContributionsCount"].as_u64().unwrap_or(0),
+        "total_prs": contributions["totalPullRequestContributions"].as_u64().unwrap_or(0),
+        "total_issues": contributions["totalIssueContributions"].as_u64().unwrap_or(0),
+        "total_stars": total_stars,
+        "repos_owned": repositories["totalCount"].as_u64().unwrap_or(0),
+        "contributed_to": user["repositoriesContributedTo"]["totalCount"].as_u64().unwrap_or(0),
+    }))
+}
+
+/// The stats from REST endpoints that answer without a token. Search only counts public work,
+/// stars only cover the first 100 repositories, and `contributed_to` has no REST equivalent.
+pub fn get_public_stats(username: &str) -> serde_json::Value {
+    let client = Client::new();
+    let get = |url: String| -> Value {
+        count_request();
+        send(client.get(&url).header("User-Agent", "Rust GitHub Action"))
+            .and_then(HttpResponse::error_for_status)
+            .map_err(|e| match is_rate_limited(&e) {
+                true => report_anomaly("unauthenticated rate limit reached, stats incomplete"),
+                false => report_anomaly(format!("Public stats incomplete: {}", e)),
+            })
+            .ok()
+            .and_then(|response| response.json().ok())
+            .unwrap_or_default()
+    };
+    let search = |query: &str| {
+        get(format!(
+            "https://api.github.com/search/{}+author:{}&per_page=1",
+            query, username
+        ))["total_count"]
+            .as_u64()
+            .unwrap_or(0)
+    };
+
+    let user = get(format!("https://api.github.com/users/{}", username));
+    // The same page the languages start from, so it comes from the cache.
+    let repos = get(format!(
+        "https://api.github.com/users/{}/repos?per_page=100&page=1",
+        username
+    ));
+    let total_stars: u64 = repos
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|repo| repo["stargazers_count"].as_u64().unwrap_or(0))
+        .sum();
+
+    json!({
+        "total_commits": search("commits?q=is:public"),
+        "total_prs": search("issues?q=is:pr"),
+        "total_issues": search("issues?q=is:issue"),
+        "total_stars": total_stars,
+        "repos_owned": user["public_repos"].as_u64().unwrap_or(0),
+    })
+}
+
+/// The follower count, or 0 (reported as an anomaly) when it can't be fetched.
+pub fn get_github_followers(username: &str, token: &str) -> u64 {
+    let client = Client::new();
+    let url = format!("https://api.github.com/users/{}", username);
+
+    count_request();
+    let response = send(
+        client
+            .get(&url)
+            .header("Authorization", format!("token {}", token))
+            .header("User-Agent", "Rust GitHub Action"),
+    )
+    .and_then(HttpResponse::error_for_status)
+    .map_err(|e| match token.is_empty() && is_rate_limited(&e) {
+        true => "unauthenticated rate limit reached".to_string(),
+        false => e.to_string(),
+    })
+    .and_then(|response| {
+        response
+            .json::<serde_json::Value>()
+            .map_err(|e| e.to_string())
+    });
+
+    match response {
+        Ok(json) => json["followers"].as_u64().unwrap_or_else(|| {
+            report_anomaly("User response has no follower count");
+            0
+        }),
+        Err(e) => {
+            report_anomaly(format!("Failed to fetch followers: {}", e));
+            0
+        }
+    }
+}
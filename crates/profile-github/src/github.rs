@@ -0,0 +1,188 @@
+use crate::http::{send, Client, HttpResponse, RequestBuilder, StatusCode};
+use chrono::Duration;
+use profile_core::anomaly::report_anomaly;
+use profile_core::cache::{read_cache, read_stale_cache, write_cache};
+use profile_core::cancel::check_cancelled;
+use profile_core::circuit::{check_circuit, CircuitOpen, GITHUB_API};
+use profile_core::query_budget::{fit_node_limit, with_rate_limit};
+use serde_json::{json, Value};
+use std::cell::Cell;
+
+thread_local! {
+    // Per thread like the anomalies, so batch mode counts each profile on its own.
+    static REQUESTS: Cell<u64> = const { Cell::new(0) };
+    /// GraphQL rate limit points spent, and how many the last response said were left.
+    static GRAPHQL_COST: Cell<(u64, Option<u64>)> = const { Cell::new((0, None)) };
+}
+
+/// Records a request that actually went out (not served from the cache), for progress output.
+pub fn count_request() {
+    REQUESTS.with(|requests| requests.set(requests.get() + 1));
+}
+
+pub fn requests_made() -> u64 {
+    REQUESTS.with(Cell::get)
+}
+
+/// Records the `rateLimit` of a GraphQL response, if it has one.
+pub fn count_graphql_cost(data: &Value) {
+    let rate_limit = &data["rateLimit"];
+    if let Some(cost) = rate_limit["cost"].as_u64() {
+        GRAPHQL_COST.with(|spent| {
+            let (total, remaining) = spent.get();
+            spent.set((total + cost, rate_limit["remaining"].as_u64().or(remaining)));
+        });
+    }
+}
+
+/// GraphQL rate limit points spent so far, and how many were left after the last query.
+pub fn graphql_cost() -> (u64, Option<u64>) {
+    GRAPHQL_COST.with(Cell::get)
+}
+
+/// Sends `request` unless `provider`'s circuit is open, and feeds the outcome back into it.
+/// Only outage-like failures count against the provider; a 404 means it is answering fine.
+pub fn send_guarded(
+    provider: &'static str,
+    request: RequestBuilder,
+) -> Result<HttpResponse, Box<dyn std::error::Error>> {
+    // Before the circuit, which may hand out its one recovery probe.
+    check_cancelled()?;
+    let permit = check_circuit(provider)?;
+    count_request();
+
+    match send(request).and_then(HttpResponse::error_for_status) {
+        Ok(response) => {
+            permit.succeeded();
+            Ok(response)
+        }
+        Err(e) => {
+            let outage = e.status.is_none_or(|status| {
+                status.is_server_error()
+                    || status == StatusCode::TOO_MANY_REQUESTS
+                    || status == StatusCode::FORBIDDEN
+            });
+            if outage {
+                permit.failed();
+            } else {
+                permit.succeeded();
+            }
+            Err(e.into())
+        }
+    }
+}
+
+/// Falls back to whatever is cached under `key` when the provider's circuit is open.
+pub fn serve_stale(
+    key: &str,
+    error: Box<dyn std::error::Error>,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    match read_stale_cache(key) {
+        Some(stale) if error.is::<CircuitOpen>() => {
+            report_anomaly(format!("{}; serving cached data", error));
+            Ok(stale)
+        }
+        _ => Err(error),
+    }
+}
+
+/// GETs a REST endpoint, serving it from the on-disk cache while it is younger than `max_age`.
+pub fn get_json_cached(
+    client: &Client,
+    url: &str,
+    token: &str,
+    max_age: Duration,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    if let Some(cached) = read_cache(url, max_age) {
+        return Ok(cached);
+    }
+
+    let request = client
+        .get(url)
+        .header("Authorization", format!("token {}", token))
+        .header("User-Agent", "Rust GitHub Action")
+        .header("Accept", "application/vnd.github+json");
+    let data = match send_guarded(GITHUB_API, request) {
+        Ok(response) => response.json::<Value>()?,
+        Err(e) => return serve_stale(url, e),
+    };
+
+    write_cache(url, &data);
+    Ok(data)
+}
+
+/// Runs a GraphQL query, serving it from the on-disk cache while it is younger than `max_age`.
+/// The query is cut down first if GitHub would reject it as too big, and its cost is counted.
+pub fn graphql_cached(
+    client: &Client,
+    query: &str,
+    token: &str,
+    max_age: Duration,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    if let Some(cached) = read_cache(query, max_age) {
+        return Ok(cached);
+    }
+
+    let (fitted, note) = fit_node_limit(query);
+    if let Some(note) = note {
+        report_anomaly(format!("GraphQL {}", note));
+    }
+    let (sent, added_rate_limit) = with_rate_limit(&fitted);
+    let request = client
+        .post("https://api.github.com/graphql")
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "Rust GitHub Action")
+        .json(&json!({ "query": sent }));
+    let response = match send_guarded(GITHUB_API, request) {
+        Ok(response) => response.json::<Value>()?,
+        Err(e) => return serve_stale(query, e),
+    };
+
+    if let Some(errors) = response["errors"].as_array() {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|error| error["message"].as_str())
+            .collect();
+        return Err(format!("GraphQL error: {}", messages.join("; ")).into());
+    }
+
+    let mut data = response["data"].clone();
+    count_graphql_cost(&data);
+    if added_rate_limit {
+        if let Some(data) = data.as_object_mut() {
+            data.remove("rateLimit");
+        }
+    }
+    // Cached under the query as asked, so the lookup above finds it next time.
+    write_cache(query, &data);
+    Ok(data)
+}
+
+/// Picks the owned, non-fork repository with the most stars.
+pub fn get_most_popular_repo(
+    client: &Client,
+    username: &str,
+    token: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let url = format!(
+        "https://api.github.com/users/{}/repos?type=owner&per_page=100",
+        username
+    );
+    let repos = get_json_cached(client, &url, token, Duration::hours(6))?;
+
+    repos
+        .as_array()
+        .ok_or("unexpected response for repositories")?
+        .iter()
+        .filter(|repo| !repo["fork"].as_bool().unwrap_or(false))
+        // On equal stars, prefer the alphabetically first name so the choice is stable.
+        .max_by(|a, b| {
+            let stars = |repo: &Value| repo["stargazers_count"].as_u64().unwrap_or(0);
+            stars(a)
+                .cmp(&stars(b))
+                .then_with(|| b["full_name"].as_str().cmp(&a["full_name"].as_str()))
+        })
+        .and_then(|repo| repo["full_name"].as_str())
+        .map(|name| name.to_string())
+        .ok_or_else(|| "no owned repositories found".into())
+}
@@ -0,0 +1,431 @@
+//! The HTTP layer every provider sends through. Requests are put together with [`Client`]'s
+//! builders and handed as plain [`HttpRequest`]s to whichever [`HttpClient`] is installed:
+//! reqwest's blocking client unless something else was, such as a [`Replayer`] of recorded
+//! responses. reqwest itself stays behind [`BlockingClient`] and, with the `async` feature,
+//! `AsyncClient`.
+
+use profile_core::cache::hash_key;
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::string::FromUtf8Error;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+pub use reqwest::StatusCode;
+
+static CLIENT: OnceLock<Arc<dyn HttpClient>> = OnceLock::new();
+
+/// Headers as name and value pairs, leaving out values that aren't text.
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect()
+}
+
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    /// What identifies the request when replaying: method, URL and body, but no headers, so
+    /// a recording holds no `Authorization` token. URLs off GitHub are hashed, see
+    /// [`redact_url`].
+    pub fn key(&self) -> String {
+        let body = self
+            .body
+            .as_deref()
+            .map(String::from_utf8_lossy)
+            .unwrap_or_default();
+        format!(
+            "{} {} {:016x}",
+            self.method,
+            redact_url(&self.url),
+            hash_key(&body)
+        )
+    }
+
+    fn error(&self, status: Option<StatusCode>, message: impl Into<String>) -> HttpError {
+        HttpError {
+            url: self.url.clone(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Starts requests, in the manner of reqwest's client, for [`send`] to send.
+#[derive(Clone, Copy, Default)]
+pub struct Client;
+
+impl Client {
+    pub fn new() -> Self {
+        Client
+    }
+
+    pub fn get(&self, url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new("GET", url.into())
+    }
+
+    pub fn head(&self, url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new("HEAD", url.into())
+    }
+
+    pub fn post(&self, url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new("POST", url.into())
+    }
+
+    pub fn put(&self, url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new("PUT", url.into())
+    }
+
+    pub fn patch(&self, url: impl Into<String>) -> RequestBuilder {
+        RequestBuilder::new("PATCH", url.into())
+    }
+}
+
+/// An [`HttpRequest`] being put together. Mistakes, like a URL that doesn't parse, surface
+/// when it is sent.
+pub struct RequestBuilder {
+    request: HttpRequest,
+    error: Option<String>,
+}
+
+impl RequestBuilder {
+    fn new(method: &str, url: String) -> Self {
+        // Parsed the way reqwest would, so the URL is normalized (spaces escaped and the like)
+        // just as it goes out, and request keys stay what they were.
+        let (url, error) = match url::Url::parse(&url) {
+            Ok(parsed) => (parsed.to_string(), None),
+            Err(e) => (url, Some(e.to_string())),
+        };
+        RequestBuilder {
+            request: HttpRequest {
+                method: method.to_string(),
+                url,
+                headers: Vec::new(),
+                body: None,
+                timeout: None,
+            },
+            error,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.request
+            .headers
+            .push((name.to_ascii_lowercase(), value.into()));
+        self
+    }
+
+    /// Sends `body` as JSON, with a JSON `Content-Type` unless one is set already.
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        match serde_json::to_vec(body) {
+            Ok(body) => self.request.body = Some(body),
+            Err(e) => self.error = self.error.or(Some(e.to_string())),
+        }
+        let typed = self
+            .request
+            .headers
+            .iter()
+            .any(|(name, _)| name == "content-type");
+        if !typed {
+            self = self.header("content-type", "application/json");
+        }
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.request.timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(self) -> Result<HttpRequest, HttpError> {
+        match self.error {
+            Some(message) => Err(self.request.error(None, message)),
+            None => Ok(self.request),
+        }
+    }
+}
+
+/// Whether `host` is GitHub's own, where credentials only ever travel in headers.
+fn is_github_host(host: &str) -> bool {
+    ["github.com", "githubusercontent.com"]
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+}
+
+/// `url` safe to write into a recording. Webhook and other third-party URLs often carry their
+/// secret in the path or query, so everything after the host is replaced by its hash; GitHub
+/// URLs are kept as they are.
+pub fn redact_url(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return format!("{:016x}", hash_key(url));
+    };
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(end);
+    // Credentials in the authority (`user:secret@host`) go as well.
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let hostname = host.split(':').next().unwrap_or(host);
+    if !authority.contains('@') && is_github_host(hostname) {
+        return url.to_string();
+    }
+    format!("{}://{}/#{:016x}", scheme, host, hash_key(path))
+}
+
+#[derive(Clone)]
+pub struct HttpResponse {
+    pub url: String,
+    pub status: StatusCode,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// The value of the header `name`, in any case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(other, _)| other.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(&self.body)
+    }
+
+    pub fn text(self) -> Result<String, FromUtf8Error> {
+        String::from_utf8(self.body)
+    }
+
+    /// Fails with the status on a 4xx or 5xx answer, like reqwest's method of the same name.
+    pub fn error_for_status(self) -> Result<Self, HttpError> {
+        match self.status.is_client_error() || self.status.is_server_error() {
+            true => Err(HttpError {
+                message: format!("HTTP status {}", self.status.as_u16()),
+                url: self.url,
+                status: Some(self.status),
+            }),
+            false => Ok(self),
+        }
+    }
+}
+
+/// A request that got no answer, or (from [`HttpResponse::error_for_status`]) an error status.
+#[derive(Debug)]
+pub struct HttpError {
+    pub url: String,
+    pub status: Option<StatusCode>,
+    pub message: String,
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} for {}", self.message, self.url)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// The HTTP status behind a failed request, if it got as far as a response.
+pub fn status_of(error: &(dyn std::error::Error + 'static)) -> Option<StatusCode> {
+    error.downcast_ref::<HttpError>()?.status
+}
+
+/// Sends requests and returns responses whatever their status.
+pub trait HttpClient: Send + Sync {
+    fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError>;
+}
+
+impl<C: HttpClient + ?Sized> HttpClient for Arc<C> {
+    fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        self.as_ref().execute(request)
+    }
+}
+
+/// The same for async backends, with the `async` feature. Nothing in the crate runs an
+/// executor; this is for callers that bring their own.
+#[cfg(feature = "async")]
+pub trait AsyncHttpClient: Send + Sync {
+    fn execute(
+        &self,
+        request: &HttpRequest,
+    ) -> impl Future<Output = Result<HttpResponse, HttpError>> + Send;
+}
+
+/// reqwest's blocking client, the default.
+#[derive(Default)]
+pub struct BlockingClient {
+    client: reqwest::blocking::Client,
+}
+
+impl HttpClient for BlockingClient {
+    fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        let method = Method::from_bytes(request.method.as_bytes())
+            .map_err(|e| request.error(None, e.to_string()))?;
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder
+            .send()
+            .map_err(|e| request.error(None, e.to_string()))?;
+        let status = response.status();
+        let headers = header_pairs(response.headers());
+        let body = response
+            .bytes()
+            .map_err(|e| request.error(Some(status), e.to_string()))?;
+        Ok(HttpResponse {
+            url: request.url.clone(),
+            status,
+            headers,
+            body: body.to_vec(),
+        })
+    }
+}
+
+/// reqwest's async client.
+#[cfg(feature = "async")]
+#[derive(Default)]
+pub struct AsyncClient {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "async")]
+impl AsyncHttpClient for AsyncClient {
+    async fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        let method = Method::from_bytes(request.method.as_bytes())
+            .map_err(|e| request.error(None, e.to_string()))?;
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| request.error(None, e.to_string()))?;
+        let status = response.status();
+        let headers = header_pairs(response.headers());
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| request.error(Some(status), e.to_string()))?;
+        Ok(HttpResponse {
+            url: request.url.clone(),
+            status,
+            headers,
+            body: body.to_vec(),
+        })
+    }
+}
+
+/// Passes requests on to `inner` and keeps every exchange, keyed by [`HttpRequest::key`].
+pub struct Recorder<C> {
+    inner: C,
+    exchanges: Mutex<Vec<(String, HttpResponse)>>,
+}
+
+impl<C: HttpClient> Recorder<C> {
+    pub fn new(inner: C) -> Self {
+        Recorder {
+            inner,
+            exchanges: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Everything recorded so far, in the order it was received.
+    pub fn exchanges(&self) -> Vec<(String, HttpResponse)> {
+        self.exchanges.lock().unwrap().clone()
+    }
+}
+
+impl<C: HttpClient> HttpClient for Recorder<C> {
+    fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        let response = self.inner.execute(request)?;
+        self.exchanges
+            .lock()
+            .unwrap()
+            .push((request.key(), response.clone()));
+        Ok(response)
+    }
+}
+
+/// Answers from recorded exchanges without touching the network. A request made more often than
+/// it was recorded gets the last answer again; one never recorded fails.
+pub struct Replayer {
+    responses: Mutex<HashMap<String, Vec<HttpResponse>>>,
+}
+
+impl Replayer {
+    pub fn new(exchanges: Vec<(String, HttpResponse)>) -> Self {
+        let mut responses: HashMap<String, Vec<HttpResponse>> = HashMap::new();
+        for (key, response) in exchanges {
+            responses.entry(key).or_default().push(response);
+        }
+        Replayer {
+            responses: Mutex::new(responses),
+        }
+    }
+}
+
+impl HttpClient for Replayer {
+    fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        let mut responses = self.responses.lock().unwrap();
+        match responses.get_mut(&request.key()) {
+            Some(recorded) if recorded.len() > 1 => Ok(recorded.remove(0)),
+            Some(recorded) => Ok(recorded[0].clone()),
+            None => Err(request.error(None, format!("no recorded {} response", request.method))),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncHttpClient for Replayer {
+    async fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        HttpClient::execute(self, request)
+    }
+}
+
+/// Makes `client` the one every request goes through. Only the first call counts, and only
+/// before the first request.
+pub fn install(client: Arc<dyn HttpClient>) -> Result<(), String> {
+    CLIENT
+        .set(client)
+        .map_err(|_| "an HTTP client is already installed".to_string())
+}
+
+fn client() -> &'static dyn HttpClient {
+    CLIENT
+        .get_or_init(|| Arc::new(BlockingClient::default()))
+        .as_ref()
+}
+
+/// Sends a request through the installed client. Error statuses come back as responses; see
+/// [`HttpResponse::error_for_status`].
+pub fn send(builder: RequestBuilder) -> Result<HttpResponse, HttpError> {
+    client().execute(&builder.build()?)
+}
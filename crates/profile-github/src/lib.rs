@@ -0,0 +1,20 @@
+//! The GitHub providers: the HTTP client and its recordings, the REST and GraphQL helpers,
+//! the fetch side of the widgets, and [`assemble`](assemble::assemble), which puts a profile
+//! together from what they return. The model and renderers are in `profile-core`.
+
+pub mod assemble;
+pub mod backfill;
+pub mod calendar;
+pub mod cassette;
+pub mod fetch;
+pub mod github;
+pub mod http;
+pub mod links;
+pub mod notify;
+pub mod plausibility;
+pub mod polite;
+pub mod publish;
+pub mod remote;
+pub mod scrape;
+pub mod token_pool;
+pub mod widgets;
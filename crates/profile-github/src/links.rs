@@ -0,0 +1,104 @@
+//! Finds dead links in the rendered README, so a renamed repo or a vanished blog doesn't stay
+//! linked from the profile for months.
+
+use crate::http::{send, Client, RequestBuilder, StatusCode};
+use chrono::Duration;
+use profile_core::cache::{read_cache, write_cache};
+use profile_core::cancel::is_cancelled;
+use profile_core::config::LinksConfig;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// How long a link's result is trusted before it is checked again.
+const MAX_AGE_HOURS: i64 = 24;
+const TIMEOUT_SECONDS: u64 = 10;
+
+/// Every distinct http(s) URL in `markdown`: link targets, `href`/`src` attributes and bare
+/// URLs, in order of first appearance.
+pub fn extract_links(markdown: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    let mut rest = markdown;
+    while let Some(start) = rest.find("http") {
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | ')' | '`'))
+            .unwrap_or(rest.len());
+        let url = rest[..end]
+            .trim_end_matches(['.', ',', ';', ':'])
+            .replace("&amp;", "&");
+        if (url.starts_with("https://") || url.starts_with("http://")) && !links.contains(&url) {
+            links.push(url);
+        }
+        rest = &rest[end.max(4)..];
+    }
+    links
+}
+
+/// `None` when `url` answers, otherwise why it doesn't. Some servers refuse HEAD, so a 405 is
+/// retried with GET. Rate limits and auth walls count as answering.
+fn check_link(client: &Client, url: &str) -> Option<String> {
+    let request = |builder: RequestBuilder| {
+        send(
+            builder
+                .header("User-Agent", "Rust GitHub Action")
+                .timeout(std::time::Duration::from_secs(TIMEOUT_SECONDS)),
+        )
+    };
+    let mut response = request(client.head(url));
+    if matches!(&response, Ok(r) if r.status == StatusCode::METHOD_NOT_ALLOWED) {
+        response = request(client.get(url));
+    }
+    match response {
+        Ok(response) => {
+            let status = response.status;
+            let answering = !(status.is_client_error() || status.is_server_error())
+                || status == StatusCode::UNAUTHORIZED
+                || status == StatusCode::FORBIDDEN
+                || status == StatusCode::TOO_MANY_REQUESTS;
+            (!answering).then(|| format!("HTTP {}", status.as_u16()))
+        }
+        Err(e) => Some(e.to_string()),
+    }
+}
+
+/// Checks every link in `markdown` on up to `config.concurrency` threads and returns the dead
+/// ones as `(url, reason)` pairs in order of appearance. Results are cached for a day.
+pub fn find_dead_links(markdown: &str, config: &LinksConfig) -> Vec<(String, String)> {
+    let links: Vec<String> = extract_links(markdown)
+        .into_iter()
+        .filter(|link| !config.ignore.iter().any(|prefix| link.starts_with(prefix)))
+        .collect();
+    let client = Client::new();
+    let next = AtomicUsize::new(0);
+    let dead = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..config.concurrency.clamp(1, links.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(link) = links
+                    .get(next.fetch_add(1, Ordering::Relaxed))
+                    .filter(|_| !is_cancelled())
+                {
+                    let key = format!("link:{}", link);
+                    let reason = match read_cache(&key, Duration::hours(MAX_AGE_HOURS)) {
+                        Some(cached) => cached["dead"].as_str().map(str::to_string),
+                        None => {
+                            let reason = check_link(&client, link);
+                            write_cache(&key, &json!({ "dead": reason }));
+                            reason
+                        }
+                    };
+                    if let Some(reason) = reason {
+                        dead.lock().unwrap().push((link.clone(), reason));
+                    }
+                }
+            });
+        }
+    });
+
+    let mut dead = dead.into_inner().unwrap();
+    dead.sort_by_key(|(url, _)| links.iter().position(|link| link == url));
+    dead
+}
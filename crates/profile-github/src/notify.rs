@@ -0,0 +1,14 @@
+use crate::http::{send, Client};
+use serde_json::json;
+
+/// Posts `message` to a chat webhook. Sends both `text` (Slack) and `content` (Discord).
+pub fn send_webhook(url: &str, message: &str) -> Result<(), Box<dyn std::error::Error>> {
+    send(
+        Client::new()
+            .post(url)
+            .header("User-Agent", "Rust GitHub Action")
+            .json(&json!({ "text": message, "content": message })),
+    )?
+    .error_for_status()?;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Sends the webhook for the implausible changes [`profile_core::plausibility`] finds.
+
+use crate::notify::send_webhook;
+use chrono::Duration;
+use chrono::NaiveDate;
+use profile_core::anomaly::report_anomaly;
+use profile_core::cache::{read_cache, write_cache};
+use profile_core::config::PlausibilityConfig;
+use profile_core::plausibility::ImplausibleChange;
+use serde_json::json;
+
+/// Posts the changes to `config.webhook`, once per metric and day.
+pub fn notify_implausible(
+    config: &PlausibilityConfig,
+    changes: &[ImplausibleChange],
+    today: NaiveDate,
+) {
+    let Some(webhook) = &config.webhook else {
+        return;
+    };
+    let unsent: Vec<&ImplausibleChange> = changes
+        .iter()
+        .filter(|change| read_cache(&notified_key(change, today), Duration::days(1)).is_none())
+        .collect();
+    if unsent.is_empty() {
+        return;
+    }
+
+    let lines: Vec<String> = unsent.iter().map(|change| change.describe()).collect();
+    let message = format!(
+        "⚠️ Implausible profile metrics, check the API token and responses:\n{}",
+        lines.join("\n")
+    );
+    match send_webhook(webhook, &message) {
+        Ok(()) => {
+            for change in unsent {
+                write_cache(&notified_key(change, today), &json!(true));
+            }
+        }
+        Err(e) => report_anomaly(format!("Failed to send the metrics warning: {}", e)),
+    }
+}
+
+fn notified_key(change: &ImplausibleChange, today: NaiveDate) -> String {
+    format!("implausible:{}:{}", change.metric, today)
+}
@@ -0,0 +1,330 @@
+//! Running without a token, for quick local previews. GitHub allows 60 unauthenticated API
+//! requests an hour, so such runs keep to the widgets a handful of REST requests can fill,
+//! cache every answer for hours, space requests out and stop at a budget well under the limit.
+
+use crate::http::{HttpClient, HttpError, HttpRequest, HttpResponse, StatusCode};
+use profile_core::cache::{read_cache, write_cache};
+use profile_core::config::{Config, HeaderBadge};
+use serde_json::json;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// GitHub API requests one unauthenticated run may send, leaving the rest of the hour's 60 for
+/// the next preview.
+pub const REQUEST_BUDGET: u64 = 40;
+
+/// Pause between two API requests.
+const REQUEST_SPACING: Duration = Duration::from_secs(1);
+
+/// How long API answers are reused.
+fn max_age() -> chrono::Duration {
+    chrono::Duration::hours(6)
+}
+
+/// Most repositories sampled for languages, each of which costs a request.
+const SAMPLED_REPOS: usize = 10;
+
+/// Why [`Polite`] refuses a request once the run's budget is spent.
+fn budget_spent() -> String {
+    format!(
+        "all {} unauthenticated requests of this run are spent",
+        REQUEST_BUDGET
+    )
+}
+
+/// Whether `error` is GitHub's rate limit answering, or [`Polite`] holding back to stay under
+/// it, rather than anything being wrong with the request.
+pub fn is_rate_limited(error: &(dyn std::error::Error + 'static)) -> bool {
+    let Some(error) = error.downcast_ref::<HttpError>() else {
+        return false;
+    };
+    match error.status {
+        Some(status) => status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS,
+        None => error.message == budget_spent(),
+    }
+}
+
+fn is_api(url: &str) -> bool {
+    url.starts_with("https://api.github.com/")
+}
+
+/// Sends GitHub API requests through `inner` without credentials, within the budget and spaced
+/// out, and answers repeated GETs from the cache. Requests elsewhere pass straight through.
+pub struct Polite<C> {
+    inner: C,
+    /// Pause between two API requests.
+    spacing: Duration,
+    /// Requests sent so far, and when the last one was.
+    sent: Mutex<(u64, Option<Instant>)>,
+}
+
+impl<C: HttpClient> Polite<C> {
+    pub fn new(inner: C) -> Self {
+        Polite {
+            inner,
+            spacing: REQUEST_SPACING,
+            sent: Mutex::new((0, None)),
+        }
+    }
+}
+
+impl<C: HttpClient> HttpClient for Polite<C> {
+    fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        if !is_api(&request.url) {
+            return self.inner.execute(request);
+        }
+        let key = format!("unauthenticated {}", request.key());
+        let cacheable = request.method == "GET";
+        if let Some(cached) = read_cache(&key, max_age()).filter(|_| cacheable) {
+            if let (Some(status), Some(body)) = (
+                cached["status"]
+                    .as_u64()
+                    .and_then(|status| StatusCode::from_u16(status as u16).ok()),
+                cached["body"].as_str(),
+            ) {
+                return Ok(HttpResponse {
+                    url: request.url.clone(),
+                    status,
+                    headers: Vec::new(),
+                    body: body.as_bytes().to_vec(),
+                });
+            }
+        }
+
+        // Held across the request, so concurrent fetches queue up behind each other.
+        let mut sent = self.sent.lock().unwrap();
+        if sent.0 >= REQUEST_BUDGET {
+            return Err(HttpError {
+                url: request.url.clone(),
+                status: None,
+                message: budget_spent(),
+            });
+        }
+        if let Some(last) = sent.1 {
+            thread::sleep(self.spacing.saturating_sub(last.elapsed()));
+        }
+        let anonymous = HttpRequest {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            headers: request
+                .headers
+                .iter()
+                .filter(|(name, _)| !name.eq_ignore_ascii_case("authorization"))
+                .cloned()
+                .collect(),
+            body: request.body.clone(),
+            timeout: request.timeout,
+        };
+        let response = self.inner.execute(&anonymous);
+        *sent = (sent.0 + 1, Some(Instant::now()));
+
+        let response = response?;
+        if let (true, true, Ok(body)) = (
+            cacheable,
+            response.status.is_success(),
+            std::str::from_utf8(&response.body),
+        ) {
+            write_cache(
+                &key,
+                &json!({ "status": response.status.as_u16(), "body": body }),
+            );
+        }
+        Ok(response)
+    }
+}
+
+/// Turns off everything in `config` that needs a token or more requests than an unauthenticated
+/// run can spare, and returns what was turned off.
+pub fn restrict_to_public(config: &mut Config) -> Vec<&'static str> {
+    let mut disabled = Vec::new();
+    let mut off = |name: &'static str, was_on: bool| {
+        if was_on {
+            disabled.push(name);
+        }
+    };
+
+    let streak = config.header.badges.contains(&HeaderBadge::Streak);
+    config
+        .header
+        .badges
+        .retain(|badge| *badge != HeaderBadge::Streak);
+    off("the streak badge", streak);
+    off(
+        "the year comparison",
+        std::mem::take(&mut config.year_comparison),
+    );
+    off("the game of life", std::mem::take(&mut config.life));
+    off(
+        "the avatar frame",
+        std::mem::take(&mut config.extras.avatar_frame),
+    );
+    off(
+        "the contribution melody",
+        std::mem::take(&mut config.extras.melody),
+    );
+    off(
+        "the weekly digest",
+        std::mem::take(&mut config.weekly_digest),
+    );
+    off(
+        "the repository timeline",
+        std::mem::take(&mut config.repo_timeline),
+    );
+    off(
+        "the review balance",
+        std::mem::take(&mut config.review_balance),
+    );
+    off("stargazer facts", config.stargazers.take().is_some());
+    off("pull request sizes", config.pr_sizes.take().is_some());
+    off(
+        "maintainer responsiveness",
+        config.responsiveness.take().is_some(),
+    );
+    off("the spotlight", config.spotlight.take().is_some());
+    off("triage velocity", config.triage.take().is_some());
+    off("discussion stats", config.discussions.take().is_some());
+    off("the latest release", config.releases.take().is_some());
+    off("contributors", config.contributors.take().is_some());
+    off("keys", config.keys.take().is_some());
+    off("events", !std::mem::take(&mut config.events).is_empty());
+    off(
+        "custom widgets",
+        !std::mem::take(&mut config.custom).is_empty(),
+    );
+    if let Some(mentorship) = &mut config.mentorship {
+        off(
+            "mentorship link checks",
+            std::mem::take(&mut mentorship.validate),
+        );
+    }
+    if config.sampling.repos > SAMPLED_REPOS {
+        config.sampling.repos = SAMPLED_REPOS;
+        off("languages beyond the top 10 repositories", true);
+    }
+    disabled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{Client, Replayer};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// A [`Replayer`] that also keeps every request it is asked, to see what reached it.
+    struct Seen {
+        replayer: Replayer,
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl HttpClient for Seen {
+        fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+            self.requests.lock().unwrap().push(HttpRequest {
+                method: request.method.clone(),
+                url: request.url.clone(),
+                headers: request.headers.clone(),
+                body: request.body.clone(),
+                timeout: request.timeout,
+            });
+            self.replayer.execute(request)
+        }
+    }
+
+    fn polite(answers: &[(&str, &str)]) -> Polite<Seen> {
+        let exchanges = answers
+            .iter()
+            .map(|(url, body)| {
+                let request = Client::new().get(*url).build().unwrap();
+                let response = HttpResponse {
+                    url: url.to_string(),
+                    status: StatusCode::OK,
+                    headers: Vec::new(),
+                    body: body.as_bytes().to_vec(),
+                };
+                (request.key(), response)
+            })
+            .collect();
+        Polite {
+            spacing: Duration::ZERO,
+            ..Polite::new(Seen {
+                replayer: Replayer::new(exchanges),
+                requests: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    /// An API URL no earlier run has cached.
+    fn fresh_url(name: &str) -> String {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        format!("https://api.github.com/users/{}-{}", name, nanos)
+    }
+
+    fn get(url: &str) -> HttpRequest {
+        Client::new()
+            .get(url)
+            .header("Authorization", "token secret")
+            .build()
+            .unwrap()
+    }
+
+    fn authorization(request: &HttpRequest) -> Option<&str> {
+        request
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn api_requests_go_out_without_credentials() {
+        let url = fresh_url("anonymous");
+        let client = polite(&[(&url, "{}")]);
+        client.execute(&get(&url)).unwrap();
+        let requests = client.inner.requests.lock().unwrap();
+        assert_eq!(authorization(&requests[0]), None);
+    }
+
+    #[test]
+    fn repeated_gets_come_from_the_cache() {
+        let url = fresh_url("cached");
+        let client = polite(&[(&url, "first"), (&url, "second")]);
+        assert_eq!(client.execute(&get(&url)).unwrap().body, b"first");
+        assert_eq!(client.execute(&get(&url)).unwrap().body, b"first");
+        assert_eq!(client.inner.requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn requests_past_the_budget_are_refused() {
+        let client = polite(&[]);
+        let url = fresh_url("budget");
+        for _ in 0..REQUEST_BUDGET {
+            // Unrecorded, so the replayer fails them, but they still count.
+            let post = Client::new().post(&url).build().unwrap();
+            assert!(client.execute(&post).is_err());
+        }
+        let Err(error) = client.execute(&get(&url)) else {
+            panic!("a request past the budget went out");
+        };
+        assert!(is_rate_limited(&error));
+        assert_eq!(
+            client.inner.requests.lock().unwrap().len() as u64,
+            REQUEST_BUDGET
+        );
+    }
+
+    #[test]
+    fn other_hosts_pass_through_untouched() {
+        let url = "https://example.com/badge.svg";
+        let client = polite(&[(url, "<svg/>")]);
+        for _ in 0..2 {
+            client.execute(&get(url)).unwrap();
+        }
+        let requests = client.inner.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(authorization(&requests[0]), Some("token secret"));
+        assert_eq!(client.sent.lock().unwrap().0, 0);
+    }
+}
@@ -0,0 +1,277 @@
+//! Pushes generated files to a branch of the profile repository and opens a pull request for
+//! them, through the REST API so no git checkout or push credentials are needed.
+
+use crate::github::send_guarded;
+use crate::http::{status_of, Client, RequestBuilder, StatusCode};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use profile_core::circuit::GITHUB_API;
+use profile_core::config::MergeMethod;
+use serde_json::{json, Value};
+
+pub struct PullRequest {
+    pub number: u64,
+    pub url: String,
+    /// GraphQL id, needed to enable auto-merge.
+    pub node_id: String,
+}
+
+/// The profile repository, which GitHub requires to be named after the user.
+pub fn profile_repo(username: &str) -> String {
+    format!("{0}/{0}", username)
+}
+
+fn authorized(request: RequestBuilder, token: &str) -> RequestBuilder {
+    request
+        .header("Authorization", format!("token {}", token))
+        .header("User-Agent", "Rust GitHub Action")
+        .header("Accept", "application/vnd.github+json")
+}
+
+fn send(request: RequestBuilder, token: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    Ok(send_guarded(GITHUB_API, authorized(request, token))?.json::<Value>()?)
+}
+
+pub fn default_branch(
+    client: &Client,
+    repo: &str,
+    token: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let url = format!("https://api.github.com/repos/{}", repo);
+    let data = send(client.get(&url), token)?;
+    Ok(data["default_branch"]
+        .as_str()
+        .ok_or("unexpected response for the repository")?
+        .to_string())
+}
+
+/// Points `branch` at the head of `base`, creating it or resetting it. Anything pushed to it
+/// before is dropped, so the branch always holds one fresh update.
+pub fn reset_branch(
+    client: &Client,
+    repo: &str,
+    branch: &str,
+    base: &str,
+    token: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let base_url = format!(
+        "https://api.github.com/repos/{}/git/ref/heads/{}",
+        repo, base
+    );
+    let sha = send(client.get(&base_url), token)?["object"]["sha"]
+        .as_str()
+        .ok_or("unexpected response for the base branch")?
+        .to_string();
+
+    let create_url = format!("https://api.github.com/repos/{}/git/refs", repo);
+    let created = send(
+        client
+            .post(&create_url)
+            .json(&json!({ "ref": format!("refs/heads/{}", branch), "sha": sha })),
+        token,
+    );
+    match created {
+        Ok(_) => Ok(()),
+        // 422: the branch already exists.
+        Err(e) if status_of(e.as_ref()) == Some(StatusCode::UNPROCESSABLE_ENTITY) => {
+            let update_url = format!(
+                "https://api.github.com/repos/{}/git/refs/heads/{}",
+                repo, branch
+            );
+            send(
+                client
+                    .patch(&update_url)
+                    .json(&json!({ "sha": sha, "force": true })),
+                token,
+            )
+            .map(|_| ())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Commits `contents` as `path` on `branch`, replacing the file if it exists.
+pub fn put_file(
+    client: &Client,
+    repo: &str,
+    branch: &str,
+    path: &str,
+    contents: &[u8],
+    message: &str,
+    token: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let url = format!("https://api.github.com/repos/{}/contents/{}", repo, path);
+    let existing = match send(client.get(format!("{}?ref={}", url, branch)), token) {
+        Ok(file) => file["sha"].as_str().map(String::from),
+        Err(e) if status_of(e.as_ref()) == Some(StatusCode::NOT_FOUND) => None,
+        Err(e) => return Err(e),
+    };
+    let mut body = json!({
+        "message": message,
+        "content": STANDARD.encode(contents),
+        "branch": branch,
+    });
+    if let Some(sha) = existing {
+        body["sha"] = json!(sha);
+    }
+    send(client.put(&url).json(&body), token).map(|_| ())
+}
+
+/// Commits `files` (path and contents) to `branch` as one commit on top of its head. Returns
+/// `false`, committing nothing, when they already match what the branch holds.
+pub fn commit_files(
+    client: &Client,
+    repo: &str,
+    branch: &str,
+    files: &[(String, Vec<u8>)],
+    message: &str,
+    token: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let git_url = format!("https://api.github.com/repos/{}/git", repo);
+    let head = send(
+        client.get(format!("{}/ref/heads/{}", git_url, branch)),
+        token,
+    )?["object"]["sha"]
+        .as_str()
+        .ok_or("unexpected response for the branch")?
+        .to_string();
+    let base_tree = send(client.get(format!("{}/commits/{}", git_url, head)), token)?["tree"]
+        ["sha"]
+        .as_str()
+        .ok_or("unexpected response for the head commit")?
+        .to_string();
+
+    let mut entries = Vec::new();
+    for (path, contents) in files {
+        // Blobs go up base64-encoded so binary assets survive the trip.
+        let blob = send(
+            client.post(format!("{}/blobs", git_url)).json(&json!({
+                "content": STANDARD.encode(contents),
+                "encoding": "base64",
+            })),
+            token,
+        )?;
+        entries.push(json!({
+            "path": path,
+            "mode": "100644",
+            "type": "blob",
+            "sha": blob["sha"],
+        }));
+    }
+    let tree = send(
+        client
+            .post(format!("{}/trees", git_url))
+            .json(&json!({ "base_tree": base_tree, "tree": entries })),
+        token,
+    )?["sha"]
+        .as_str()
+        .ok_or("unexpected response for the tree")?
+        .to_string();
+    if tree == base_tree {
+        return Ok(false);
+    }
+
+    let commit = send(
+        client.post(format!("{}/commits", git_url)).json(&json!({
+            "message": message,
+            "tree": tree,
+            "parents": [head],
+        })),
+        token,
+    )?["sha"]
+        .as_str()
+        .ok_or("unexpected response for the commit")?
+        .to_string();
+    send(
+        client
+            .patch(format!("{}/refs/heads/{}", git_url, branch))
+            .json(&json!({ "sha": commit })),
+        token,
+    )?;
+    Ok(true)
+}
+
+/// Opens a pull request from `head` into `base`, or updates the body of the one already open.
+pub fn open_pull_request(
+    client: &Client,
+    repo: &str,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    token: &str,
+) -> Result<PullRequest, Box<dyn std::error::Error>> {
+    let owner = repo.split('/').next().unwrap_or(repo);
+    let pulls_url = format!("https://api.github.com/repos/{}/pulls", repo);
+    let open = send(
+        client.get(format!(
+            "{}?state=open&head={}:{}&base={}",
+            pulls_url, owner, head, base
+        )),
+        token,
+    )?;
+    let pull = match open.as_array().and_then(|pulls| pulls.first()) {
+        Some(pull) => {
+            let number = pull["number"].as_u64().unwrap_or(0);
+            send(
+                client
+                    .patch(format!("{}/{}", pulls_url, number))
+                    .json(&json!({ "title": title, "body": body })),
+                token,
+            )?
+        }
+        None => send(
+            client.post(&pulls_url).json(&json!({
+                "title": title,
+                "body": body,
+                "head": head,
+                "base": base,
+            })),
+            token,
+        )?,
+    };
+    Ok(PullRequest {
+        number: pull["number"]
+            .as_u64()
+            .ok_or("unexpected response for the pull request")?,
+        url: pull["html_url"].as_str().unwrap_or_default().to_string(),
+        node_id: pull["node_id"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// Has GitHub merge `pull` once its required reviews and checks pass. There is no REST endpoint
+/// for this, so it is the one GraphQL mutation here.
+pub fn enable_auto_merge(
+    client: &Client,
+    pull: &PullRequest,
+    method: MergeMethod,
+    token: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let method = match method {
+        MergeMethod::Merge => "MERGE",
+        MergeMethod::Squash => "SQUASH",
+        MergeMethod::Rebase => "REBASE",
+    };
+    let query = format!(
+        "mutation {{ enablePullRequestAutoMerge(input: {{ pullRequestId: \"{}\", mergeMethod: {} }}) {{ clientMutationId }} }}",
+        pull.node_id, method
+    );
+    let request = client
+        .post("https://api.github.com/graphql")
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "Rust GitHub Action")
+        .json(&json!({ "query": query }));
+    let response = send_guarded(GITHUB_API, request)?.json::<Value>()?;
+    // Already enabled is fine: the pull request is reused across runs.
+    let errors: Vec<&str> = response["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|error| error["message"].as_str())
+        .filter(|message| !message.contains("already enabled"))
+        .collect();
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(format!("GraphQL error: {}", errors.join("; ")).into()),
+    }
+}
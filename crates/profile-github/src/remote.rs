@@ -0,0 +1,50 @@
+//! Fetching for [`profile_core::remote`]: install [`fetch`] with
+//! [`install_fetcher`](profile_core::remote::install_fetcher) before reading a remote source.
+
+use crate::github::count_request;
+use crate::http::{send, Client, HttpResponse};
+use chrono::Duration;
+use profile_core::anomaly::report_anomaly;
+use profile_core::cache::{read_cache, read_stale_cache, write_cache};
+use profile_core::cancel::check_cancelled;
+use profile_core::remote::resolve_url;
+
+/// Remote files are fetched at most this often; a pinned file can't change anyway.
+const MAX_AGE_HOURS: i64 = 1;
+
+/// Fetches a URL or `github:` source, at most once an hour, falling back to the last copy
+/// when the fetch fails.
+pub fn fetch(source: &str) -> Result<String, String> {
+    let url = resolve_url(source);
+    let key = format!("remote:{}", url);
+    if let Some(cached) = read_cache(&key, Duration::hours(MAX_AGE_HOURS)) {
+        return Ok(cached.as_str().unwrap_or_default().to_string());
+    }
+
+    check_cancelled().map_err(|e| e.to_string())?;
+    count_request();
+    let fetched = send(
+        Client::new()
+            .get(&url)
+            .header("User-Agent", "Rust GitHub Action"),
+    )
+    .and_then(HttpResponse::error_for_status)
+    .map_err(|e| e.to_string())
+    .and_then(|response| response.text().map_err(|e| e.to_string()));
+    match fetched {
+        Ok(text) => {
+            write_cache(&key, &text.clone().into());
+            Ok(text)
+        }
+        Err(e) => match read_stale_cache(&key) {
+            Some(stale) => {
+                report_anomaly(format!(
+                    "Failed to fetch {}: {}; using the cached copy",
+                    url, e
+                ));
+                Ok(stale.as_str().unwrap_or_default().to_string())
+            }
+            None => Err(format!("failed to fetch {}: {}", url, e)),
+        },
+    }
+}
@@ -0,0 +1,195 @@
+//! Fetching of data that has no official API, kept apart from the API helpers in `github`.
+//!
+//! Every page goes through the cache first, requests are spaced out, and paths disallowed by
+//! the site's robots.txt are never requested.
+
+use crate::github::{send_guarded, serve_stale};
+use crate::http::{status_of, Client, StatusCode};
+use chrono::Duration;
+use profile_core::cache::{read_cache, untracked, write_cache};
+use profile_core::circuit::GITHUB_WEB;
+use profile_core::config::ScrapingConfig;
+use serde_json::Value;
+use std::thread;
+use std::time::Instant;
+
+const USER_AGENT: &str =
+    "m4ster-slave_readme_update (profile README generator; +https://github.com/m4ster-slave/m4ster-slave)";
+
+pub struct Scraper {
+    client: Client,
+    enabled: bool,
+    delay: std::time::Duration,
+    last_request: Option<Instant>,
+}
+
+/// Collects the `Disallow` rules that apply to every user agent.
+fn parse_robots(robots: &str) -> Vec<String> {
+    let mut disallowed = Vec::new();
+    let mut applies = false;
+    // Consecutive `User-agent` lines open one group, whose rules apply to all of them.
+    let mut in_agents = false;
+
+    for line in robots.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let field = field.trim().to_ascii_lowercase();
+        if field == "user-agent" {
+            applies = (in_agents && applies) || value.trim() == "*";
+            in_agents = true;
+            continue;
+        }
+        in_agents = false;
+        if field == "disallow" && applies && !value.trim().is_empty() {
+            disallowed.push(value.trim().to_string());
+        }
+    }
+    disallowed
+}
+
+/// Matches a robots.txt path rule, supporting the `*` wildcard and `$` anchor.
+fn rule_matches(rule: &str, path: &str) -> bool {
+    let (rule, anchored) = match rule.strip_suffix('$') {
+        Some(rule) => (rule, true),
+        None => (rule, false),
+    };
+    let mut parts = rule.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+fn split_url(url: &str) -> Option<(&str, &str)> {
+    let after_scheme = url.find("://")? + 3;
+    match url[after_scheme..].find('/') {
+        Some(pos) => Some((&url[..after_scheme + pos], &url[after_scheme + pos..])),
+        None => Some((url, "/")),
+    }
+}
+
+impl Scraper {
+    pub fn new(config: &ScrapingConfig) -> Self {
+        Scraper {
+            client: Client::new(),
+            enabled: config.enabled,
+            delay: std::time::Duration::from_millis(config.delay_ms),
+            last_request: None,
+        }
+    }
+
+    fn fetch(&mut self, url: &str) -> Result<String, Box<dyn std::error::Error>> {
+        if let Some(last_request) = self.last_request {
+            let elapsed = last_request.elapsed();
+            if elapsed < self.delay {
+                thread::sleep(self.delay - elapsed);
+            }
+        }
+        self.last_request = Some(Instant::now());
+
+        let request = self.client.get(url).header("User-Agent", USER_AGENT);
+        Ok(send_guarded(GITHUB_WEB, request)?.text()?)
+    }
+
+    fn get_text_cached(
+        &mut self,
+        url: &str,
+        max_age: Duration,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if let Some(Value::String(text)) = read_cache(url, max_age) {
+            return Ok(text);
+        }
+        let text = match self.fetch(url) {
+            Ok(text) => text,
+            Err(e) => match serve_stale(url, e)? {
+                Value::String(text) => return Ok(text),
+                _ => return Err(format!("unexpected cache entry for {}", url).into()),
+            },
+        };
+        write_cache(url, &Value::String(text.clone()));
+        Ok(text)
+    }
+
+    fn is_allowed(&mut self, url: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let (origin, path) = split_url(url).ok_or("invalid URL")?;
+        let robots_url = format!("{}/robots.txt", origin);
+        // No robots.txt means no restrictions; any other failure means we don't know, so don't.
+        let robots = match untracked(|| self.get_text_cached(&robots_url, Duration::days(7))) {
+            Ok(robots) => robots,
+            Err(e) if status_of(e.as_ref()) == Some(StatusCode::NOT_FOUND) => String::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(!parse_robots(&robots)
+            .iter()
+            .any(|rule| rule_matches(rule, path)))
+    }
+
+    /// Fetches a page for scraping, serving it from the cache while it is younger than `max_age`.
+    pub fn get_html(
+        &mut self,
+        url: &str,
+        max_age: Duration,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if !self.enabled {
+            return Err("scraping is disabled in the config".into());
+        }
+        if let Some(Value::String(html)) = read_cache(url, max_age) {
+            return Ok(html);
+        }
+        if !self.is_allowed(url)? {
+            return Err(format!("{} is disallowed by robots.txt", url).into());
+        }
+        self.get_text_cached(url, max_age)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_robots, rule_matches};
+
+    #[test]
+    fn only_rules_for_every_agent_are_kept() {
+        let robots = "\
+User-agent: Googlebot
+Disallow: /google-only
+
+User-agent: *
+Disallow: /private # not for anyone
+Disallow:
+Allow: /private/public
+";
+        assert_eq!(parse_robots(robots), ["/private"]);
+    }
+
+    #[test]
+    fn consecutive_agents_share_their_rules() {
+        for robots in [
+            "User-agent: *\nUser-agent: Googlebot\nDisallow: /shared\n",
+            "User-agent: Googlebot\nuser-agent: *\nDisallow: /shared\n",
+        ] {
+            assert_eq!(parse_robots(robots), ["/shared"], "{}", robots);
+        }
+        let robots = "User-agent: *\nDisallow: /a\nUser-agent: Googlebot\nDisallow: /b\n";
+        assert_eq!(parse_robots(robots), ["/a"]);
+    }
+
+    #[test]
+    fn rules_match_prefixes_wildcards_and_anchors() {
+        assert!(rule_matches("/private", "/private/page"));
+        assert!(!rule_matches("/private", "/public"));
+        assert!(rule_matches("/*/stars", "/octocat/stars"));
+        assert!(!rule_matches("/*/stars", "/octocat/followers"));
+        assert!(rule_matches("/*.json$", "/data/x.json"));
+        assert!(!rule_matches("/*.json$", "/data/x.json?page=2"));
+        assert!(rule_matches("/", "/anything"));
+    }
+}
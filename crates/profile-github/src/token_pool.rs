@@ -0,0 +1,283 @@
+//! Several tokens taking turns, for batch runs over more profiles than one token's rate limit
+//! covers. Each GitHub API request goes out with whichever token GitHub last reported the most
+//! requests left for, counted separately for REST, GraphQL and search as GitHub does.
+
+use crate::http::{HttpClient, HttpError, HttpRequest, HttpResponse, StatusCode};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+/// Tokens beyond `GITHUB_TOKEN`, separated by commas or whitespace.
+pub const TOKENS_VAR: &str = "GITHUB_TOKENS";
+
+/// `GITHUB_TOKEN` followed by the tokens in [`TOKENS_VAR`], each once.
+pub fn github_tokens() -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let vars = [env::var("GITHUB_TOKEN"), env::var(TOKENS_VAR)];
+    for token in vars
+        .iter()
+        .flatten()
+        .flat_map(|value| value.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|token| !token.is_empty())
+    {
+        if !tokens.iter().any(|known| known == token) {
+            tokens.push(token.to_string());
+        }
+    }
+    tokens
+}
+
+/// The token requests are built with; [`TokenPool`] swaps it for another as it sees fit.
+pub fn github_token() -> Option<String> {
+    github_tokens().into_iter().next()
+}
+
+/// The rate limit a request to `url` counts against.
+fn resource(url: &str) -> &'static str {
+    if url.ends_with("/graphql") {
+        "graphql"
+    } else if url.starts_with("https://api.github.com/search/") {
+        "search"
+    } else {
+        "core"
+    }
+}
+
+/// What a response said about its token's rate limit.
+struct Limit {
+    remaining: u64,
+    /// Unix time the limit resets at.
+    reset: i64,
+}
+
+impl Limit {
+    fn from_response(response: &HttpResponse) -> Option<Self> {
+        Some(Limit {
+            remaining: response.header("x-ratelimit-remaining")?.parse().ok()?,
+            reset: response.header("x-ratelimit-reset")?.parse().ok()?,
+        })
+    }
+}
+
+fn is_rate_limited(response: &HttpResponse) -> bool {
+    (response.status == StatusCode::FORBIDDEN || response.status == StatusCode::TOO_MANY_REQUESTS)
+        && response.header("x-ratelimit-remaining") == Some("0")
+}
+
+/// Sends GitHub API requests through `inner` with the token that has the most of its rate limit
+/// left, trying the next one when a token turns out to be used up. Requests elsewhere, or
+/// without credentials, pass straight through.
+pub struct TokenPool<C> {
+    inner: C,
+    tokens: Vec<String>,
+    /// The last limit reported, by token index and resource.
+    limits: Mutex<HashMap<(usize, &'static str), Limit>>,
+}
+
+impl<C: HttpClient> TokenPool<C> {
+    pub fn new(inner: C, tokens: Vec<String>) -> Self {
+        TokenPool {
+            inner,
+            tokens,
+            limits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Requests left for token `index`; a token not heard from yet, or whose limit has reset
+    /// since, counts as untouched.
+    fn remaining(&self, index: usize, resource: &'static str) -> u64 {
+        match self.limits.lock().unwrap().get(&(index, resource)) {
+            Some(limit) if limit.reset > Utc::now().timestamp() => limit.remaining,
+            _ => u64::MAX,
+        }
+    }
+
+    /// The token with the most requests left among those not `tried`, the first on a tie.
+    fn pick(&self, resource: &'static str, tried: &[usize]) -> Option<usize> {
+        (0..self.tokens.len())
+            .filter(|index| !tried.contains(index))
+            .map(|index| (self.remaining(index, resource), index))
+            .max_by_key(|(remaining, index)| (*remaining, std::cmp::Reverse(*index)))
+            .map(|(_, index)| index)
+    }
+}
+
+impl<C: HttpClient> HttpClient for TokenPool<C> {
+    fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+        // "token …" for REST, "Bearer …" for GraphQL; the scheme stays, the credential changes.
+        let scheme = request.headers.iter().find_map(|(name, value)| {
+            let (scheme, _) = value.split_once(' ')?;
+            name.eq_ignore_ascii_case("authorization")
+                .then(|| scheme.to_string())
+        });
+        let Some(scheme) = scheme.filter(|_| request.url.starts_with("https://api.github.com/"))
+        else {
+            return self.inner.execute(request);
+        };
+
+        let resource = resource(&request.url);
+        let mut tried = Vec::new();
+        loop {
+            // Only an empty pool has nothing to pick the first time round.
+            let Some(index) = self.pick(resource, &tried) else {
+                return self.inner.execute(request);
+            };
+            tried.push(index);
+            let authorized = HttpRequest {
+                method: request.method.clone(),
+                url: request.url.clone(),
+                headers: request
+                    .headers
+                    .iter()
+                    .map(
+                        |(name, value)| match name.eq_ignore_ascii_case("authorization") {
+                            true => (name.clone(), format!("{} {}", scheme, self.tokens[index])),
+                            false => (name.clone(), value.clone()),
+                        },
+                    )
+                    .collect(),
+                body: request.body.clone(),
+                timeout: request.timeout,
+            };
+            let response = self.inner.execute(&authorized)?;
+            if let Some(limit) = Limit::from_response(&response) {
+                self.limits.lock().unwrap().insert((index, resource), limit);
+            }
+            if !is_rate_limited(&response) || tried.len() == self.tokens.len() {
+                return Ok(response);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::Client;
+
+    /// Answers every request with `answer` and keeps the `Authorization` each one went out with.
+    struct Fake {
+        answer: fn(Option<&str>) -> HttpResponse,
+        seen: Mutex<Vec<Option<String>>>,
+    }
+
+    impl HttpClient for Fake {
+        fn execute(&self, request: &HttpRequest) -> Result<HttpResponse, HttpError> {
+            let authorization = request
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+                .map(|(_, value)| value.as_str());
+            self.seen
+                .lock()
+                .unwrap()
+                .push(authorization.map(str::to_string));
+            Ok((self.answer)(authorization))
+        }
+    }
+
+    fn response(status: StatusCode, remaining: u64) -> HttpResponse {
+        HttpResponse {
+            url: String::new(),
+            status,
+            headers: vec![
+                ("x-ratelimit-remaining".to_string(), remaining.to_string()),
+                (
+                    "x-ratelimit-reset".to_string(),
+                    (Utc::now().timestamp() + 3600).to_string(),
+                ),
+            ],
+            body: Vec::new(),
+        }
+    }
+
+    fn pool(answer: fn(Option<&str>) -> HttpResponse) -> TokenPool<Fake> {
+        let fake = Fake {
+            answer,
+            seen: Mutex::new(Vec::new()),
+        };
+        TokenPool::new(fake, vec!["a".to_string(), "b".to_string()])
+    }
+
+    fn seen(pool: &TokenPool<Fake>) -> Vec<Option<String>> {
+        pool.inner.seen.lock().unwrap().clone()
+    }
+
+    fn get(url: &str, token: Option<&str>) -> HttpRequest {
+        let mut request = Client::new().get(url);
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("token {}", token));
+        }
+        request.build().unwrap()
+    }
+
+    fn set_limit(pool: &TokenPool<Fake>, index: usize, remaining: u64, reset: i64) {
+        pool.limits
+            .lock()
+            .unwrap()
+            .insert((index, "core"), Limit { remaining, reset });
+    }
+
+    #[test]
+    fn picks_the_token_with_the_most_left_and_the_first_on_a_tie() {
+        let pool = pool(|_| response(StatusCode::OK, 100));
+        assert_eq!(pool.pick("core", &[]), Some(0));
+        let later = Utc::now().timestamp() + 3600;
+        set_limit(&pool, 0, 10, later);
+        set_limit(&pool, 1, 50, later);
+        assert_eq!(pool.pick("core", &[]), Some(1));
+        assert_eq!(pool.pick("core", &[1]), Some(0));
+        // Limits are kept per resource.
+        assert_eq!(pool.pick("graphql", &[]), Some(0));
+    }
+
+    #[test]
+    fn a_limit_past_its_reset_counts_as_fresh() {
+        let pool = pool(|_| response(StatusCode::OK, 100));
+        set_limit(&pool, 0, 0, Utc::now().timestamp() - 1);
+        set_limit(&pool, 1, 50, Utc::now().timestamp() + 3600);
+        assert_eq!(pool.pick("core", &[]), Some(0));
+    }
+
+    #[test]
+    fn a_used_up_token_fails_over_to_the_next() {
+        let pool = pool(|authorization| match authorization {
+            Some("token a") => response(StatusCode::FORBIDDEN, 0),
+            _ => response(StatusCode::OK, 4999),
+        });
+        let answer = pool
+            .execute(&get("https://api.github.com/user", Some("a")))
+            .unwrap();
+        assert_eq!(answer.status, StatusCode::OK);
+        assert_eq!(
+            seen(&pool),
+            [Some("token a".to_string()), Some("token b".to_string())]
+        );
+        // The next request starts with the token that has requests left.
+        pool.execute(&get("https://api.github.com/user", Some("a")))
+            .unwrap();
+        assert_eq!(seen(&pool)[2].as_deref(), Some("token b"));
+    }
+
+    #[test]
+    fn the_last_limit_error_comes_back_once_every_token_is_tried() {
+        let pool = pool(|_| response(StatusCode::FORBIDDEN, 0));
+        let answer = pool
+            .execute(&get("https://api.github.com/user", Some("a")))
+            .unwrap();
+        assert_eq!(answer.status, StatusCode::FORBIDDEN);
+        assert_eq!(seen(&pool).len(), 2);
+    }
+
+    #[test]
+    fn requests_without_credentials_or_off_the_api_pass_through() {
+        let pool = pool(|_| response(StatusCode::OK, 100));
+        pool.execute(&get("https://api.github.com/users/octocat", None))
+            .unwrap();
+        pool.execute(&get("https://example.com/badge.svg", Some("secret")))
+            .unwrap();
+        assert_eq!(seen(&pool), [None, Some("token secret".to_string())]);
+        assert!(pool.limits.lock().unwrap().is_empty());
+    }
+}
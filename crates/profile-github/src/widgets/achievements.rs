@@ -0,0 +1,15 @@
+use crate::scrape::Scraper;
+use chrono::Duration;
+use profile_core::widgets::achievements::{parse_achievements, Achievement};
+
+/// Achievements change rarely, and the page is scraped rather than fetched from an API.
+const MAX_AGE_HOURS: i64 = 24;
+
+pub fn get_achievements(
+    scraper: &mut Scraper,
+    username: &str,
+) -> Result<Vec<Achievement>, Box<dyn std::error::Error>> {
+    let url = format!("https://github.com/{}?tab=achievements", username);
+    let html = scraper.get_html(&url, Duration::hours(MAX_AGE_HOURS))?;
+    Ok(parse_achievements(&html))
+}
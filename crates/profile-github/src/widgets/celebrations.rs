@@ -0,0 +1,34 @@
+use crate::notify::send_webhook;
+use chrono::{Duration, NaiveDate};
+use profile_core::anomaly::report_anomaly;
+use profile_core::cache::{read_cache, write_cache};
+use profile_core::config::CelebrationsConfig;
+use profile_core::widgets::celebrations::Celebration;
+use serde_json::json;
+
+/// Announces milestones crossed today on the configured webhook, once per milestone.
+pub fn notify_celebrations(
+    config: &CelebrationsConfig,
+    celebrations: &[Celebration],
+    today: NaiveDate,
+) {
+    let Some(webhook) = &config.webhook else {
+        return;
+    };
+
+    for celebration in celebrations.iter().filter(|c| c.crossed_on == today) {
+        let key = format!(
+            "celebration:{}:{}:{}",
+            celebration.metric, celebration.threshold, celebration.crossed_on
+        );
+        if read_cache(&key, Duration::days(config.days)).is_some() {
+            continue;
+        }
+
+        let message = format!("🎉 Just crossed {}", celebration.headline());
+        match send_webhook(webhook, &message) {
+            Ok(()) => write_cache(&key, &json!(true)),
+            Err(e) => report_anomaly(format!("Failed to send milestone notification: {}", e)),
+        }
+    }
+}
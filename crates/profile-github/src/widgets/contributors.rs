@@ -0,0 +1,49 @@
+use crate::github::{get_json_cached, get_most_popular_repo};
+use crate::http::Client;
+use chrono::Duration;
+use profile_core::config::ContributorsConfig;
+use profile_core::widgets::contributors::{Contributor, Contributors};
+
+pub fn get_contributors(
+    config: &ContributorsConfig,
+    username: &str,
+    token: &str,
+) -> Result<Contributors, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let repo = match &config.repo {
+        Some(repo) => repo.clone(),
+        None => get_most_popular_repo(&client, username, token)?,
+    };
+
+    let url = format!(
+        "https://api.github.com/repos/{}/contributors?per_page=100",
+        repo
+    );
+    let mut contributors: Vec<Contributor> =
+        get_json_cached(&client, &url, token, Duration::hours(24))?
+            .as_array()
+            .ok_or("unexpected response for contributors")?
+            .iter()
+            .filter_map(|contributor| {
+                let login = contributor["login"].as_str()?;
+                // Thanking yourself or a bot is not much of a thank-you.
+                if login.eq_ignore_ascii_case(username) || contributor["type"] == "Bot" {
+                    return None;
+                }
+                Some(Contributor {
+                    login: login.to_string(),
+                    avatar_url: contributor["avatar_url"].as_str().unwrap_or("").to_string(),
+                    contributions: contributor["contributions"].as_u64().unwrap_or(0),
+                })
+            })
+            .collect();
+
+    contributors.sort_by(|a, b| {
+        b.contributions
+            .cmp(&a.contributions)
+            .then_with(|| a.login.to_lowercase().cmp(&b.login.to_lowercase()))
+    });
+    contributors.truncate(config.limit);
+
+    Ok(Contributors { repo, contributors })
+}
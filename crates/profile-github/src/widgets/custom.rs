@@ -0,0 +1,16 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::Duration;
+use profile_core::config::CustomWidgetConfig;
+use profile_core::widgets::custom::{render_custom_widget, validate_query};
+
+pub fn get_custom_widget(
+    widget: &CustomWidgetConfig,
+    username: &str,
+    token: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    validate_query(&widget.query)?;
+    let query = widget.query.replace("{username}", username);
+    let data = graphql_cached(&Client::new(), &query, token, Duration::hours(6))?;
+    render_custom_widget(widget, &data)
+}
@@ -0,0 +1,89 @@
+use crate::github::get_json_cached;
+use crate::http::Client;
+use chrono::{DateTime, Duration, NaiveDate};
+use profile_core::history::History;
+use profile_core::history::WeeklyDigest;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// The public events API serves at most three pages of 100.
+const EVENT_PAGES: usize = 3;
+
+fn event_date(event: &Value) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(event["created_at"].as_str()?)
+        .ok()
+        .map(|date| date.date_naive())
+}
+
+pub fn get_weekly_digest(
+    username: &str,
+    token: &str,
+    history: &History,
+    week_start: NaiveDate,
+) -> Result<WeeklyDigest, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let week_end = week_start + Duration::days(6);
+
+    let mut events = Vec::new();
+    for page in 1..=EVENT_PAGES {
+        let url = format!(
+            "https://api.github.com/users/{}/events/public?per_page=100&page={}",
+            username, page
+        );
+        let batch = get_json_cached(&client, &url, token, Duration::hours(1))?;
+        let batch = batch.as_array().ok_or("unexpected response for events")?;
+        let reached_older = batch
+            .last()
+            .and_then(event_date)
+            .is_some_and(|date| date < week_start);
+        events.extend(batch.iter().cloned());
+        if batch.len() < 100 || reached_older {
+            break;
+        }
+    }
+
+    let mut commits = 0;
+    let mut repos = BTreeSet::new();
+    let mut prs_merged = 0;
+    for event in &events {
+        if !event_date(event).is_some_and(|date| date >= week_start && date <= week_end) {
+            continue;
+        }
+        match event["type"].as_str() {
+            Some("PushEvent") => {
+                commits += event["payload"]["distinct_size"]
+                    .as_u64()
+                    .or_else(|| event["payload"]["size"].as_u64())
+                    .unwrap_or(0);
+                if let Some(repo) = event["repo"]["name"].as_str() {
+                    repos.insert(repo.to_string());
+                }
+            }
+            Some("PullRequestEvent")
+                if event["payload"]["action"] == "closed"
+                    && event["payload"]["pull_request"]["merged"] == true =>
+            {
+                prs_merged += 1;
+            }
+            _ => {}
+        }
+    }
+
+    // Followers at the end of the week minus followers before it started.
+    let followers_at = |date: NaiveDate| history.previous(date).and_then(|s| s.get("followers"));
+    let follower_delta = match (
+        followers_at(week_start),
+        followers_at(week_end + Duration::days(1)),
+    ) {
+        (Some(before), Some(after)) => after as i64 - before as i64,
+        _ => 0,
+    };
+
+    Ok(WeeklyDigest {
+        week_start,
+        commits,
+        repos: repos.len(),
+        prs_merged,
+        follower_delta,
+    })
+}
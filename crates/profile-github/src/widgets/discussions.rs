@@ -0,0 +1,96 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::{DateTime, Duration};
+use profile_core::config::DiscussionsConfig;
+use profile_core::widgets::discussions::{AnsweredThread, DiscussionStats};
+use serde_json::Value;
+
+/// The fields of an answer this widget reads, shared by both connections it queries.
+const ANSWER_FRAGMENT: &str = r#"
+fragment Answer on DiscussionComment {
+  createdAt
+  discussion { title repository { nameWithOwner } }
+}
+"#;
+
+/// Answers are fetched in one page and filtered by repository afterwards.
+const ANSWER_PAGE: usize = 100;
+
+fn answered_thread(answer: &Value) -> Option<AnsweredThread> {
+    Some(AnsweredThread {
+        repo: answer["discussion"]["repository"]["nameWithOwner"]
+            .as_str()?
+            .to_string(),
+        title: answer["discussion"]["title"].as_str()?.to_string(),
+        answered_on: DateTime::parse_from_rfc3339(answer["createdAt"].as_str()?)
+            .ok()?
+            .format("%Y-%m-%d")
+            .to_string(),
+    })
+}
+
+/// The user's discussion replies and accepted answers, limited to `config.repos` if any are
+/// listed. Totals are exact without a filter; with one they only count the first page of 100.
+pub fn get_discussion_stats(
+    config: &DiscussionsConfig,
+    username: &str,
+    token: &str,
+) -> Result<DiscussionStats, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let query = format!(
+        r#"
+        query {{
+          user(login: "{}") {{
+            replies: repositoryDiscussionComments(first: {page}) {{
+              totalCount
+              nodes {{ ...Answer }}
+            }}
+            answers: repositoryDiscussionComments(first: {page}, onlyAnswers: true) {{
+              totalCount
+              nodes {{ ...Answer }}
+            }}
+          }}
+        }}
+        {}"#,
+        username,
+        ANSWER_FRAGMENT,
+        page = ANSWER_PAGE
+    );
+    let data = graphql_cached(&client, &query, token, Duration::hours(12))?;
+    let user = &data["user"];
+
+    let in_scope = |answer: &&Value| {
+        let repo = answer["discussion"]["repository"]["nameWithOwner"].as_str();
+        config.repos.is_empty()
+            || config
+                .repos
+                .iter()
+                .any(|listed| Some(listed.as_str()) == repo)
+    };
+    let count = |connection: &Value| -> Result<u64, Box<dyn std::error::Error>> {
+        let nodes = connection["nodes"]
+            .as_array()
+            .ok_or("unexpected response for discussion comments")?;
+        Ok(match config.repos.is_empty() {
+            true => connection["totalCount"].as_u64().unwrap_or(0),
+            false => nodes.iter().filter(in_scope).count() as u64,
+        })
+    };
+
+    // The connection has no ordering argument, so the newest answers are picked here.
+    let mut recent: Vec<AnsweredThread> = user["answers"]["nodes"]
+        .as_array()
+        .ok_or("unexpected response for discussion answers")?
+        .iter()
+        .filter(in_scope)
+        .filter_map(answered_thread)
+        .collect();
+    recent.sort_by(|a, b| b.answered_on.cmp(&a.answered_on));
+    recent.truncate(config.recent);
+
+    Ok(DiscussionStats {
+        replies: count(&user["replies"])?,
+        accepted: count(&user["answers"])?,
+        recent,
+    })
+}
@@ -0,0 +1,57 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::NaiveDate;
+use chrono::{Datelike, Duration};
+use profile_core::config::EventConfig;
+use profile_core::widgets::events::EventProgress;
+
+/// `MM-DD` in `year`.
+fn on(year: i32, month_day: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-{}", year, month_day), "%Y-%m-%d")
+        .map_err(|_| format!("`{}` is not a MM-DD date", month_day))
+}
+
+/// This year's run of the event if `today` is in it. An event ending on an earlier day of the
+/// year than it starts (`12-15` to `01-15`) runs over the new year.
+pub fn event_window(
+    config: &EventConfig,
+    today: NaiveDate,
+) -> Result<Option<(NaiveDate, NaiveDate)>, String> {
+    let year = today.year();
+    let (mut from, mut to) = (on(year, &config.from)?, on(year, &config.to)?);
+    if to < from {
+        match today >= from {
+            true => to = on(year + 1, &config.to)?,
+            false => from = on(year - 1, &config.from)?,
+        }
+    }
+    Ok(Some((from, to)).filter(|(from, to)| (*from..=*to).contains(&today)))
+}
+
+/// Counts the user's pull requests opened within the event that match its qualifiers.
+pub fn get_event_progress(
+    config: &EventConfig,
+    username: &str,
+    token: &str,
+    (from, to): (NaiveDate, NaiveDate),
+) -> Result<EventProgress, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let search = format!(
+        "author:{} is:pr created:{}..{} {}",
+        username, from, to, config.qualifiers
+    );
+    let query = format!(
+        r#"query {{ search(query: "{}", type: ISSUE, first: 0) {{ issueCount }} }}"#,
+        search.trim().replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    let data = graphql_cached(&client, &query, token, Duration::hours(1))?;
+    Ok(EventProgress {
+        name: config.name.clone(),
+        from,
+        to,
+        pull_requests: data["search"]["issueCount"]
+            .as_u64()
+            .ok_or("unexpected response for the event search")?,
+        goal: config.goal,
+    })
+}
@@ -0,0 +1,54 @@
+use crate::github::get_json_cached;
+use crate::http::Client;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use base64::Engine;
+use chrono::Duration;
+use profile_core::widgets::keys::{GpgKey, Keys, SshKey};
+use sha2::{Digest, Sha256};
+
+/// Computes the OpenSSH-style `SHA256:...` fingerprint of a `<type> <base64 blob>` public key.
+fn ssh_fingerprint(public_key: &str) -> Option<SshKey> {
+    let mut parts = public_key.split_whitespace();
+    let key_type = parts.next()?;
+    let blob = STANDARD.decode(parts.next()?).ok()?;
+
+    Some(SshKey {
+        key_type: key_type.trim_start_matches("ssh-").to_string(),
+        fingerprint: format!("SHA256:{}", STANDARD_NO_PAD.encode(Sha256::digest(&blob))),
+    })
+}
+
+pub fn get_keys(username: &str, token: &str) -> Result<Keys, Box<dyn std::error::Error>> {
+    let client = Client::new();
+
+    let gpg_url = format!("https://api.github.com/users/{}/gpg_keys", username);
+    let gpg = get_json_cached(&client, &gpg_url, token, Duration::hours(24))?
+        .as_array()
+        .ok_or("unexpected response for GPG keys")?
+        .iter()
+        .filter_map(|key| {
+            Some(GpgKey {
+                key_id: key["key_id"].as_str()?.to_uppercase(),
+                emails: key["emails"]
+                    .as_array()
+                    .map(|emails| {
+                        emails
+                            .iter()
+                            .filter_map(|email| email["email"].as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    let ssh_url = format!("https://api.github.com/users/{}/keys", username);
+    let ssh = get_json_cached(&client, &ssh_url, token, Duration::hours(24))?
+        .as_array()
+        .ok_or("unexpected response for SSH keys")?
+        .iter()
+        .filter_map(|key| ssh_fingerprint(key["key"].as_str()?))
+        .collect();
+
+    Ok(Keys { gpg, ssh })
+}
@@ -0,0 +1,33 @@
+use crate::github::get_json_cached;
+use crate::http::{status_of, Client, StatusCode};
+use chrono::Duration;
+use profile_core::config::MentorshipEntry;
+use profile_core::widgets::mentorship::{github_target, short_link};
+
+/// Indices of the entries whose GitHub repository or pull request is gone, with the reason.
+/// Links elsewhere are left to the dead link check, and a link that can't be checked right now
+/// is given the benefit of the doubt.
+pub fn find_missing_links(entries: &[MentorshipEntry], token: &str) -> Vec<(usize, String)> {
+    let client = Client::new();
+    let mut missing = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let Some(link) = entry.link.as_deref() else {
+            continue;
+        };
+        let Some((repo, number)) = github_target(link) else {
+            continue;
+        };
+        // The issues endpoint answers for pull requests too.
+        let url = match number {
+            Some(number) => format!("https://api.github.com/repos/{}/issues/{}", repo, number),
+            None => format!("https://api.github.com/repos/{}", repo),
+        };
+        if let Err(e) = get_json_cached(&client, &url, token, Duration::days(1)) {
+            let status = status_of(e.as_ref());
+            if status == Some(StatusCode::NOT_FOUND) || status == Some(StatusCode::GONE) {
+                missing.push((index, format!("{} no longer exists", short_link(link))));
+            }
+        }
+    }
+    missing
+}
@@ -0,0 +1,18 @@
+pub mod achievements;
+pub mod celebrations;
+pub mod contributors;
+pub mod custom;
+pub mod digest;
+pub mod discussions;
+pub mod events;
+pub mod keys;
+pub mod mentorship;
+pub mod pr_sizes;
+pub mod proofs;
+pub mod releases;
+pub mod responsiveness;
+pub mod review_balance;
+pub mod spotlight;
+pub mod stargazers;
+pub mod timeline;
+pub mod triage;
@@ -0,0 +1,35 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::Duration;
+use profile_core::config::PrSizesConfig;
+use profile_core::widgets::pr_sizes::PrSizes;
+
+/// Search returns at most 100 results per page.
+const MAX_SAMPLE: usize = 100;
+
+pub fn get_pr_sizes(
+    config: &PrSizesConfig,
+    username: &str,
+    token: &str,
+) -> Result<PrSizes, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let query = format!(
+        r#"
+        query {{
+          search(query: "author:{} is:pr sort:created-desc", type: ISSUE, first: {}) {{
+            nodes {{ ... on PullRequest {{ additions deletions }} }}
+          }}
+        }}
+        "#,
+        username,
+        config.sample.clamp(1, MAX_SAMPLE)
+    );
+    let data = graphql_cached(&client, &query, token, Duration::hours(24))?;
+    let sizes = data["search"]["nodes"]
+        .as_array()
+        .ok_or("unexpected response for pull requests")?
+        .iter()
+        .filter_map(|pr| Some(pr["additions"].as_u64()? + pr["deletions"].as_u64()?))
+        .collect();
+    Ok(PrSizes { sizes })
+}
@@ -0,0 +1,12 @@
+use crate::links::find_dead_links;
+use profile_core::config::LinksConfig;
+use profile_core::widgets::proofs::Proof;
+
+/// Proof URLs that don't resolve, with the reason, checked like the README's links.
+pub fn unresolved_proofs(proofs: &[Proof], links: &LinksConfig) -> Vec<(String, String)> {
+    let urls: Vec<&str> = proofs
+        .iter()
+        .filter_map(|proof| proof.url.as_deref())
+        .collect();
+    find_dead_links(&urls.join("\n"), links)
+}
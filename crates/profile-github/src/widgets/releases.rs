@@ -0,0 +1,106 @@
+use crate::github::{get_json_cached, get_most_popular_repo};
+use crate::http::Client;
+use chrono::{DateTime, Duration};
+use profile_core::config::ReleasesConfig;
+use profile_core::widgets::releases::{strip_html, Release};
+
+/// Replaces `[text](url)` with `text` and drops images entirely.
+fn strip_links(line: &str) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find("](").map(|i| open + i) else {
+            break;
+        };
+        let Some(end) = rest[close..].find(')').map(|i| close + i) else {
+            break;
+        };
+        let is_image = rest[..open].ends_with('!');
+        result += &rest[..if is_image { open - 1 } else { open }];
+        if !is_image {
+            result += &rest[open + 1..close];
+        }
+        rest = &rest[end + 1..];
+    }
+
+    result + rest
+}
+
+/// Turns one line of release-note markdown into plain text, or `None` if nothing readable is left.
+fn strip_markdown_line(line: &str) -> Option<String> {
+    let mut line = line.trim();
+    if line.starts_with("```") || line.chars().all(|c| "-*_=|: ".contains(c)) {
+        return None;
+    }
+
+    line = line.trim_start_matches('#').trim_start_matches('>').trim();
+    let bullet = ["- ", "* ", "+ "]
+        .iter()
+        .any(|marker| line.starts_with(marker));
+    if bullet {
+        line = &line[2..];
+    }
+
+    let text = strip_links(line)
+        .replace("**", "")
+        .replace("__", "")
+        .replace("~~", "")
+        .replace('`', "");
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else if bullet {
+        Some(format!("• {}", text))
+    } else {
+        Some(text.to_string())
+    }
+}
+
+fn truncate(line: &str, width: usize) -> String {
+    if line.chars().count() <= width {
+        line.to_string()
+    } else {
+        let mut truncated: String = line.chars().take(width.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+pub fn get_latest_release(
+    config: &ReleasesConfig,
+    username: &str,
+    token: &str,
+) -> Result<Release, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let repo = match &config.repo {
+        Some(repo) => repo.clone(),
+        None => get_most_popular_repo(&client, username, token)?,
+    };
+
+    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
+    let release = get_json_cached(&client, &url, token, Duration::hours(6))?;
+
+    let body = strip_html(release["body"].as_str().unwrap_or(""));
+    let lines: Vec<String> = body.lines().filter_map(strip_markdown_line).collect();
+    let excerpt: Vec<String> = lines
+        .iter()
+        .take(config.lines)
+        .map(|line| truncate(line, config.width))
+        .collect();
+
+    let published_at = release["published_at"]
+        .as_str()
+        .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    Ok(Release {
+        tag: release["tag_name"].as_str().unwrap_or("").to_string(),
+        name: release["name"].as_str().unwrap_or("").to_string(),
+        truncated: lines.len() > excerpt.len(),
+        repo,
+        published_at,
+        excerpt,
+    })
+}
@@ -0,0 +1,100 @@
+use crate::github::get_json_cached;
+use crate::http::Client;
+use chrono::{Duration, Utc};
+use profile_core::config::ResponsivenessConfig;
+use profile_core::widgets::responsiveness::{first_reaction_delay, parse_time, Responsiveness};
+use serde_json::Value;
+
+/// Timeline events that count as a maintainer reacting to an issue.
+const RESPONSE_EVENTS: [&str; 4] = ["commented", "labeled", "assigned", "closed"];
+
+/// Open-issue age buckets as (label, exclusive upper bound in days).
+const AGE_BUCKETS: [(&str, i64); 4] = [
+    ("< 1 week", 7),
+    ("1-4 weeks", 28),
+    ("1-3 months", 90),
+    ("> 3 months", i64::MAX),
+];
+
+fn median(mut durations: Vec<Duration>) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    durations.sort();
+    let mid = durations.len() / 2;
+    if durations.len().is_multiple_of(2) {
+        Some((durations[mid - 1] + durations[mid]) / 2)
+    } else {
+        Some(durations[mid])
+    }
+}
+
+pub fn get_responsiveness(
+    config: &ResponsivenessConfig,
+    token: &str,
+) -> Result<Responsiveness, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let now = Utc::now();
+
+    let open_url = format!(
+        "https://api.github.com/repos/{}/issues?state=open&per_page=100",
+        config.repo
+    );
+    let mut open_ages: Vec<(&'static str, usize)> =
+        AGE_BUCKETS.iter().map(|(label, _)| (*label, 0)).collect();
+    for issue in get_json_cached(&client, &open_url, token, Duration::hours(6))?
+        .as_array()
+        .ok_or("unexpected response for open issues")?
+        .iter()
+        .filter(|issue| issue.get("pull_request").is_none())
+    {
+        if let Some(created_at) = parse_time(&issue["created_at"]) {
+            let age_days = (now - created_at).num_days();
+            let bucket = AGE_BUCKETS
+                .iter()
+                .position(|(_, max_days)| age_days < *max_days)
+                .unwrap_or(AGE_BUCKETS.len() - 1);
+            open_ages[bucket].1 += 1;
+        }
+    }
+
+    let recent_url = format!(
+        "https://api.github.com/repos/{}/issues?state=all&sort=created&direction=desc&per_page=100",
+        config.repo
+    );
+    let recent = get_json_cached(&client, &recent_url, token, Duration::hours(6))?;
+    let sample: Vec<&Value> = recent
+        .as_array()
+        .ok_or("unexpected response for recent issues")?
+        .iter()
+        .filter(|issue| issue.get("pull_request").is_none())
+        .take(config.sample)
+        .collect();
+
+    let mut delays = Vec::new();
+    for issue in &sample {
+        let timeline_url = format!(
+            "https://api.github.com/repos/{}/issues/{}/timeline?per_page=100",
+            config.repo,
+            issue["number"].as_u64().unwrap_or(0)
+        );
+        // Closed issues rarely change, so their timelines can stay cached much longer.
+        let max_age = if issue["state"] == "closed" {
+            Duration::days(30)
+        } else {
+            Duration::hours(12)
+        };
+        let timeline = get_json_cached(&client, &timeline_url, token, max_age)?;
+        if let Some(delay) = first_reaction_delay(issue, &timeline, &RESPONSE_EVENTS) {
+            delays.push(delay);
+        }
+    }
+
+    Ok(Responsiveness {
+        repo: config.repo.clone(),
+        responded: delays.len(),
+        sampled: sample.len(),
+        median_first_response: median(delays),
+        open_ages,
+    })
+}
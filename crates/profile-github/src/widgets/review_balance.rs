@@ -0,0 +1,78 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::NaiveDate;
+use chrono::{Duration, NaiveTime};
+use profile_core::widgets::review_balance::{ReviewBalance, REVIEW_WINDOW_DAYS};
+
+/// Sums the reviews in `reviews` (GraphQL review nodes) and their comments.
+fn count_reviews<'a>(reviews: impl Iterator<Item = &'a serde_json::Value>) -> (u64, u64) {
+    reviews.fold((0, 0), |(reviews, comments), review| {
+        (
+            reviews + 1,
+            comments + review["comments"]["totalCount"].as_u64().unwrap_or(0),
+        )
+    })
+}
+
+/// Reviews given come from the contributions collection, which only lists reviews of other
+/// people's pull requests; reviews received from a search for the user's own pull requests.
+/// Both are capped at 100 per side, enough for a quarter for nearly everyone.
+pub fn get_review_balance(
+    username: &str,
+    token: &str,
+    today: NaiveDate,
+) -> Result<ReviewBalance, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let since = today - Duration::days(REVIEW_WINDOW_DAYS);
+    let from = since.and_time(NaiveTime::MIN).and_utc().to_rfc3339();
+    let query = format!(
+        r#"
+        query {{
+          user(login: "{0}") {{
+            contributionsCollection(from: "{1}") {{
+              pullRequestReviewContributions(first: 100) {{
+                nodes {{ pullRequestReview {{ comments {{ totalCount }} }} }}
+              }}
+            }}
+          }}
+          search(query: "author:{0} is:pr created:>={2}", type: ISSUE, first: 100) {{
+            nodes {{
+              ... on PullRequest {{
+                reviews(first: 50) {{
+                  nodes {{ author {{ login }} comments {{ totalCount }} }}
+                }}
+              }}
+            }}
+          }}
+        }}
+        "#,
+        username, from, since
+    );
+    let data = graphql_cached(&client, &query, token, Duration::hours(24))?;
+
+    let given = data["user"]["contributionsCollection"]["pullRequestReviewContributions"]["nodes"]
+        .as_array()
+        .ok_or("unexpected response for review contributions")?;
+    let (given_reviews, given_comments) =
+        count_reviews(given.iter().map(|node| &node["pullRequestReview"]));
+
+    let pull_requests = data["search"]["nodes"]
+        .as_array()
+        .ok_or("unexpected response for pull requests")?;
+    // The user's replies in their own review threads show up as reviews too.
+    let (received_reviews, received_comments) = count_reviews(
+        pull_requests
+            .iter()
+            .filter_map(|pr| pr["reviews"]["nodes"].as_array())
+            .flatten()
+            .filter(|review| review["author"]["login"].as_str() != Some(username)),
+    );
+
+    Ok(ReviewBalance {
+        since,
+        given_reviews,
+        given_comments,
+        received_reviews,
+        received_comments,
+    })
+}
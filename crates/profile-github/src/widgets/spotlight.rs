@@ -0,0 +1,77 @@
+use crate::github::get_json_cached;
+use crate::http::Client;
+use chrono::{Duration, NaiveDate};
+use profile_core::config::{SpotlightConfig, SpotlightOrder};
+use profile_core::history::History;
+use profile_core::widgets::spotlight::{week_start, Spotlight};
+use serde_json::Value;
+
+fn spotlight_from(repo: &Value) -> Spotlight {
+    Spotlight {
+        repo: repo["name"].as_str().unwrap_or("").to_string(),
+        url: repo["html_url"].as_str().unwrap_or("").to_string(),
+        description: repo["description"].as_str().map(str::to_string),
+        language: repo["language"].as_str().map(str::to_string),
+        stars: repo["stargazers_count"].as_u64().unwrap_or(0),
+    }
+}
+
+/// Picks this week's repository from the user's own (not forked, not archived) repositories,
+/// advancing the rotation kept in `history`.
+pub fn get_spotlight(
+    config: &SpotlightConfig,
+    username: &str,
+    token: &str,
+    history: &mut History,
+    today: NaiveDate,
+) -> Result<Spotlight, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let url = format!(
+        "https://api.github.com/users/{}/repos?type=owner&per_page=100",
+        username
+    );
+    let repos = get_json_cached(&client, &url, token, Duration::hours(6))?;
+    let mut repos: Vec<&Value> = repos
+        .as_array()
+        .ok_or("unexpected response for repositories")?
+        .iter()
+        .filter(|repo| !repo["fork"].as_bool().unwrap_or(false))
+        .filter(|repo| !repo["archived"].as_bool().unwrap_or(false))
+        .filter(|repo| {
+            let name = repo["name"].as_str().unwrap_or("");
+            !config.exclude.iter().any(|excluded| excluded == name)
+        })
+        .collect();
+
+    let name = |repo: &Value| repo["name"].as_str().unwrap_or("").to_string();
+    match config.order {
+        SpotlightOrder::Stars => repos.sort_by(|a, b| {
+            let stars = |repo: &Value| repo["stargazers_count"].as_u64().unwrap_or(0);
+            stars(b).cmp(&stars(a)).then_with(|| name(a).cmp(&name(b)))
+        }),
+        // RFC 3339 timestamps in UTC sort correctly as strings.
+        SpotlightOrder::Recent => repos.sort_by(|a, b| {
+            b["pushed_at"]
+                .as_str()
+                .cmp(&a["pushed_at"].as_str())
+                .then_with(|| name(a).cmp(&name(b)))
+        }),
+        SpotlightOrder::Name => repos.sort_by_key(|repo| name(repo).to_lowercase()),
+        // Listed repositories in the given order, unlisted ones never.
+        SpotlightOrder::Listed => {
+            repos.retain(|repo| config.repos.contains(&name(repo)));
+            repos.sort_by_key(|repo| config.repos.iter().position(|listed| *listed == name(repo)));
+        }
+    }
+
+    let candidates: Vec<String> = repos.iter().map(|repo| name(repo)).collect();
+    let chosen = history
+        .spotlight
+        .advance(week_start(today), &candidates)
+        .ok_or("no repositories to feature")?;
+    let repo = repos
+        .iter()
+        .find(|repo| name(repo) == chosen)
+        .ok_or("featured repository disappeared")?;
+    Ok(spotlight_from(repo))
+}
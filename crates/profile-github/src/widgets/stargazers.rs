@@ -0,0 +1,203 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::{DateTime, Duration, Timelike, Utc};
+use profile_core::config::StargazersConfig;
+use profile_core::widgets::stargazers::StargazerFacts;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Country names, each with the spellings people commonly use in their profile location.
+const COUNTRIES: [(&str, &[&str]); 48] = [
+    ("Argentina", &["argentina"]),
+    ("Australia", &["australia"]),
+    ("Austria", &["austria", "österreich", "vienna", "wien"]),
+    ("Bangladesh", &["bangladesh", "dhaka"]),
+    ("Belgium", &["belgium", "belgië", "belgique"]),
+    ("Brazil", &["brazil", "brasil", "são paulo", "sao paulo"]),
+    ("Canada", &["canada", "toronto", "vancouver", "montreal"]),
+    ("Chile", &["chile"]),
+    (
+        "China",
+        &[
+            "china", "beijing", "shanghai", "shenzhen", "hangzhou", "中国",
+        ],
+    ),
+    ("Colombia", &["colombia"]),
+    ("Czechia", &["czechia", "czech republic", "prague"]),
+    ("Denmark", &["denmark", "copenhagen"]),
+    ("Egypt", &["egypt", "cairo"]),
+    ("Finland", &["finland", "helsinki"]),
+    ("France", &["france", "paris"]),
+    (
+        "Germany",
+        &[
+            "germany",
+            "deutschland",
+            "berlin",
+            "munich",
+            "münchen",
+            "hamburg",
+        ],
+    ),
+    ("Greece", &["greece", "athens"]),
+    ("Hungary", &["hungary", "budapest"]),
+    (
+        "India",
+        &[
+            "india",
+            "bangalore",
+            "bengaluru",
+            "mumbai",
+            "delhi",
+            "hyderabad",
+            "pune",
+        ],
+    ),
+    ("Indonesia", &["indonesia", "jakarta"]),
+    ("Iran", &["iran", "tehran"]),
+    ("Ireland", &["ireland", "dublin"]),
+    ("Israel", &["israel", "tel aviv"]),
+    ("Italy", &["italy", "italia", "rome", "milan"]),
+    ("Japan", &["japan", "tokyo", "osaka", "日本"]),
+    ("Mexico", &["mexico", "méxico"]),
+    (
+        "Netherlands",
+        &["netherlands", "the netherlands", "amsterdam"],
+    ),
+    ("New Zealand", &["new zealand"]),
+    ("Nigeria", &["nigeria", "lagos"]),
+    ("Norway", &["norway", "oslo"]),
+    ("Pakistan", &["pakistan", "karachi", "lahore"]),
+    ("Philippines", &["philippines", "manila"]),
+    (
+        "Poland",
+        &["poland", "polska", "warsaw", "kraków", "krakow"],
+    ),
+    ("Portugal", &["portugal", "lisbon"]),
+    ("Romania", &["romania", "bucharest"]),
+    ("Russia", &["russia", "moscow", "saint petersburg"]),
+    ("Singapore", &["singapore"]),
+    ("South Africa", &["south africa"]),
+    ("South Korea", &["south korea", "korea", "seoul"]),
+    ("Spain", &["spain", "españa", "madrid", "barcelona"]),
+    ("Sweden", &["sweden", "stockholm"]),
+    (
+        "Switzerland",
+        &["switzerland", "zurich", "zürich", "geneva"],
+    ),
+    ("Taiwan", &["taiwan", "taipei"]),
+    ("Turkey", &["turkey", "türkiye", "istanbul", "ankara"]),
+    ("Ukraine", &["ukraine", "kyiv", "kiev"]),
+    (
+        "United Kingdom",
+        &[
+            "united kingdom",
+            "uk",
+            "england",
+            "scotland",
+            "wales",
+            "london",
+        ],
+    ),
+    (
+        "United States",
+        &[
+            "united states",
+            "usa",
+            "us",
+            "u.s.",
+            "new york",
+            "nyc",
+            "san francisco",
+            "seattle",
+            "california",
+            "ca",
+            "texas",
+            "tx",
+            "ny",
+            "wa",
+        ],
+    ),
+    (
+        "Vietnam",
+        &["vietnam", "viet nam", "hanoi", "ho chi minh city"],
+    ),
+];
+
+/// Matches a free-text profile location against the country table, most specific part last
+/// ("Berlin, Germany" and "Munich" both resolve to Germany).
+fn location_to_country(location: &str) -> Option<&'static str> {
+    location
+        .split([',', '/', '|', '·'])
+        .rev()
+        .map(|part| part.trim().to_lowercase())
+        .find_map(|part| {
+            COUNTRIES
+                .iter()
+                .find(|(_, aliases)| aliases.contains(&part.as_str()))
+                .map(|(country, _)| *country)
+        })
+}
+
+pub fn get_stargazer_facts(
+    config: &StargazersConfig,
+    username: &str,
+    token: &str,
+) -> Result<StargazerFacts, Box<dyn std::error::Error>> {
+    // One query covers every repo's most recent stargazers, instead of a profile request each.
+    let query = format!(
+        r#"
+        query {{
+          user(login: "{}") {{
+            repositories(first: {}, ownerAffiliations: OWNER, isFork: false, orderBy: {{field: STARGAZERS, direction: DESC}}) {{
+              nodes {{
+                stargazers(last: {}, orderBy: {{field: STARRED_AT, direction: ASC}}) {{
+                  edges {{
+                    starredAt
+                    node {{ location }}
+                  }}
+                }}
+              }}
+            }}
+          }}
+        }}
+        "#,
+        username, config.repos, config.per_repo
+    );
+    let data = graphql_cached(&Client::new(), &query, token, Duration::hours(24))?;
+
+    let edges: Vec<&serde_json::Value> = data["user"]["repositories"]["nodes"]
+        .as_array()
+        .ok_or("unexpected response for stargazers")?
+        .iter()
+        .filter_map(|repo| repo["stargazers"]["edges"].as_array())
+        .flatten()
+        .collect();
+
+    let countries: BTreeSet<&str> = edges
+        .iter()
+        .filter_map(|edge| location_to_country(edge["node"]["location"].as_str()?))
+        .collect();
+
+    let mut hours: BTreeMap<u32, usize> = BTreeMap::new();
+    for edge in &edges {
+        if let Some(starred_at) = edge["starredAt"]
+            .as_str()
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+        {
+            *hours
+                .entry(starred_at.with_timezone(&Utc).hour())
+                .or_insert(0) += 1;
+        }
+    }
+    // Ties go to the earliest hour so the line doesn't flip between runs.
+    let busiest_hour = hours
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+        .map(|(hour, _)| *hour);
+
+    Ok(StargazerFacts {
+        sampled: edges.len(),
+        countries: countries.len(),
+        busiest_hour,
+    })
+}
@@ -0,0 +1,69 @@
+use crate::github::graphql_cached;
+use crate::http::Client;
+use chrono::{DateTime, Datelike, Duration};
+use std::collections::BTreeMap;
+
+/// Upper bound on pages of 100 repositories fetched for the timeline.
+const MAX_PAGES: usize = 10;
+
+/// Counts the user's own (non-fork) repositories by the year they were created.
+pub fn get_repos_per_year(
+    username: &str,
+    token: &str,
+) -> Result<BTreeMap<i32, u64>, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let mut per_year = BTreeMap::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..MAX_PAGES {
+        let after = cursor
+            .as_ref()
+            .map(|cursor| format!(", after: \"{}\"", cursor))
+            .unwrap_or_default();
+        let query = format!(
+            r#"
+            query {{
+              user(login: "{}") {{
+                repositories(first: 100, ownerAffiliations: OWNER, isFork: false{}) {{
+                  pageInfo {{ hasNextPage endCursor }}
+                  nodes {{ createdAt }}
+                }}
+              }}
+            }}
+            "#,
+            username, after
+        );
+        let data = graphql_cached(&client, &query, token, Duration::hours(24))?;
+        let repositories = &data["user"]["repositories"];
+
+        for repo in repositories["nodes"]
+            .as_array()
+            .ok_or("unexpected response for repositories")?
+        {
+            if let Some(created_at) = repo["createdAt"]
+                .as_str()
+                .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            {
+                *per_year.entry(created_at.year()).or_insert(0) += 1;
+            }
+        }
+
+        if !repositories["pageInfo"]["hasNextPage"]
+            .as_bool()
+            .unwrap_or(false)
+        {
+            break;
+        }
+        cursor = repositories["pageInfo"]["endCursor"]
+            .as_str()
+            .map(String::from);
+    }
+
+    // Years without a new repo still get a row, so gaps stay visible.
+    if let (Some(&first), Some(&last)) = (per_year.keys().next(), per_year.keys().last()) {
+        for year in first..=last {
+            per_year.entry(year).or_insert(0);
+        }
+    }
+    Ok(per_year)
+}
@@ -0,0 +1,64 @@
+use crate::github::get_json_cached;
+use crate::http::Client;
+use chrono::{Duration, Utc};
+use profile_core::config::TriageConfig;
+use profile_core::widgets::responsiveness::{first_reaction_delay, parse_time};
+use profile_core::widgets::triage::TriageVelocity;
+
+/// Timeline events that count as an issue having been triaged.
+const TRIAGE_EVENTS: [&str; 4] = ["labeled", "assigned", "milestoned", "closed"];
+
+/// Per repository, the issues opened in the last `config.days` days (older than
+/// `config.hours`, so each had its full chance) that a maintainer labeled, assigned,
+/// milestoned or closed within `config.hours`.
+pub fn get_triage_velocity(
+    config: &TriageConfig,
+    token: &str,
+) -> Result<Vec<TriageVelocity>, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let now = Utc::now();
+    let deadline = Duration::hours(config.hours);
+    let window_start = now - Duration::days(config.days);
+
+    let mut velocities = Vec::new();
+    for repo in &config.repos {
+        let url = format!(
+            "https://api.github.com/repos/{}/issues?state=all&sort=created&direction=desc&per_page=100",
+            repo
+        );
+        let recent = get_json_cached(&client, &url, token, Duration::hours(6))?;
+        let mut velocity = TriageVelocity {
+            repo: repo.clone(),
+            triaged: 0,
+            issues: 0,
+        };
+        for issue in recent
+            .as_array()
+            .ok_or("unexpected response for recent issues")?
+            .iter()
+            .filter(|issue| issue.get("pull_request").is_none())
+        {
+            let Some(opened) = parse_time(&issue["created_at"]) else {
+                continue;
+            };
+            if opened < window_start || now - opened < deadline {
+                continue;
+            }
+            let timeline_url = format!(
+                "https://api.github.com/repos/{}/issues/{}/timeline?per_page=100",
+                repo,
+                issue["number"].as_u64().unwrap_or(0)
+            );
+            // Only the first `hours` of the timeline matter, and they are over by now.
+            let timeline = get_json_cached(&client, &timeline_url, token, Duration::days(30))?;
+            velocity.issues += 1;
+            if first_reaction_delay(issue, &timeline, &TRIAGE_EVENTS)
+                .is_some_and(|delay| delay <= deadline)
+            {
+                velocity.triaged += 1;
+            }
+        }
+        velocities.push(velocity);
+    }
+    Ok(velocities)
+}
@@ -0,0 +1,55 @@
+//! The core fetches failing with an error the run can show as a placeholder, not a panic.
+
+use profile_core::cache::bypass_cache;
+use profile_core::config::{LanguagesConfig, SamplingConfig};
+use profile_github::fetch::{get_all_languages, get_github_stats};
+use profile_github::http::{install, Client, HttpResponse, Replayer, StatusCode};
+use std::sync::Arc;
+
+fn answer(url: &str, status: StatusCode, body: &str) -> (String, HttpResponse) {
+    let request = Client::new().get(url).build().unwrap();
+    let response = HttpResponse {
+        url: url.to_string(),
+        status,
+        headers: Vec::new(),
+        body: body.as_bytes().to_vec(),
+    };
+    (request.key(), response)
+}
+
+fn languages(username: &str, token: &str) -> Result<Vec<(String, f64)>, String> {
+    get_all_languages(
+        username,
+        token,
+        &LanguagesConfig::default(),
+        &SamplingConfig::default(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// One test, as the client can only be installed once per process.
+#[test]
+fn failed_fetches_are_errors() {
+    bypass_cache(true);
+    install(Arc::new(Replayer::new(vec![
+        answer(
+            "https://api.github.com/users/nobody/repos?per_page=100&page=1",
+            StatusCode::NOT_FOUND,
+            r#"{"message": "Not Found"}"#,
+        ),
+        answer(
+            "https://api.github.com/users/octocat/repos?per_page=100&page=1",
+            StatusCode::FORBIDDEN,
+            r#"{"message": "API rate limit exceeded"}"#,
+        ),
+    ])))
+    .unwrap();
+
+    assert!(languages("nobody", "token").is_err());
+    assert_eq!(
+        languages("octocat", "").unwrap_err(),
+        "unauthenticated rate limit reached, languages unavailable"
+    );
+    // Nothing was recorded for the stats query.
+    assert!(get_github_stats("nobody", "token").is_err());
+}
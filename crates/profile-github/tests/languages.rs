@@ -0,0 +1,97 @@
+//! The languages section over a fixed set of byte counts, compared with `tests/golden`, so the
+//! same repositories always produce the same README. After an intended change, rewrite the
+//! snapshot with `UPDATE_GOLDEN=1 cargo test`.
+
+use profile_core::builder::ProfileBuilder;
+use profile_core::config::LanguagesConfig;
+use profile_core::document::Block;
+use profile_core::profile::format_languages_compact;
+use profile_github::fetch::language_shares;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn tests_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+fn load_bytes() -> BTreeMap<String, u64> {
+    let path = tests_dir().join("fixtures").join("languages.json");
+    let json = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("invalid {}: {}", path.display(), e))
+}
+
+fn languages_config() -> LanguagesConfig {
+    LanguagesConfig {
+        aliases: BTreeMap::from([("Jupyter Notebook".to_string(), "Python".to_string())]),
+        ..LanguagesConfig::default()
+    }
+}
+
+fn render(bytes: &BTreeMap<String, u64>) -> String {
+    let shares = language_shares(bytes, &languages_config());
+    ProfileBuilder::new("octocat")
+        .with_header(Vec::new())
+        .with_languages(&shares)
+        .build()
+        .markdown
+}
+
+#[test]
+fn languages_match_golden() {
+    let actual = render(&load_bytes());
+    let path = tests_dir().join("golden").join("languages.md");
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}; create it with UPDATE_GOLDEN=1",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "{} no longer matches the output; rerun with UPDATE_GOLDEN=1 if the change is intended\n\
+         --- expected\n{}\n--- actual\n{}",
+        path.display(),
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn reruns_are_byte_identical() {
+    let bytes = load_bytes();
+    assert_eq!(render(&bytes), render(&bytes));
+}
+
+#[test]
+fn equal_shares_are_alphabetical() {
+    let shares = language_shares(&load_bytes(), &languages_config());
+    let names: Vec<&str> = shares.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["Rust", "Python", "Go", "TypeScript", "C", "Shell", "Other"]
+    );
+    assert_eq!(shares[2].1, shares[3].1);
+}
+
+#[test]
+fn compact_languages_escape_aliases() {
+    let languages_config = LanguagesConfig {
+        aliases: BTreeMap::from([("Rust".to_string(), "<Systems> & Co".to_string())]),
+        ..LanguagesConfig::default()
+    };
+    let shares = language_shares(&load_bytes(), &languages_config);
+    match format_languages_compact(&shares) {
+        Block::Text { html, .. } => {
+            assert!(html.contains("<b>&lt;Systems&gt; &amp; Co</b>"), "{}", html);
+            assert!(!html.contains("<Systems>"), "{}", html);
+        }
+        _ => panic!("compact languages are text"),
+    }
+}
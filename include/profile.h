@@ -0,0 +1,25 @@
+#ifndef M4STER_SLAVE_PROFILE_H
+#define M4STER_SLAVE_PROFILE_H
+
+/* C ABI of the README renderers, built with `cargo build --release --features ffi`.
+ * Every returned string is owned by the caller and must be released with
+ * profile_string_free(); NULL means the input was rejected (details on stderr). */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Preview markdown from metrics JSON (languages, stats, activities, calendar)
+ * and the contents of a profile.toml. */
+char *profile_render_preview(const char *metrics_json, const char *config_toml);
+
+/* SVG line chart from a JSON array of ["YYYY-MM-DD", value] pairs. */
+char *profile_render_chart(const char *title, const char *series_json);
+
+void profile_string_free(char *output);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
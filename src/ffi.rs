@@ -0,0 +1,87 @@
+use crate::profile::{render_chart_json, render_preview_json};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, UnwindSafe};
+use std::ptr;
+
+/// Converts a rendering result into a string owned by the caller, or null on error (with the
+/// message on stderr, since the renderers already report anomalies there).
+fn into_c_string(result: Result<String, String>) -> *mut c_char {
+    match result.and_then(|output| CString::new(output).map_err(|e| e.to_string())) {
+        Ok(output) => output.into_raw(),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Runs `render` and converts its result with [`into_c_string`]. A panic is caught and treated
+/// as an error, since unwinding into the caller would abort it.
+fn render_to_c_string(render: impl FnOnce() -> Result<String, String> + UnwindSafe) -> *mut c_char {
+    let result = catch_unwind(render).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_string());
+        Err(format!("renderer panicked: {}", message))
+    });
+    into_c_string(result)
+}
+
+/// # Safety
+///
+/// `pointer` must be null or a valid NUL-terminated string.
+unsafe fn to_str<'a>(pointer: *const c_char) -> Result<&'a str, String> {
+    if pointer.is_null() {
+        return Err("null string argument".to_string());
+    }
+    CStr::from_ptr(pointer).to_str().map_err(|e| e.to_string())
+}
+
+/// Renders the preview markdown from metrics JSON and `profile.toml` contents.
+///
+/// Returns null on error; a non-null result must be released with [`profile_string_free`].
+///
+/// # Safety
+///
+/// Both arguments must be valid NUL-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn profile_render_preview(
+    metrics_json: *const c_char,
+    config_toml: *const c_char,
+) -> *mut c_char {
+    render_to_c_string(|| {
+        to_str(metrics_json).and_then(|metrics| render_preview_json(metrics, to_str(config_toml)?))
+    })
+}
+
+/// Renders an SVG line chart from a JSON array of `["YYYY-MM-DD", value]` pairs.
+///
+/// Returns null on error; a non-null result must be released with [`profile_string_free`].
+///
+/// # Safety
+///
+/// Both arguments must be valid NUL-terminated UTF-8 strings.
+#[no_mangle]
+pub unsafe extern "C" fn profile_render_chart(
+    title: *const c_char,
+    series_json: *const c_char,
+) -> *mut c_char {
+    render_to_c_string(|| {
+        to_str(title).and_then(|title| render_chart_json(title, to_str(series_json)?))
+    })
+}
+
+/// Frees a string returned by this library.
+///
+/// # Safety
+///
+/// `output` must be null or a pointer returned by one of the `profile_render_*` functions that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn profile_string_free(output: *mut c_char) {
+    if !output.is_null() {
+        drop(CString::from_raw(output));
+    }
+}
@@ -0,0 +1,12 @@
+//! Bindings of the renderers for other languages: a C ABI, WebAssembly and a Python module.
+//! The renderers themselves are re-exported from `profile-core`, so Rust callers can depend on
+//! either crate.
+
+pub use profile_core::*;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
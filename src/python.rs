@@ -0,0 +1,26 @@
+// The `#[pyfunction]` wrappers pyo3 0.22 generates convert `PyResult` into itself, which
+// `clippy::useless_conversion` flags at each function below.
+#![allow(clippy::useless_conversion)]
+
+use crate::profile::{render_chart_json, render_preview_json};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// `render_preview(metrics_json, config_toml) -> str`, raising `ValueError` on bad input.
+#[pyfunction]
+fn render_preview(metrics_json: &str, config_toml: &str) -> PyResult<String> {
+    render_preview_json(metrics_json, config_toml).map_err(PyValueError::new_err)
+}
+
+/// `render_chart(title, series_json) -> str` with an SVG document.
+#[pyfunction]
+fn render_chart(title: &str, series_json: &str) -> PyResult<String> {
+    render_chart_json(title, series_json).map_err(PyValueError::new_err)
+}
+
+#[pymodule]
+fn m4ster_slave_readme_update(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(render_preview, module)?)?;
+    module.add_function(wrap_pyfunction!(render_chart, module)?)?;
+    Ok(())
+}
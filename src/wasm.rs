@@ -0,0 +1,14 @@
+use crate::profile::{render_chart_json, render_preview_json};
+use wasm_bindgen::prelude::*;
+
+/// Playground entry point: renders the preview from pasted metrics JSON (a `ProfileData`) and
+/// the contents of a `profile.toml`.
+#[wasm_bindgen]
+pub fn preview(metrics_json: &str, config_toml: &str) -> Result<String, JsValue> {
+    render_preview_json(metrics_json, config_toml).map_err(JsValue::from)
+}
+
+#[wasm_bindgen]
+pub fn chart(title: &str, series_json: &str) -> Result<String, JsValue> {
+    render_chart_json(title, series_json).map_err(JsValue::from)
+}